@@ -0,0 +1,14 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec4 ourColor;
+in vec2 TexCoord;
+
+uniform sampler2D character;
+uniform vec4 color;
+
+void main()
+{
+    vec4 sampled = vec4(1.0, 1.0, 1.0, texture(character, TexCoord).r);
+    FragColor = color * sampled;
+}
\ No newline at end of file
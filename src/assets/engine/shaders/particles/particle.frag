@@ -0,0 +1,13 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec4 ourColor;
+in vec2 TexCoord;
+
+uniform sampler2D ourTexture;
+
+// particle texture is tinted by colour of particle
+void main()
+{
+    FragColor = texture(ourTexture, TexCoord) * ourColor;
+}
@@ -0,0 +1,33 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec2 TexCoord;
+
+uniform sampler2D screenTexture;
+uniform vec2 resolution;
+uniform float threshold = 0.7;
+uniform float intensity = 1.2;
+
+const int RADIUS = 4;
+
+vec3 bright_part(vec2 uv) {
+    vec3 clr = texture(screenTexture, uv).rgb;
+    float luminance = dot(clr, vec3(0.2126, 0.7152, 0.0722));
+    return luminance > threshold ? clr : vec3(0.0);
+}
+
+// single pass approximation - bright pixels are blurred with box filter and added on top of the scene
+void main()
+{
+    vec2 texel = 1.0 / resolution;
+    vec3 glow = vec3(0.0);
+    for (int x = -RADIUS; x <= RADIUS; x++) {
+        for (int y = -RADIUS; y <= RADIUS; y++) {
+            glow += bright_part(TexCoord + vec2(x, y) * texel * 2.0);
+        }
+    }
+    glow /= float((2 * RADIUS + 1) * (2 * RADIUS + 1));
+
+    vec4 sampled = texture(screenTexture, TexCoord);
+    FragColor = vec4(sampled.rgb + glow * intensity, sampled.a);
+}
@@ -0,0 +1,31 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec2 TexCoord;
+
+uniform sampler2D screenTexture;
+uniform vec2 resolution;
+uniform float timeMillis;
+uniform float curvature = 6.0;
+uniform float scanlineStrength = 0.25;
+
+vec2 curve(vec2 uv) {
+    uv = uv * 2.0 - 1.0;
+    vec2 offset = abs(uv.yx) / curvature;
+    uv = uv + uv * offset * offset;
+    return uv * 0.5 + 0.5;
+}
+
+void main()
+{
+    vec2 uv = curve(TexCoord);
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    vec4 sampled = texture(screenTexture, uv);
+    float scanline = sin((uv.y * resolution.y + timeMillis * 0.01) * 3.14159) * 0.5 + 0.5;
+    vec3 clr = sampled.rgb * (1.0 - scanlineStrength * scanline);
+    FragColor = vec4(clr, sampled.a);
+}
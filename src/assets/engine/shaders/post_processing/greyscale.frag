@@ -0,0 +1,13 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec2 TexCoord;
+
+uniform sampler2D screenTexture;
+
+void main()
+{
+    vec4 sampled = texture(screenTexture, TexCoord);
+    float luminance = dot(sampled.rgb, vec3(0.2126, 0.7152, 0.0722));
+    FragColor = vec4(vec3(luminance), sampled.a);
+}
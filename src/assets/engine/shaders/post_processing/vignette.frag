@@ -0,0 +1,16 @@
+#version 330 core
+out vec4 FragColor;
+
+in vec2 TexCoord;
+
+uniform sampler2D screenTexture;
+uniform float radius = 0.75;
+uniform float softness = 0.45;
+
+void main()
+{
+    vec4 sampled = texture(screenTexture, TexCoord);
+    float dist = distance(TexCoord, vec2(0.5, 0.5));
+    float vignette = smoothstep(radius, radius - softness, dist);
+    FragColor = vec4(sampled.rgb * vignette, sampled.a);
+}
@@ -1,22 +1,39 @@
+use std::f32::consts::FRAC_PI_2;
 use std::rc::Rc;
+use std::time::Duration;
 
 use sdl2::event::Event;
 
-use crate::chess::chessboard::Chessboard;
+use crate::chess::chessboard::{Chessboard, DRAGGED_PIECE_ORDER};
 use crate::chess::infrastructure::Side;
-use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::colour::{BLUE, Colour, GREEN, RED, TRANSPARENT, WHITE};
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
 use crate::engine::api::engine_utilities::EngineUtilities;
 use crate::engine::api::maths::rectangle::Rectangle;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::particles::{Curve, ParticleEmitter, ParticleEmitterConfig};
+use crate::engine::api::post_processing::{BuiltInPass, PostProcessing};
 use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::resource_manager::ResourceManager;
+use crate::engine::api::scene_node::SceneNode;
 use crate::engine::api::texture::{Sprite};
 use crate::engine::opengl_context::OpenglContext;
 use crate::engine::rendering::material::Material;
 
+// game over screen goes over everything on the chessboard
+const CONFETTI_ORDER: i32 = DRAGGED_PIECE_ORDER + 1;
+const WIN_BANNER_ORDER: i32 = DRAGGED_PIECE_ORDER + 2;
+
 pub struct ChessGame {
-    chessboard: Chessboard,
+    // pieces and fields are positioned relative to the board, moving this node moves all of them
+    chessboard: SceneNode<Chessboard>,
     black_win_banner: Rectangle<TexturedVertexDataLayout>,
     white_win_banner: Rectangle<TexturedVertexDataLayout>,
+    // falls behind win banner once game is over
+    confetti: ParticleEmitter,
+    // applied only to camera showing this game
+    post_processing: Rc<PostProcessing>,
 }
 
 impl ChessGame {
@@ -27,6 +44,8 @@ impl ChessGame {
         chessboard.init_pieces(Rc::clone(&res_manager));
 
         let texture_material = res_manager.fetch_shader_material("chess/shaders/texture");
+        let post_processing = Rc::new(PostProcessing::new());
+        post_processing.register_built_in_passes(res_manager.as_ref());
 
         let white_win_banner = ChessGame::create_win_banner(
             res_manager.fetch_sprite("chess/textures/white_win_banner.png"),
@@ -39,12 +58,31 @@ impl ChessGame {
         );
 
         ChessGame {
-            chessboard,
+            chessboard: SceneNode::new(Transform::new(), chessboard),
             black_win_banner,
             white_win_banner,
+            confetti: ChessGame::create_confetti(res_manager.as_ref()),
+            post_processing,
         }
     }
 
+    pub fn get_post_processing(&self) -> Rc<PostProcessing> {
+        Rc::clone(&self.post_processing)
+    }
+
+    fn create_confetti(res_manager: &dyn ResourceManager) -> ParticleEmitter {
+        let config = ParticleEmitterConfig::new_default(res_manager)
+            .with_spawn_rate(60.0)
+            .with_spawn_area(glam::vec2(4.0, 0.0))
+            .with_lifetime(Duration::from_secs(4), Duration::from_secs(6))
+            .with_velocity_cone(-FRAC_PI_2, 0.5, 0.5, 1.5)
+            .with_gravity(glam::vec2(0.0, -0.5))
+            .with_size_over_lifetime(Curve::new(0.15))
+            .with_start_colours(vec![RED, GREEN, BLUE, Colour::new_f(1.0, 0.85, 0.0, 1.0), WHITE])
+            .with_colour_over_lifetime(Curve::new(WHITE).with_key(0.8, WHITE).with_key(1.0, TRANSPARENT));
+        ParticleEmitter::new(config, glam::vec3(4.0, 8.5, 0.0))
+    }
+
     fn create_win_banner(sprite: Sprite, material: Material) -> Rectangle<TexturedVertexDataLayout> {
         Rectangle::new_textured(
             &glam::vec3(2.0, 2.0, 0.0),
@@ -57,39 +95,68 @@ impl ChessGame {
 
 impl Drawable for ChessGame {
     fn render(&mut self, render_util: &RenderUtil) {
-        match self.chessboard.get_winner().clone() {
+        match self.chessboard.get_content().get_winner().clone() {
             None => { self.chessboard.render(render_util) }
             Some(winning_side) => {
                 self.chessboard.render(render_util);
+                let render_order = render_util.get_render_order();
+                self.confetti.render(&render_util.with_render_order(render_order.offset_by(CONFETTI_ORDER)));
+                let banner_render_util = render_util.with_render_order(render_order.offset_by(WIN_BANNER_ORDER));
                 match winning_side {
-                    Side::BLACK => { self.black_win_banner.render(render_util) }
-                    Side::WHITE => { self.white_win_banner.render(render_util) }
+                    Side::BLACK => { self.black_win_banner.render(&banner_render_util) }
+                    Side::WHITE => { self.white_win_banner.render(&banner_render_util) }
                 }
             }
         }
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
+    fn update(&mut self, update_context: &UpdateContext) {
+        // game over screen is greyed out
+        let game_over = self.chessboard.get_content().get_winner().is_some();
+        if self.post_processing.is_enabled(BuiltInPass::GREYSCALE.get_name()) != game_over {
+            self.post_processing.set_enabled(BuiltInPass::GREYSCALE.get_name(), game_over);
+        }
+        if game_over && !self.confetti.is_emitting() {
+            self.confetti.start_emitting();
+        }
+        self.confetti.update(update_context);
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
         match context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &update_context.get_camera_config()) {
-            None => {}
+            None => { EventResult::IGNORED }
             Some(world_mouse_position) => {
+                let board_mouse_position = self.chessboard.to_local_point(&world_mouse_position);
+                let chessboard = self.chessboard.get_content_mut();
+                // chess only cares about mouse while piece is grabbed
                 match event {
                     sdl2::event::Event::MouseButtonDown { .. } => {
-                        self.chessboard.handle_start_piece_dragging_attempt(&world_mouse_position);
+                        chessboard.handle_start_piece_dragging_attempt(&board_mouse_position);
+                        EventResult::consumed_if(chessboard.is_dragging_piece())
                     }
 
                     sdl2::event::Event::MouseButtonUp { .. } => {
-                        self.chessboard.handle_piece_drop_attempt(&world_mouse_position, Rc::clone(&update_context.get_engine_utilities().get_resource_manager()));
+                        let was_dragging = chessboard.is_dragging_piece();
+                        chessboard.handle_piece_drop_attempt(&board_mouse_position, Rc::clone(&update_context.get_engine_utilities().get_resource_manager()));
+                        EventResult::consumed_if(was_dragging)
                     }
 
                     sdl2::event::Event::MouseMotion { .. } => {
-                        self.chessboard.handle_piece_dragging_attempt(&world_mouse_position);
+                        chessboard.handle_piece_dragging_attempt(&board_mouse_position);
+                        EventResult::consumed_if(chessboard.is_dragging_piece())
                     }
 
-                    _ => {}
+                    _ => { EventResult::IGNORED }
                 }
             }
         }
     }
+
+    fn is_under_mouse(&self, context: &OpenglContext, update_context: &UpdateContext) -> bool {
+        match context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &update_context.get_camera_config()) {
+            None => false,
+            Some(world_mouse_position) => self.chessboard.get_content().contains_point(&self.chessboard.to_local_point(&world_mouse_position))
+        }
+    }
 }
 
@@ -8,9 +8,16 @@ use crate::chess::piece::{Piece, PieceFactory, PieceLogic};
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::rectangle::Rectangle;
 use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::maths::shapes_common::Area;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::api::resource_manager::ResourceManager;
 
+// relative to render order of the chessboard, parents drawing over chessboard must use bigger offset
+const BOARD_ORDER: i32 = 0;
+const FIELD_ORDER: i32 = 1;
+const PIECE_ORDER: i32 = 2;
+pub const DRAGGED_PIECE_ORDER: i32 = 3;
+
 pub struct Chessboard {
     board: Rectangle<TexturedVertexDataLayout>,
     pieces: Vec<Piece>,
@@ -228,6 +235,15 @@ impl Chessboard {
         self.global_game_state.get_winner()
     }
 
+    // point is in space of the chessboard, same as for dragging
+    pub fn contains_point(&self, point: &glam::Vec3) -> bool {
+        self.board.contains_point(&(point.x, point.y))
+    }
+
+    pub fn is_dragging_piece(&self) -> bool {
+        self.dragged_piece.is_some()
+    }
+
     fn is_game_over(&self) -> bool {
         self.global_game_state.get_winner().is_some()
     }
@@ -333,10 +349,20 @@ impl Chessboard {
 }
 
 impl Drawable for Chessboard {
+    // draw order does not depend on order of pieces in vector, dragged piece is always on top
     fn render(&mut self, render_util: &RenderUtil) {
-        self.board.render(render_util);
-        self.fields.iter_mut().for_each(|row| row.iter_mut().for_each(|field| field.render(render_util)));
-        self.pieces.iter_mut().for_each(|piece| { piece.render(render_util) });
+        let render_order = render_util.get_render_order();
+        self.board.render(&render_util.with_render_order(render_order.offset_by(BOARD_ORDER).with_opaque(true)));
+        let fields_render_util = render_util.with_render_order(render_order.offset_by(FIELD_ORDER));
+        self.fields.iter_mut().for_each(|row| row.iter_mut().for_each(|field| field.render(&fields_render_util)));
+        let pieces_render_util = render_util.with_render_order(render_order.offset_by(PIECE_ORDER));
+        let dragged_piece_render_util = render_util.with_render_order(render_order.offset_by(DRAGGED_PIECE_ORDER));
+        for (idx, piece) in self.pieces.iter_mut().enumerate() {
+            match self.dragged_piece == Some(idx) {
+                true => piece.render(&dragged_piece_render_util),
+                false => piece.render(&pieces_render_util)
+            }
+        }
     }
 }
 
@@ -5,4 +5,7 @@ pub mod rendering;
 mod renderer;
 pub mod opengl_context;
 pub mod glam_utils;
-mod resources;
\ No newline at end of file
+mod resources;
+pub mod timestep;
+pub mod replay;
+mod debug_view;
\ No newline at end of file
@@ -12,3 +12,16 @@ pub mod game_api;
 pub mod engine_utilities;
 pub mod text_game_object;
 pub mod countdown_timer;
+pub mod post_processing;
+pub mod input;
+pub mod random;
+pub mod scene_node;
+pub mod ecs;
+pub mod collision;
+pub mod physics;
+pub mod animation;
+pub mod particles;
+pub mod camera_controller;
+pub mod ui;
+pub mod console;
+pub mod debug_overlay;
@@ -0,0 +1,3 @@
+pub mod easing;
+pub mod tween;
+pub mod animated_sprite;
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::maths::rectangle::Rectangle;
+use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::texture::Sprite;
+use crate::engine::rendering::material::Material;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipPlayback {
+    LOOP,
+    // plays forward then backward, end frames are not repeated
+    PING_PONG,
+    // stops at last frame, `AnimatedSprite::just_finished` reports the end
+    ONE_SHOT,
+}
+
+/**
+Range of spritesheet frames played with given durations. Frames are numbered row by row,
+frame `row * n_cols + col` is the cell that `Rectangle::new_from_spritesheet` shows for `row` and `col`.
+```
+let walk = AnimationClip::new(0, 3, Duration::from_millis(100), ClipPlayback::LOOP)
+    .with_frame_duration(3, Duration::from_millis(300));
+```
+ */
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    frames: Vec<u32>,
+    frame_durations: Vec<Duration>,
+    playback: ClipPlayback,
+}
+
+impl AnimationClip {
+    // frames from first to last inclusive, last can be smaller than first to play them backwards
+    pub fn new(first_frame: u32, last_frame: u32, frame_duration: Duration, playback: ClipPlayback) -> AnimationClip {
+        assert_frame_duration(&frame_duration);
+        let frames: Vec<u32> = match first_frame <= last_frame {
+            true => (first_frame..=last_frame).collect(),
+            false => (last_frame..=first_frame).rev().collect()
+        };
+        AnimationClip {
+            frame_durations: vec![frame_duration; frames.len()],
+            frames,
+            playback,
+        }
+    }
+
+    // frame is given as spritesheet frame number, not position in clip
+    pub fn with_frame_duration(mut self, frame: u32, duration: Duration) -> AnimationClip {
+        assert_frame_duration(&duration);
+        match self.frames.iter().position(|clip_frame| *clip_frame == frame) {
+            None => panic!("Frame {} is not part of animation clip {:?}", frame, self.frames),
+            Some(idx) => self.frame_durations[idx] = duration
+        }
+        self
+    }
+
+    pub fn get_playback(&self) -> &ClipPlayback {
+        &self.playback
+    }
+
+    pub fn get_num_frames(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+fn assert_frame_duration(duration: &Duration) {
+    if duration.is_zero() {
+        panic!("Animation clip frames must last longer than zero");
+    }
+}
+
+struct ClipPlayer {
+    clip_name: String,
+    // position in clip frames, not spritesheet frame number
+    frame_idx: usize,
+    time_in_frame: Duration,
+    backwards: bool,
+    finished: bool,
+}
+
+/**
+Textured rectangle that animates through cells of its spritesheet by playing named clips.
+Only texture coordinates of rectangle vertices change, nothing is uploaded to gpu again.
+```
+let sheet = resource_manager.fetch_sprite("pacman/textures/pacman.png").as_spritesheet(1, 4);
+let mut pacman = AnimatedSprite::new(&glam::vec3(0.0, 0.0, 0.0), &glam::vec2(1.0, 1.0), material, sheet);
+pacman.add_clip("chomp", AnimationClip::new(0, 3, Duration::from_millis(80), ClipPlayback::PING_PONG));
+pacman.play("chomp");
+```
+ */
+pub struct AnimatedSprite {
+    rectangle: Rectangle<TexturedVertexDataLayout>,
+    sprite: Sprite,
+    clips: HashMap<String, AnimationClip>,
+    player: Option<ClipPlayer>,
+    finished_in_last_update: bool,
+}
+
+impl AnimatedSprite {
+    // shows first cell of spritesheet until a clip is played
+    pub fn new(bottom_left: &glam::Vec3, size: &glam::Vec2, material: Material, sprite: Sprite) -> AnimatedSprite {
+        let rectangle = Rectangle::new_from_spritesheet(bottom_left, size, material, sprite.clone(), 0, 0);
+        AnimatedSprite {
+            rectangle,
+            sprite,
+            clips: HashMap::new(),
+            player: None,
+            finished_in_last_update: false,
+        }
+    }
+
+    pub fn add_clip(&mut self, name: &str, clip: AnimationClip) {
+        let (n_rows, n_cols) = self.sprite.get_spritesheet_dimensions();
+        if let Some(frame) = clip.frames.iter().find(|frame| **frame >= n_rows * n_cols) {
+            panic!("Clip {} uses frame {} but spritesheet has only {} frames", name, frame, n_rows * n_cols);
+        }
+        self.clips.insert(name.to_string(), clip);
+    }
+
+    // playing clip that is already playing does not restart it
+    pub fn play(&mut self, name: &str) {
+        if self.get_current_clip() == Some(name) {
+            return;
+        }
+        self.restart(name);
+    }
+
+    pub fn restart(&mut self, name: &str) {
+        if !self.clips.contains_key(name) {
+            panic!("Animated sprite has no clip named {}", name);
+        }
+        self.player = Some(ClipPlayer {
+            clip_name: name.to_string(),
+            frame_idx: 0,
+            time_in_frame: Duration::ZERO,
+            backwards: false,
+            finished: false,
+        });
+        self.finished_in_last_update = false;
+        self.show_current_frame();
+    }
+
+    // current frame stays visible
+    pub fn stop(&mut self) {
+        self.player = None;
+    }
+
+    pub fn get_current_clip(&self) -> Option<&str> {
+        self.player.as_ref().map(|player| player.clip_name.as_str())
+    }
+
+    // spritesheet frame number that is shown now
+    pub fn get_current_frame(&self) -> Option<u32> {
+        self.player.as_ref().map(|player| self.clips[&player.clip_name].frames[player.frame_idx])
+    }
+
+    // true only in the update in which one shot clip reached its end
+    pub fn just_finished(&self) -> bool {
+        self.finished_in_last_update
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.player.as_ref().map_or(false, |player| player.finished)
+    }
+
+    pub fn get_rectangle(&self) -> &Rectangle<TexturedVertexDataLayout> {
+        &self.rectangle
+    }
+
+    pub fn get_rectangle_mut(&mut self) -> &mut Rectangle<TexturedVertexDataLayout> {
+        &mut self.rectangle
+    }
+
+    pub fn advance(&mut self, time_passed: Duration) {
+        self.finished_in_last_update = false;
+        let player = match self.player.as_mut() {
+            None => return,
+            Some(player) => player
+        };
+        if player.finished {
+            return;
+        }
+        let clip = &self.clips[&player.clip_name];
+        let previous_frame_idx = player.frame_idx;
+        player.time_in_frame += time_passed;
+        // long updates can skip several frames
+        while player.time_in_frame >= clip.frame_durations[player.frame_idx] && !player.finished {
+            player.time_in_frame -= clip.frame_durations[player.frame_idx];
+            step_frame(player, clip);
+        }
+        self.finished_in_last_update = player.finished;
+        if player.frame_idx != previous_frame_idx {
+            self.show_current_frame();
+        }
+    }
+
+    fn show_current_frame(&mut self) {
+        if let Some(frame) = self.get_current_frame() {
+            let n_cols = self.sprite.get_spritesheet_dimensions().1;
+            let coords = self.sprite.get_texture_coords_from_spritesheet(frame / n_cols, frame % n_cols);
+            self.rectangle.set_texture_coords(&coords);
+        }
+    }
+}
+
+fn step_frame(player: &mut ClipPlayer, clip: &AnimationClip) {
+    let last_idx = clip.frames.len() - 1;
+    match clip.playback {
+        ClipPlayback::LOOP => {
+            player.frame_idx = if player.frame_idx == last_idx { 0 } else { player.frame_idx + 1 };
+        }
+        ClipPlayback::ONE_SHOT => {
+            if player.frame_idx == last_idx {
+                player.finished = true;
+                player.time_in_frame = Duration::ZERO;
+            } else {
+                player.frame_idx += 1;
+            }
+        }
+        ClipPlayback::PING_PONG => {
+            if last_idx == 0 {
+                return;
+            }
+            if player.backwards && player.frame_idx == 0 {
+                player.backwards = false;
+            } else if !player.backwards && player.frame_idx == last_idx {
+                player.backwards = true;
+            }
+            player.frame_idx = if player.backwards { player.frame_idx - 1 } else { player.frame_idx + 1 };
+        }
+    }
+}
+
+impl Drawable for AnimatedSprite {
+    fn render(&mut self, render_util: &RenderUtil) {
+        self.rectangle.render(render_util)
+    }
+
+    fn update(&mut self, update_context: &UpdateContext) {
+        self.advance(*update_context.get_delta_time());
+    }
+}
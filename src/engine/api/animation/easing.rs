@@ -0,0 +1,87 @@
+use std::f32::consts::PI;
+
+// how progress of animation maps to progress of animated value, see https://easings.net
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    LINEAR,
+    QUAD_IN,
+    QUAD_OUT,
+    QUAD_IN_OUT,
+    CUBIC_IN,
+    CUBIC_OUT,
+    CUBIC_IN_OUT,
+    ELASTIC_IN,
+    ELASTIC_OUT,
+    ELASTIC_IN_OUT,
+    BOUNCE_IN,
+    BOUNCE_OUT,
+    BOUNCE_IN_OUT,
+    BACK_IN,
+    BACK_OUT,
+    BACK_IN_OUT,
+}
+
+// how far back animations go before moving forward
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+impl Easing {
+    // takes progress from 0 to 1, elastic and back curves go outside of that range in between
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::LINEAR => t,
+            Easing::QUAD_IN => t * t,
+            Easing::QUAD_OUT => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QUAD_IN_OUT => in_out(t, |t| t * t),
+            Easing::CUBIC_IN => t * t * t,
+            Easing::CUBIC_OUT => 1.0 - (1.0 - t).powi(3),
+            Easing::CUBIC_IN_OUT => in_out(t, |t| t * t * t),
+            Easing::ELASTIC_IN => elastic_in(t),
+            Easing::ELASTIC_OUT => 1.0 - elastic_in(1.0 - t),
+            Easing::ELASTIC_IN_OUT => in_out(t, elastic_in),
+            Easing::BOUNCE_IN => 1.0 - bounce_out(1.0 - t),
+            Easing::BOUNCE_OUT => bounce_out(t),
+            Easing::BOUNCE_IN_OUT => in_out(t, |t| 1.0 - bounce_out(1.0 - t)),
+            Easing::BACK_IN => back_in(t),
+            Easing::BACK_OUT => 1.0 - back_in(1.0 - t),
+            Easing::BACK_IN_OUT => in_out(t, back_in),
+        }
+    }
+}
+
+// first half plays ease in curve, second half plays it mirrored
+fn in_out(t: f32, ease_in: fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+fn elastic_in(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 {
+        return t;
+    }
+    -(2.0_f32).powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * (2.0 * PI / 3.0)).sin()
+}
+
+fn back_in(t: f32) -> f32 {
+    (BACK_OVERSHOOT + 1.0) * t * t * t - BACK_OVERSHOOT * t * t
+}
+
+fn bounce_out(t: f32) -> f32 {
+    let n = 7.5625;
+    let d = 2.75;
+    if t < 1.0 / d {
+        n * t * t
+    } else if t < 2.0 / d {
+        let t = t - 1.5 / d;
+        n * t * t + 0.75
+    } else if t < 2.5 / d {
+        let t = t - 2.25 / d;
+        n * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / d;
+        n * t * t + 0.984375
+    }
+}
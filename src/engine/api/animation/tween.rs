@@ -0,0 +1,336 @@
+use std::time::Duration;
+
+use crate::engine::api::animation::easing::Easing;
+use crate::engine::api::colour::Colour;
+
+// values that can be interpolated, t can go outside of 0..1 for overshooting easings
+pub trait Tweenable: Clone {
+    fn interpolate(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Tweenable for glam::Vec2 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        *self + (*to - *self) * t
+    }
+}
+
+impl Tweenable for glam::Vec3 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        *self + (*to - *self) * t
+    }
+}
+
+impl Tweenable for glam::Vec4 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        *self + (*to - *self) * t
+    }
+}
+
+impl Tweenable for glam::Quat {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self.slerp(*to, t)
+    }
+}
+
+impl Tweenable for Colour {
+    // overshoot is clamped, colour channels must stay in range
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        let from: glam::Vec4 = (*self).into();
+        let to: glam::Vec4 = (*to).into();
+        from.interpolate(&to, t).clamp(glam::Vec4::ZERO, glam::Vec4::ONE).into()
+    }
+}
+
+/**
+Anything that can put its target into the state it has at given time since animation start.
+Animations are played by `Animator`, `Sequence` and `Parallel` combine them.
+ */
+pub trait Animation<T> {
+    fn get_duration(&self) -> Duration;
+
+    // time is clamped to duration by caller
+    fn apply(&mut self, target: &mut T, time: Duration);
+}
+
+/**
+Animates one property of target between two values. Property is written by setter closure,
+so anything that can be set can be animated: position, scale, colour or material uniform.
+```
+let grow = Tween::new(1.0, 1.5, Duration::from_millis(250), Easing::BACK_OUT,
+    |rect: &mut Rectangle<TexturedVertexDataLayout>, scale| rect.set_scale(glam::vec3(scale, scale, 1.0)))
+    .with_delay(Duration::from_millis(100))
+    .on_complete(|_| println!("grown"));
+let mut animator = Animator::new(grow);
+// in update
+animator.update(&mut rect, *update_context.get_delta_time());
+```
+ */
+pub struct Tween<T, V: Tweenable> {
+    from: V,
+    to: V,
+    duration: Duration,
+    delay: Duration,
+    easing: Easing,
+    setter: Box<dyn Fn(&mut T, V)>,
+    on_complete: Option<Box<dyn FnMut(&mut T)>>,
+    // callback is called once per pass through the end
+    completed: bool,
+}
+
+impl<T, V: Tweenable> Tween<T, V> {
+    pub fn new(from: V, to: V, duration: Duration, easing: Easing, setter: impl Fn(&mut T, V) + 'static) -> Tween<T, V> {
+        Tween {
+            from,
+            to,
+            duration,
+            delay: Duration::ZERO,
+            easing,
+            setter: Box::new(setter),
+            on_complete: None,
+            completed: false,
+        }
+    }
+
+    // target is not touched until delay passes
+    pub fn with_delay(mut self, delay: Duration) -> Tween<T, V> {
+        self.delay = delay;
+        self
+    }
+
+    pub fn on_complete(mut self, callback: impl FnMut(&mut T) + 'static) -> Tween<T, V> {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    pub fn get_value_at(&self, time: Duration) -> V {
+        let progress = match self.duration.is_zero() {
+            true => 1.0,
+            false => (time.saturating_sub(self.delay).as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        self.from.interpolate(&self.to, self.easing.apply(progress))
+    }
+}
+
+impl<T, V: Tweenable> Animation<T> for Tween<T, V> {
+    fn get_duration(&self) -> Duration {
+        self.delay + self.duration
+    }
+
+    fn apply(&mut self, target: &mut T, time: Duration) {
+        if time < self.delay {
+            self.completed = false;
+            return;
+        }
+        (self.setter)(target, self.get_value_at(time));
+        if time < self.get_duration() {
+            self.completed = false;
+        } else if !self.completed {
+            self.completed = true;
+            if let Some(callback) = self.on_complete.as_mut() {
+                callback(target);
+            }
+        }
+    }
+}
+
+// does nothing for given time, used to space out steps of sequence
+pub struct Delay {
+    duration: Duration,
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Delay {
+        Delay { duration }
+    }
+}
+
+impl<T> Animation<T> for Delay {
+    fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn apply(&mut self, _target: &mut T, _time: Duration) {}
+}
+
+// plays animations one after another
+pub struct Sequence<T> {
+    steps: Vec<Box<dyn Animation<T>>>,
+}
+
+impl<T> Sequence<T> {
+    pub fn new() -> Sequence<T> {
+        Sequence { steps: vec!() }
+    }
+
+    pub fn then(mut self, animation: impl Animation<T> + 'static) -> Sequence<T> {
+        self.steps.push(Box::new(animation));
+        self
+    }
+
+    pub fn wait(self, duration: Duration) -> Sequence<T> {
+        self.then(Delay::new(duration))
+    }
+}
+
+impl<T> Animation<T> for Sequence<T> {
+    fn get_duration(&self) -> Duration {
+        self.steps.iter().map(|step| step.get_duration()).sum()
+    }
+
+    // steps that have not started yet are left alone, so that they do not overwrite values set by earlier steps
+    fn apply(&mut self, target: &mut T, time: Duration) {
+        let mut step_start = Duration::ZERO;
+        for step in self.steps.iter_mut() {
+            if time < step_start {
+                break;
+            }
+            let step_duration = step.get_duration();
+            step.apply(target, (time - step_start).min(step_duration));
+            step_start += step_duration;
+        }
+    }
+}
+
+// plays animations at the same time, lasts as long as the longest of them
+pub struct Parallel<T> {
+    animations: Vec<Box<dyn Animation<T>>>,
+}
+
+impl<T> Parallel<T> {
+    pub fn new() -> Parallel<T> {
+        Parallel { animations: vec!() }
+    }
+
+    pub fn with(mut self, animation: impl Animation<T> + 'static) -> Parallel<T> {
+        self.animations.push(Box::new(animation));
+        self
+    }
+}
+
+impl<T> Animation<T> for Parallel<T> {
+    fn get_duration(&self) -> Duration {
+        self.animations.iter().map(|animation| animation.get_duration()).max().unwrap_or(Duration::ZERO)
+    }
+
+    fn apply(&mut self, target: &mut T, time: Duration) {
+        for animation in self.animations.iter_mut() {
+            let duration = animation.get_duration();
+            animation.apply(target, time.min(duration));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoopMode {
+    ONCE,
+    // none means forever
+    LOOP { count: Option<u32> },
+    // every other pass plays backwards, going there and back counts as two passes
+    YOYO { count: Option<u32> },
+}
+
+// plays animation on a target, driven by update delta time
+pub struct Animator<T> {
+    animation: Box<dyn Animation<T>>,
+    loop_mode: LoopMode,
+    elapsed: Duration,
+    paused: bool,
+    finished: bool,
+}
+
+impl<T> Animator<T> {
+    pub fn new(animation: impl Animation<T> + 'static) -> Animator<T> {
+        Animator {
+            animation: Box::new(animation),
+            loop_mode: LoopMode::ONCE,
+            elapsed: Duration::ZERO,
+            paused: false,
+            finished: false,
+        }
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Animator<T> {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    pub fn update(&mut self, target: &mut T, time_passed: Duration) {
+        if self.finished || self.paused {
+            return;
+        }
+        let duration = self.animation.get_duration();
+        if duration.is_zero() {
+            self.animation.apply(target, duration);
+            self.finished = true;
+            return;
+        }
+
+        let previous_pass = self.get_pass(self.elapsed, duration);
+        self.elapsed += time_passed;
+        let current_pass = self.get_pass(self.elapsed, duration);
+        let max_passes = match self.loop_mode {
+            LoopMode::ONCE => Some(1),
+            LoopMode::LOOP { count } => count,
+            LoopMode::YOYO { count } => count,
+        };
+
+        // finish the pass that was interrupted so that its end values and callbacks are not skipped
+        if current_pass != previous_pass {
+            self.animation.apply(target, self.get_pass_end(previous_pass, duration));
+        }
+        match max_passes {
+            Some(max_passes) if current_pass >= max_passes => {
+                if max_passes > 0 {
+                    self.animation.apply(target, self.get_pass_end(max_passes - 1, duration));
+                }
+                self.finished = true;
+            }
+            _ => {
+                let pass_time = Duration::from_nanos((self.elapsed.as_nanos() % duration.as_nanos()) as u64);
+                self.animation.apply(target, self.get_time_in_pass(current_pass, pass_time, duration));
+            }
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false
+    }
+
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+    }
+
+    fn get_pass(&self, elapsed: Duration, duration: Duration) -> u32 {
+        (elapsed.as_nanos() / duration.as_nanos()) as u32
+    }
+
+    fn is_reversed(&self, pass: u32) -> bool {
+        matches!(self.loop_mode, LoopMode::YOYO { .. }) && pass % 2 == 1
+    }
+
+    fn get_time_in_pass(&self, pass: u32, pass_time: Duration, duration: Duration) -> Duration {
+        match self.is_reversed(pass) {
+            true => duration - pass_time,
+            false => pass_time
+        }
+    }
+
+    fn get_pass_end(&self, pass: u32, duration: Duration) -> Duration {
+        self.get_time_in_pass(pass, duration, duration)
+    }
+}
@@ -1,11 +1,15 @@
 use std::ops::Mul;
 
-use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::game_api::{CameraConfig, Projection};
+
+// relative change of orthographic zoom per unit of zoom amount
+const ORTHOGRAPHIC_ZOOM_SPEED: f32 = 0.05;
 
 pub struct CameraGameObject {
     position: glam::Vec3,
     up: glam::Vec3,
     look_at: glam::Vec3,
+    projection: Projection,
 }
 
 impl CameraGameObject {
@@ -14,11 +18,63 @@ impl CameraGameObject {
             position,
             up,
             look_at,
+            projection: Projection::new_perspective(),
         }
     }
 
     pub fn get_current_config(&self) -> CameraConfig {
-        CameraConfig::new(self.position, self.up, self.look_at)
+        CameraConfig::new(self.position, self.up, self.look_at).with_projection(self.projection)
+    }
+
+    pub fn get_projection(&self) -> &Projection {
+        &self.projection
+    }
+
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    pub fn get_position(&self) -> &glam::Vec3 {
+        &self.position
+    }
+
+    pub fn get_look_at(&self) -> &glam::Vec3 {
+        &self.look_at
+    }
+
+    pub fn set_look_at(&mut self, look_at: glam::Vec3) {
+        self.look_at = look_at;
+    }
+
+    pub fn set_up(&mut self, up: glam::Vec3) {
+        self.up = up;
+    }
+
+    // distance from look at point for perspective camera, world units per pixel for orthographic one
+    // screen space camera always shows one unit per pixel and can not zoom
+    pub fn get_zoom_level(&self) -> f32 {
+        match self.projection {
+            Projection::PERSPECTIVE { .. } => self.position.distance(self.look_at),
+            Projection::ORTHOGRAPHIC { world_units_per_pixel, .. } => world_units_per_pixel,
+            Projection::SCREEN_SPACE { .. } => 1.0,
+        }
+    }
+
+    // zoom levels that are not positive are skipped, perspective camera with eye on look at point has no direction to zoom along
+    pub fn set_zoom_level(&mut self, zoom_level: f32) {
+        if !(zoom_level > 0.0) || !zoom_level.is_finite() {
+            return;
+        }
+        match &mut self.projection {
+            Projection::PERSPECTIVE { .. } if self.position == self.look_at => {}
+            Projection::PERSPECTIVE { .. } => {
+                self.position = self.look_at + (self.position - self.look_at).normalize().mul(zoom_level);
+            }
+            Projection::ORTHOGRAPHIC { world_units_per_pixel, .. } => {
+                *world_units_per_pixel = zoom_level;
+            }
+            Projection::SCREEN_SPACE { .. } => {}
+        }
     }
 
     pub fn set_position(&mut self, new_position: glam::Vec3) {
@@ -29,9 +85,19 @@ impl CameraGameObject {
         self.position = self.position + offset;
     }
 
+    // positive amount zooms out, perspective camera moves away and orthographic one shows more world per pixel
     pub fn zoom_by(&mut self, amount: f32) {
-        println!("ZOOMING BY {:?}", amount);
-        self.position = self.position + (self.position - self.look_at).normalize().mul(amount);
+        self.set_zoom_level(self.get_zoom_level_after(amount));
+    }
+
+    pub fn get_zoom_level_after(&self, zoom_amount: f32) -> f32 {
+        match self.projection {
+            Projection::PERSPECTIVE { .. } => self.get_zoom_level() + zoom_amount,
+            Projection::ORTHOGRAPHIC { world_units_per_pixel, .. } => {
+                world_units_per_pixel * (1.0 + zoom_amount * ORTHOGRAPHIC_ZOOM_SPEED).max(ORTHOGRAPHIC_ZOOM_SPEED)
+            }
+            Projection::SCREEN_SPACE { .. } => 1.0,
+        }
     }
 }
 
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use crate::engine::api::camera::CameraGameObject;
+use crate::engine::api::collision::shapes::Aabb;
+use crate::engine::api::game_api::{CameraConfig, Projection};
+
+const DEFAULT_TRAUMA_DECAY: f32 = 1.0;
+const DEFAULT_MAX_SHAKE_OFFSET: f32 = 0.5;
+const DEFAULT_MAX_SHAKE_ANGLE: f32 = 0.05;
+// zoom level of 0 puts perspective camera eye onto look at point and leaves no world visible for orthographic one
+const DEFAULT_MIN_ZOOM_LEVEL: f32 = 0.001;
+// how fast shake changes direction
+const SHAKE_FREQUENCY: f32 = 25.0;
+
+/**
+Drives a camera looking down at z == 0 plane: follows a target, keeps view inside world bounds,
+limits zoom and shakes. Any game that owns a camera can use it, game sets target and calls `update` every frame.
+```
+let mut camera = CameraController::new(CameraGameObject::new(eye, up, look_at));
+camera.set_follow_speed(5.0);
+camera.set_dead_zone(glam::vec2(1.0, 0.5));
+camera.set_bounds(Some(Aabb::new(glam::vec2(-20.0, -5.0), glam::vec2(20.0, 15.0))));
+// in update
+camera.set_target(player_position);
+camera.update(*update_context.get_delta_time());
+// when player is hit
+camera.add_trauma(0.5);
+```
+ */
+pub struct CameraController {
+    camera: CameraGameObject,
+    // camera eye relative to look at point, kept while camera moves
+    eye_offset: glam::Vec3,
+    // point at z == 0 plane that camera looks at, without shake
+    center: glam::Vec2,
+    target: glam::Vec2,
+    // how fast camera catches up with target, infinity makes it snap
+    follow_speed: f32,
+    // target can move this far from center along each axis before camera follows it
+    dead_zone: glam::Vec2,
+    // area of world that view must stay inside of
+    bounds: Option<Aabb>,
+    min_zoom_level: f32,
+    max_zoom_level: f32,
+    // in pixels, remembered from last render
+    viewport_size: glam::Vec2,
+    // from 0 to 1, shake strength grows with its square
+    trauma: f32,
+    // trauma lost per second
+    trauma_decay: f32,
+    max_shake_offset: f32,
+    max_shake_angle: f32,
+    shake_time: f32,
+}
+
+impl CameraController {
+    pub fn new(camera: CameraGameObject) -> CameraController {
+        let center = camera.get_look_at().truncate();
+        let eye_offset = *camera.get_position() - *camera.get_look_at();
+        CameraController {
+            camera,
+            eye_offset,
+            center,
+            target: center,
+            follow_speed: f32::INFINITY,
+            dead_zone: glam::vec2(0.0, 0.0),
+            bounds: None,
+            min_zoom_level: DEFAULT_MIN_ZOOM_LEVEL,
+            max_zoom_level: f32::MAX,
+            viewport_size: glam::vec2(1920.0, 1080.0),
+            trauma: 0.0,
+            trauma_decay: DEFAULT_TRAUMA_DECAY,
+            max_shake_offset: DEFAULT_MAX_SHAKE_OFFSET,
+            max_shake_angle: DEFAULT_MAX_SHAKE_ANGLE,
+            shake_time: 0.0,
+        }
+    }
+
+    pub fn get_camera(&self) -> &CameraGameObject {
+        &self.camera
+    }
+
+    pub fn get_current_config(&self) -> CameraConfig {
+        self.camera.get_current_config()
+    }
+
+    pub fn get_center(&self) -> &glam::Vec2 {
+        &self.center
+    }
+
+    pub fn get_target(&self) -> &glam::Vec2 {
+        &self.target
+    }
+
+    pub fn set_target(&mut self, target: glam::Vec2) {
+        self.target = target;
+    }
+
+    pub fn move_target_by(&mut self, offset: glam::Vec2) {
+        self.target += offset;
+    }
+
+    // moves camera to target right away, e.g. after teleporting player
+    pub fn snap_to_target(&mut self) {
+        self.center = self.clamp_to_bounds(self.target);
+        self.apply_to_camera(glam::vec2(0.0, 0.0), 0.0);
+    }
+
+    pub fn set_follow_speed(&mut self, follow_speed: f32) {
+        self.follow_speed = follow_speed;
+    }
+
+    pub fn set_dead_zone(&mut self, half_size: glam::Vec2) {
+        self.dead_zone = half_size;
+    }
+
+    pub fn set_bounds(&mut self, bounds: Option<Aabb>) {
+        self.bounds = bounds;
+    }
+
+    // zoom level is distance from z == 0 plane for perspective camera and world units per pixel for orthographic one
+    pub fn set_zoom_limits(&mut self, min_zoom_level: f32, max_zoom_level: f32) {
+        if min_zoom_level <= 0.0 {
+            panic!("Minimum zoom level must be positive, got {}", min_zoom_level);
+        }
+        if min_zoom_level > max_zoom_level {
+            panic!("Minimum zoom level {} is above maximum {}", min_zoom_level, max_zoom_level);
+        }
+        self.min_zoom_level = min_zoom_level;
+        self.max_zoom_level = max_zoom_level;
+        self.set_zoom_level(self.camera.get_zoom_level());
+    }
+
+    pub fn set_viewport_size(&mut self, viewport_size: glam::Vec2) {
+        self.viewport_size = viewport_size;
+    }
+
+    // positive amount zooms out
+    pub fn zoom_by(&mut self, amount: f32) {
+        self.set_zoom_level(self.camera.get_zoom_level_after(amount));
+    }
+
+    // world point under cursor stays under cursor
+    pub fn zoom_to_cursor(&mut self, amount: f32, cursor_world_position: &glam::Vec3) {
+        let old_zoom_level = self.camera.get_zoom_level();
+        self.zoom_by(amount);
+        // camera that started with eye on look at point only zooms
+        if old_zoom_level <= 0.0 {
+            return;
+        }
+        let zoom_ratio = self.camera.get_zoom_level() / old_zoom_level;
+        let cursor = cursor_world_position.truncate();
+        self.center = cursor + (self.center - cursor) * zoom_ratio;
+        self.target = cursor + (self.target - cursor) * zoom_ratio;
+        self.center = self.clamp_to_bounds(self.center);
+        self.apply_to_camera(glam::vec2(0.0, 0.0), 0.0);
+    }
+
+    // trauma adds up to 1, camera shakes until it decays
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn get_trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    pub fn set_shake(&mut self, max_offset: f32, max_angle: f32, trauma_decay: f32) {
+        self.max_shake_offset = max_offset;
+        self.max_shake_angle = max_angle;
+        self.trauma_decay = trauma_decay;
+    }
+
+    pub fn update(&mut self, time_passed: Duration) {
+        let delta_seconds = time_passed.as_secs_f32();
+
+        // target only pulls camera once it leaves dead zone
+        let offset = self.target - self.center;
+        let desired_center = self.center + glam::vec2(
+            offset.x.signum() * (offset.x.abs() - self.dead_zone.x).max(0.0),
+            offset.y.signum() * (offset.y.abs() - self.dead_zone.y).max(0.0),
+        );
+        // exponential smoothing does not depend on frame rate
+        let catch_up = match self.follow_speed.is_infinite() {
+            true => 1.0,
+            false => 1.0 - (-self.follow_speed * delta_seconds).exp()
+        };
+        self.center = self.clamp_to_bounds(self.center + (desired_center - self.center) * catch_up);
+
+        self.trauma = (self.trauma - self.trauma_decay * delta_seconds).max(0.0);
+        self.shake_time += delta_seconds;
+        let shake = self.trauma * self.trauma;
+        // smooth noise from sines, keeps replays deterministic
+        let shake_offset = glam::vec2(get_shake_noise(self.shake_time, 0.0), get_shake_noise(self.shake_time, 1.7)) * self.max_shake_offset * shake;
+        let shake_angle = get_shake_noise(self.shake_time, 3.1) * self.max_shake_angle * shake;
+        self.apply_to_camera(shake_offset, shake_angle);
+    }
+
+    // clamped to zoom limits
+    pub fn set_zoom_level(&mut self, zoom_level: f32) {
+        self.camera.set_zoom_level(zoom_level.clamp(self.min_zoom_level, self.max_zoom_level));
+        self.eye_offset = *self.camera.get_position() - *self.camera.get_look_at();
+        self.center = self.clamp_to_bounds(self.center);
+    }
+
+    // half of world area visible at z == 0 plane
+    fn get_view_half_size(&self) -> glam::Vec2 {
+        match self.camera.get_projection() {
+            Projection::PERSPECTIVE { fov, .. } => {
+                let half_height = self.eye_offset.z.abs() * (fov / 2.0).tan();
+                glam::vec2(half_height * self.viewport_size.x / self.viewport_size.y, half_height)
+            }
+            Projection::ORTHOGRAPHIC { world_units_per_pixel, .. } => self.viewport_size * *world_units_per_pixel / 2.0,
+            Projection::SCREEN_SPACE { .. } => self.viewport_size / 2.0,
+        }
+    }
+
+    // view bigger than bounds is centered on them
+    fn clamp_to_bounds(&self, center: glam::Vec2) -> glam::Vec2 {
+        let bounds = match &self.bounds {
+            None => return center,
+            Some(bounds) => bounds
+        };
+        let half_size = self.get_view_half_size();
+        let clamp_axis = |value: f32, min: f32, max: f32, half_size: f32| {
+            if max - min <= half_size * 2.0 { (min + max) / 2.0 } else { value.clamp(min + half_size, max - half_size) }
+        };
+        glam::vec2(
+            clamp_axis(center.x, bounds.get_min().x, bounds.get_max().x, half_size.x),
+            clamp_axis(center.y, bounds.get_min().y, bounds.get_max().y, half_size.y),
+        )
+    }
+
+    fn apply_to_camera(&mut self, shake_offset: glam::Vec2, shake_angle: f32) {
+        let look_at = (self.center + shake_offset).extend(self.camera.get_look_at().z);
+        self.camera.set_look_at(look_at);
+        self.camera.set_position(look_at + self.eye_offset);
+        self.camera.set_up(glam::vec3(-shake_angle.sin(), shake_angle.cos(), 0.0));
+    }
+}
+
+// roughly between -1 and 1
+fn get_shake_noise(time: f32, phase: f32) -> f32 {
+    let t = time * SHAKE_FREQUENCY + phase * 10.0;
+    (t.sin() * 0.6 + (t * 2.3 + phase).sin() * 0.3 + (t * 5.1 + phase * 2.0).sin() * 0.1).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::api::camera::CameraGameObject;
+
+    use super::CameraController;
+
+    fn perspective_controller() -> CameraController {
+        CameraController::new(CameraGameObject::new(glam::vec3(0.0, 0.0, 10.0), glam::vec3(0.0, 1.0, 0.0), glam::vec3(0.0, 0.0, 0.0)))
+    }
+
+    #[test]
+    fn zooming_in_past_look_at_point_stops_at_minimum() {
+        let mut camera = perspective_controller();
+        camera.zoom_by(-20.0);
+        let position = *camera.get_camera().get_position();
+        assert!(position.is_finite());
+        assert!(position.z > 0.0);
+    }
+
+    #[test]
+    fn zooming_to_cursor_from_minimum_keeps_camera_finite() {
+        let mut camera = perspective_controller();
+        camera.set_zoom_level(0.0);
+        camera.zoom_to_cursor(-1.0, &glam::vec3(3.0, 2.0, 0.0));
+        camera.zoom_to_cursor(5.0, &glam::vec3(3.0, 2.0, 0.0));
+        assert!(camera.get_camera().get_position().is_finite());
+        assert!(camera.get_camera().get_look_at().is_finite());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zoom_limits_must_be_positive() {
+        perspective_controller().set_zoom_limits(0.0, 10.0);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod shapes;
+pub mod narrow_phase;
+pub mod raycast;
+pub mod broad_phase;
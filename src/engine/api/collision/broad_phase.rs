@@ -0,0 +1,176 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::engine::api::collision::narrow_phase::{Contact, test_collision};
+use crate::engine::api::collision::shapes::{Aabb, CollisionShape};
+use crate::engine::api::ecs::components::ColliderComponent;
+use crate::engine::api::ecs::world::{Entity, World};
+use crate::engine::api::maths::transform::Transform;
+
+/**
+Uniform grid that finds pairs of objects whose bounds overlap, so that exact collision tests
+are only run for objects that are close to each other. Objects are identified by ids chosen by caller,
+usually their index in caller's collection. Cell size should be about the size of typical object.
+```
+let mut grid = SpatialGrid::new(2.0);
+shapes.iter().enumerate().for_each(|(id, shape)| grid.insert(id, shape.get_bounds()));
+for (first, second) in grid.find_candidate_pairs() {
+    if let Some(contact) = test_collision(&shapes[first], &shapes[second]) { ... }
+}
+```
+ */
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    bounds: HashMap<usize, Aabb>,
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> SpatialGrid {
+        if cell_size <= 0.0 {
+            panic!("Cell size of spatial grid must be positive, got {}", cell_size);
+        }
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+            bounds: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds.clear();
+    }
+
+    pub fn insert(&mut self, id: usize, bounds: Aabb) {
+        if self.bounds.insert(id, bounds).is_some() {
+            panic!("Object with id {} is already in spatial grid", id);
+        }
+        for cell in self.get_cells(&bounds) {
+            self.cells.entry(cell).or_insert_with(|| vec!()).push(id);
+        }
+    }
+
+    // ids of objects whose bounds overlap given ones, in ascending order
+    pub fn query(&self, bounds: &Aabb) -> Vec<usize> {
+        let mut found = BTreeSet::new();
+        for cell in self.get_cells(bounds) {
+            for id in self.cells.get(&cell).iter().flat_map(|ids| ids.iter()) {
+                if self.bounds[id].intersects(bounds) {
+                    found.insert(*id);
+                }
+            }
+        }
+        found.into_iter().collect()
+    }
+
+    // every pair is reported once with smaller id first, pairs are sorted
+    pub fn find_candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = BTreeSet::new();
+        for ids in self.cells.values() {
+            for (idx, first) in ids.iter().enumerate() {
+                for second in ids[idx + 1..].iter() {
+                    if self.bounds[first].intersects(&self.bounds[second]) {
+                        pairs.insert((*first.min(second), *first.max(second)));
+                    }
+                }
+            }
+        }
+        pairs.into_iter().collect()
+    }
+
+    fn get_cells(&self, bounds: &Aabb) -> Vec<(i32, i32)> {
+        let min_x = (bounds.get_min().x / self.cell_size).floor() as i32;
+        let min_y = (bounds.get_min().y / self.cell_size).floor() as i32;
+        let max_x = (bounds.get_max().x / self.cell_size).floor() as i32;
+        let max_y = (bounds.get_max().y / self.cell_size).floor() as i32;
+        let mut cells = vec!();
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                cells.push((x, y));
+            }
+        }
+        cells
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EntityCollision {
+    pub first: Entity,
+    pub second: Entity,
+    // normal points from first entity towards second
+    pub contact: Contact,
+}
+
+// collisions between all entities of the world that have both collider and transform
+pub fn find_world_collisions(world: &World, grid: &mut SpatialGrid) -> Vec<EntityCollision> {
+    let colliders = world.get_storage::<ColliderComponent>();
+    let colliders = colliders.borrow();
+    let transforms = world.get_storage::<Transform>();
+    let transforms = transforms.borrow();
+    let shapes: Vec<(Entity, CollisionShape)> = colliders.iter()
+        .filter_map(|(entity, collider)| transforms.get(entity).map(|transform| (*entity, collider.to_collision_shape(transform))))
+        .collect();
+
+    grid.clear();
+    shapes.iter().enumerate().for_each(|(id, (_, shape))| grid.insert(id, shape.get_bounds()));
+    grid.find_candidate_pairs().into_iter()
+        .filter_map(|(first, second)| {
+            test_collision(&shapes[first].1, &shapes[second].1).map(|contact| EntityCollision {
+                first: shapes[first].0,
+                second: shapes[second].0,
+                contact,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::api::collision::shapes::Aabb;
+
+    use super::SpatialGrid;
+
+    fn square(min_x: f32, min_y: f32, size: f32) -> Aabb {
+        Aabb::new(glam::vec2(min_x, min_y), glam::vec2(min_x + size, min_y + size))
+    }
+
+    #[test]
+    fn only_overlapping_bounds_are_paired() {
+        let mut grid = SpatialGrid::new(1.0);
+        grid.insert(0, square(0.1, 0.1, 0.3));
+        // same cell as 0 but not overlapping it
+        grid.insert(1, square(0.6, 0.6, 0.3));
+        grid.insert(2, square(0.3, 0.3, 0.5));
+        grid.insert(3, square(10.0, 10.0, 1.0));
+        assert_eq!(grid.find_candidate_pairs(), vec![(0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn objects_spanning_several_cells_are_paired_once() {
+        let mut grid = SpatialGrid::new(1.0);
+        grid.insert(5, square(-0.5, -0.5, 3.0));
+        grid.insert(2, square(0.5, 0.5, 2.0));
+        assert_eq!(grid.find_candidate_pairs(), vec![(2, 5)]);
+    }
+
+    #[test]
+    fn query_returns_overlapping_ids_in_order() {
+        let mut grid = SpatialGrid::new(2.0);
+        grid.insert(3, square(-3.0, -3.0, 2.0));
+        grid.insert(1, square(0.0, 0.0, 1.0));
+        grid.insert(2, square(5.0, 5.0, 1.0));
+        assert_eq!(grid.query(&square(-1.5, -1.5, 2.0)), vec![1, 3]);
+        assert!(grid.query(&square(20.0, 20.0, 1.0)).is_empty());
+
+        grid.clear();
+        assert!(grid.query(&square(-1.5, -1.5, 2.0)).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn id_cannot_be_inserted_twice() {
+        let mut grid = SpatialGrid::new(1.0);
+        grid.insert(0, square(0.0, 0.0, 1.0));
+        grid.insert(0, square(4.0, 4.0, 1.0));
+    }
+}
@@ -0,0 +1,270 @@
+use glam::Vec2;
+
+use crate::engine::api::collision::shapes::{Aabb, CollisionShape, get_outward_edge_normals};
+
+// how two overlapping shapes touch each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    // unit vector pointing from first shape towards second one
+    normal: Vec2,
+    // how far second shape must be moved along normal to stop overlapping
+    penetration: f32,
+}
+
+impl Contact {
+    pub fn new(normal: Vec2, penetration: f32) -> Contact {
+        Contact { normal, penetration }
+    }
+
+    pub fn get_normal(&self) -> &Vec2 {
+        &self.normal
+    }
+
+    pub fn get_penetration(&self) -> f32 {
+        self.penetration
+    }
+
+    // same contact seen from the other shape
+    pub fn flipped(&self) -> Contact {
+        Contact::new(-self.normal, self.penetration)
+    }
+}
+
+// shapes that only touch are not colliding
+pub fn test_collision(first: &CollisionShape, second: &CollisionShape) -> Option<Contact> {
+    match (first, second) {
+        (CollisionShape::CIRCLE { center: first_center, radius: first_radius },
+            CollisionShape::CIRCLE { center: second_center, radius: second_radius }) => {
+            test_circles(first_center, *first_radius, second_center, *second_radius)
+        }
+        (CollisionShape::AABB { aabb: first_aabb }, CollisionShape::AABB { aabb: second_aabb }) => {
+            test_aabbs(first_aabb, second_aabb)
+        }
+        (CollisionShape::CIRCLE { center, radius }, CollisionShape::AABB { aabb }) => {
+            test_circle_aabb(center, *radius, aabb)
+        }
+        (CollisionShape::AABB { aabb }, CollisionShape::CIRCLE { center, radius }) => {
+            test_circle_aabb(center, *radius, aabb).map(|contact| contact.flipped())
+        }
+        (CollisionShape::CIRCLE { center, radius }, polygon) => {
+            test_polygon_circle(&polygon.get_polygon_vertices().unwrap(), center, *radius).map(|contact| contact.flipped())
+        }
+        (polygon, CollisionShape::CIRCLE { center, radius }) => {
+            test_polygon_circle(&polygon.get_polygon_vertices().unwrap(), center, *radius)
+        }
+        (first_polygon, second_polygon) => {
+            test_polygons(&first_polygon.get_polygon_vertices().unwrap(), &second_polygon.get_polygon_vertices().unwrap())
+        }
+    }
+}
+
+fn test_circles(first_center: &Vec2, first_radius: f32, second_center: &Vec2, second_radius: f32) -> Option<Contact> {
+    let offset = *second_center - *first_center;
+    let distance = offset.length();
+    let radii = first_radius + second_radius;
+    if distance >= radii {
+        return None;
+    }
+    // concentric circles can be pushed apart in any direction
+    let normal = if distance > 0.0 { offset / distance } else { glam::vec2(1.0, 0.0) };
+    Some(Contact::new(normal, radii - distance))
+}
+
+fn test_aabbs(first: &Aabb, second: &Aabb) -> Option<Contact> {
+    let overlap_x = first.get_max().x.min(second.get_max().x) - first.get_min().x.max(second.get_min().x);
+    let overlap_y = first.get_max().y.min(second.get_max().y) - first.get_min().y.max(second.get_min().y);
+    if overlap_x <= 0.0 || overlap_y <= 0.0 {
+        return None;
+    }
+    let offset = second.get_center() - first.get_center();
+    // boxes are separated along the axis with smaller overlap
+    if overlap_x < overlap_y {
+        Some(Contact::new(glam::vec2(if offset.x < 0.0 { -1.0 } else { 1.0 }, 0.0), overlap_x))
+    } else {
+        Some(Contact::new(glam::vec2(0.0, if offset.y < 0.0 { -1.0 } else { 1.0 }), overlap_y))
+    }
+}
+
+// normal points from circle towards box
+fn test_circle_aabb(center: &Vec2, radius: f32, aabb: &Aabb) -> Option<Contact> {
+    if !aabb.contains_point(center) {
+        let closest = center.max(*aabb.get_min()).min(*aabb.get_max());
+        let offset = closest - *center;
+        let distance = offset.length();
+        if distance >= radius {
+            return None;
+        }
+        return Some(Contact::new(offset / distance, radius - distance));
+    }
+
+    // center inside of box, circle is pushed out through the nearest face
+    let faces = [
+        (center.x - aabb.get_min().x, glam::vec2(-1.0, 0.0)),
+        (aabb.get_max().x - center.x, glam::vec2(1.0, 0.0)),
+        (center.y - aabb.get_min().y, glam::vec2(0.0, -1.0)),
+        (aabb.get_max().y - center.y, glam::vec2(0.0, 1.0)),
+    ];
+    let (face_distance, face_normal) = faces.iter()
+        .fold(faces[0], |nearest, face| if face.0 < nearest.0 { *face } else { nearest });
+    Some(Contact::new(-face_normal, radius + face_distance))
+}
+
+// separating axis test, axes are edge normals of both polygons
+fn test_polygons(first: &[Vec2], second: &[Vec2]) -> Option<Contact> {
+    let mut axes = get_outward_edge_normals(first);
+    axes.extend(get_outward_edge_normals(second));
+
+    let mut best: Option<Contact> = None;
+    for axis in axes {
+        let (first_min, first_max) = project_polygon(first, &axis);
+        let (second_min, second_max) = project_polygon(second, &axis);
+        let overlap = first_max.min(second_max) - first_min.max(second_min);
+        if overlap <= 0.0 {
+            return None;
+        }
+        if best.map_or(true, |contact| overlap < contact.penetration) {
+            best = Some(Contact::new(axis, overlap));
+        }
+    }
+    best.map(|contact| orient_from_first_to_second(contact, &centroid(first), &centroid(second)))
+}
+
+// normal points from polygon towards circle
+fn test_polygon_circle(polygon: &[Vec2], center: &Vec2, radius: f32) -> Option<Contact> {
+    let mut axes = get_outward_edge_normals(polygon);
+    // vertex closest to circle is the only other place where polygon and circle can be separated
+    let closest_vertex = polygon.iter()
+        .fold(polygon[0], |closest, vertex| if vertex.distance_squared(*center) < closest.distance_squared(*center) { *vertex } else { closest });
+    let vertex_axis = *center - closest_vertex;
+    if vertex_axis.length_squared() > 0.0 {
+        axes.push(vertex_axis.normalize());
+    }
+
+    let mut best: Option<Contact> = None;
+    for axis in axes {
+        let (polygon_min, polygon_max) = project_polygon(polygon, &axis);
+        let circle_center = center.dot(axis);
+        let overlap = polygon_max.min(circle_center + radius) - polygon_min.max(circle_center - radius);
+        if overlap <= 0.0 {
+            return None;
+        }
+        if best.map_or(true, |contact| overlap < contact.penetration) {
+            best = Some(Contact::new(axis, overlap));
+        }
+    }
+    best.map(|contact| orient_from_first_to_second(contact, &centroid(polygon), center))
+}
+
+fn project_polygon(vertices: &[Vec2], axis: &Vec2) -> (f32, f32) {
+    vertices.iter()
+        .map(|vertex| vertex.dot(*axis))
+        .fold((f32::MAX, f32::MIN), |(min, max), projection| (min.min(projection), max.max(projection)))
+}
+
+fn centroid(vertices: &[Vec2]) -> Vec2 {
+    vertices.iter().fold(glam::vec2(0.0, 0.0), |sum, vertex| sum + *vertex) / vertices.len() as f32
+}
+
+fn orient_from_first_to_second(contact: Contact, first_center: &Vec2, second_center: &Vec2) -> Contact {
+    if (*second_center - *first_center).dot(contact.normal) < 0.0 {
+        contact.flipped()
+    } else {
+        contact
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::api::collision::shapes::CollisionShape;
+
+    use super::test_collision;
+
+    const EPSILON: f32 = 0.0001;
+
+    fn square(center: glam::Vec2, half_size: f32) -> CollisionShape {
+        CollisionShape::new_polygon(vec![
+            center + glam::vec2(-half_size, -half_size),
+            center + glam::vec2(half_size, -half_size),
+            center + glam::vec2(half_size, half_size),
+            center + glam::vec2(-half_size, half_size),
+        ])
+    }
+
+    #[test]
+    fn circles_are_pushed_apart_along_centres() {
+        let first = CollisionShape::new_circle(glam::vec2(0.0, 0.0), 1.0);
+        let second = CollisionShape::new_circle(glam::vec2(0.0, 1.5), 1.0);
+        let contact = test_collision(&first, &second).unwrap();
+        assert_eq!(*contact.get_normal(), glam::vec2(0.0, 1.0));
+        assert!((contact.get_penetration() - 0.5).abs() < EPSILON);
+
+        let touching = CollisionShape::new_circle(glam::vec2(2.0, 0.0), 1.0);
+        assert!(test_collision(&first, &touching).is_none());
+    }
+
+    #[test]
+    fn boxes_are_separated_along_smaller_overlap() {
+        let first = CollisionShape::new_aabb(glam::vec2(0.0, 0.0), glam::vec2(2.0, 2.0));
+        let second = CollisionShape::new_aabb(glam::vec2(1.5, 0.5), glam::vec2(3.5, 2.5));
+        let contact = test_collision(&first, &second).unwrap();
+        assert_eq!(*contact.get_normal(), glam::vec2(1.0, 0.0));
+        assert!((contact.get_penetration() - 0.5).abs() < EPSILON);
+        assert_eq!(test_collision(&second, &first).unwrap(), contact.flipped());
+    }
+
+    #[test]
+    fn circle_inside_box_leaves_through_nearest_face() {
+        let aabb = CollisionShape::new_aabb(glam::vec2(0.0, 0.0), glam::vec2(4.0, 4.0));
+        let circle = CollisionShape::new_circle(glam::vec2(3.5, 2.0), 1.0);
+        let contact = test_collision(&circle, &aabb).unwrap();
+        // normal points from circle to box, so circle moves against it to get out
+        assert_eq!(*contact.get_normal(), glam::vec2(-1.0, 0.0));
+        assert!((contact.get_penetration() - 1.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn polygons_overlap_by_minimum_translation() {
+        let first = square(glam::vec2(0.0, 0.0), 1.0);
+        let second = square(glam::vec2(1.75, 0.25), 1.0);
+        let contact = test_collision(&first, &second).unwrap();
+        assert!((*contact.get_normal() - glam::vec2(1.0, 0.0)).length() < EPSILON);
+        assert!((contact.get_penetration() - 0.25).abs() < EPSILON);
+
+        let far = square(glam::vec2(2.5, 0.0), 1.0);
+        assert!(test_collision(&first, &far).is_none());
+    }
+
+    #[test]
+    fn rotated_polygon_is_separated_on_its_own_axis() {
+        // diamond next to square, boxes around them overlap but shapes do not
+        let first = square(glam::vec2(0.0, 0.0), 1.0);
+        let diamond = CollisionShape::new_polygon(vec![
+            glam::vec2(1.6, 0.6),
+            glam::vec2(2.6, 1.6),
+            glam::vec2(1.6, 2.6),
+            glam::vec2(0.6, 1.6),
+        ]);
+        assert!(first.get_bounds().intersects(&diamond.get_bounds()));
+        assert!(test_collision(&first, &diamond).is_none());
+    }
+
+    #[test]
+    fn repeated_vertices_do_not_break_separating_axes() {
+        let first = CollisionShape::new_polygon(vec![
+            glam::vec2(-1.0, -1.0),
+            glam::vec2(1.0, -1.0),
+            glam::vec2(1.0, -1.0),
+            glam::vec2(1.0, 1.0),
+            glam::vec2(-1.0, 1.0),
+        ]);
+        let second = square(glam::vec2(1.5, 0.0), 1.0);
+        let contact = test_collision(&first, &second).unwrap();
+        assert!(contact.get_normal().is_finite());
+        assert!((contact.get_penetration() - 0.5).abs() < EPSILON);
+
+        let circle = CollisionShape::new_circle(glam::vec2(0.0, 1.5), 1.0);
+        let contact = test_collision(&first, &circle).unwrap();
+        assert!((*contact.get_normal() - glam::vec2(0.0, 1.0)).length() < EPSILON);
+        assert!((contact.get_penetration() - 0.5).abs() < EPSILON);
+    }
+}
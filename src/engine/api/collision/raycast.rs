@@ -0,0 +1,200 @@
+use glam::Vec2;
+
+use crate::engine::api::collision::shapes::{CollisionShape, get_outward_edges};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ray {
+    origin: Vec2,
+    // unit vector
+    direction: Vec2,
+}
+
+impl Ray {
+    pub fn new(origin: Vec2, direction: Vec2) -> Ray {
+        if direction.length_squared() == 0.0 {
+            panic!("Ray from {:?} needs non zero direction", origin);
+        }
+        Ray {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    pub fn get_origin(&self) -> &Vec2 {
+        &self.origin
+    }
+
+    pub fn get_direction(&self) -> &Vec2 {
+        &self.direction
+    }
+
+    pub fn get_point_at(&self, distance: f32) -> Vec2 {
+        self.origin + self.direction * distance
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RaycastHit {
+    distance: f32,
+    point: Vec2,
+    // normal of shape surface at hit point
+    normal: Vec2,
+}
+
+impl RaycastHit {
+    pub fn get_distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn get_point(&self) -> &Vec2 {
+        &self.point
+    }
+
+    pub fn get_normal(&self) -> &Vec2 {
+        &self.normal
+    }
+}
+
+// rays starting inside of shape hit it at their origin, normal is opposite to ray direction then
+pub fn raycast(ray: &Ray, max_distance: f32, shape: &CollisionShape) -> Option<RaycastHit> {
+    let hit = match shape {
+        CollisionShape::CIRCLE { center, radius } => raycast_circle(ray, center, *radius),
+        polygon => raycast_polygon(ray, max_distance, &polygon.get_polygon_vertices().unwrap()),
+    };
+    hit.filter(|hit| hit.distance <= max_distance)
+}
+
+pub fn raycast_segment(start: Vec2, end: Vec2, shape: &CollisionShape) -> Option<RaycastHit> {
+    raycast(&Ray::new(start, end - start), start.distance(end), shape)
+}
+
+fn raycast_circle(ray: &Ray, center: &Vec2, radius: f32) -> Option<RaycastHit> {
+    let to_origin = ray.origin - *center;
+    let c = to_origin.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some(hit_at_origin(ray));
+    }
+    // direction is unit vector so quadratic equation simplifies
+    let b = to_origin.dot(ray.direction);
+    let discriminant = b * b - c;
+    if b > 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let distance = -b - discriminant.sqrt();
+    let point = ray.get_point_at(distance);
+    Some(RaycastHit {
+        distance,
+        point,
+        normal: (point - *center).normalize(),
+    })
+}
+
+// clips ray against half planes of all edges
+fn raycast_polygon(ray: &Ray, max_distance: f32, vertices: &[Vec2]) -> Option<RaycastHit> {
+    let mut enter_distance = 0.0;
+    let mut exit_distance = max_distance;
+    let mut enter_normal: Option<Vec2> = None;
+    for (vertex, normal) in get_outward_edges(vertices).iter() {
+        let denominator = normal.dot(ray.direction);
+        let numerator = normal.dot(*vertex - ray.origin);
+        if denominator == 0.0 {
+            // parallel to edge and outside of it
+            if numerator < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let distance = numerator / denominator;
+        if denominator < 0.0 {
+            if distance > enter_distance {
+                enter_distance = distance;
+                enter_normal = Some(*normal);
+            }
+        } else if distance < exit_distance {
+            exit_distance = distance;
+        }
+        if enter_distance > exit_distance {
+            return None;
+        }
+    }
+    match enter_normal {
+        None => Some(hit_at_origin(ray)),
+        Some(normal) => Some(RaycastHit {
+            distance: enter_distance,
+            point: ray.get_point_at(enter_distance),
+            normal,
+        })
+    }
+}
+
+fn hit_at_origin(ray: &Ray) -> RaycastHit {
+    RaycastHit {
+        distance: 0.0,
+        point: ray.origin,
+        normal: -ray.direction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::api::collision::shapes::CollisionShape;
+
+    use super::{Ray, raycast, raycast_segment};
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn ray_hits_near_face_of_box() {
+        let aabb = CollisionShape::new_aabb(glam::vec2(2.0, -1.0), glam::vec2(4.0, 1.0));
+        let hit = raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0)), 10.0, &aabb).unwrap();
+        assert!((hit.get_distance() - 2.0).abs() < EPSILON);
+        assert_eq!(*hit.get_point(), glam::vec2(2.0, 0.0));
+        assert_eq!(*hit.get_normal(), glam::vec2(-1.0, 0.0));
+
+        assert!(raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0)), 1.5, &aabb).is_none());
+        assert!(raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(-1.0, 0.0)), 10.0, &aabb).is_none());
+        assert!(raycast(&Ray::new(glam::vec2(0.0, 2.0), glam::vec2(1.0, 0.0)), 10.0, &aabb).is_none());
+    }
+
+    #[test]
+    fn ray_hits_circle() {
+        let circle = CollisionShape::new_circle(glam::vec2(5.0, 0.0), 1.0);
+        let hit = raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(2.0, 0.0)), 10.0, &circle).unwrap();
+        assert!((hit.get_distance() - 4.0).abs() < EPSILON);
+        assert!((*hit.get_normal() - glam::vec2(-1.0, 0.0)).length() < EPSILON);
+        assert!(raycast(&Ray::new(glam::vec2(0.0, 2.0), glam::vec2(1.0, 0.0)), 10.0, &circle).is_none());
+    }
+
+    #[test]
+    fn ray_starting_inside_hits_at_origin() {
+        let circle = CollisionShape::new_circle(glam::vec2(0.0, 0.0), 1.0);
+        let hit = raycast(&Ray::new(glam::vec2(0.5, 0.0), glam::vec2(0.0, 1.0)), 10.0, &circle).unwrap();
+        assert_eq!(hit.get_distance(), 0.0);
+        assert_eq!(*hit.get_normal(), glam::vec2(0.0, -1.0));
+
+        let aabb = CollisionShape::new_aabb(glam::vec2(-1.0, -1.0), glam::vec2(1.0, 1.0));
+        assert_eq!(raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0)), 10.0, &aabb).unwrap().get_distance(), 0.0);
+    }
+
+    #[test]
+    fn segment_stops_at_its_end() {
+        let triangle = CollisionShape::new_polygon(vec![glam::vec2(3.0, -1.0), glam::vec2(3.0, 1.0), glam::vec2(5.0, 0.0)]);
+        assert!(raycast_segment(glam::vec2(0.0, 0.0), glam::vec2(2.0, 0.0), &triangle).is_none());
+        let hit = raycast_segment(glam::vec2(0.0, 0.0), glam::vec2(4.0, 0.0), &triangle).unwrap();
+        assert!((hit.get_distance() - 3.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn repeated_vertices_do_not_break_polygon_raycast() {
+        let polygon = CollisionShape::new_polygon(vec![
+            glam::vec2(2.0, -1.0),
+            glam::vec2(4.0, -1.0),
+            glam::vec2(4.0, 1.0),
+            glam::vec2(4.0, 1.0),
+            glam::vec2(2.0, 1.0),
+        ]);
+        let hit = raycast(&Ray::new(glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0)), 10.0, &polygon).unwrap();
+        assert!((hit.get_distance() - 2.0).abs() < EPSILON);
+        assert!((*hit.get_normal() - glam::vec2(-1.0, 0.0)).length() < EPSILON);
+    }
+}
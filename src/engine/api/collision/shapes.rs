@@ -0,0 +1,188 @@
+use glam::Vec2;
+
+// axis aligned bounding box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Aabb {
+    pub fn new(min: Vec2, max: Vec2) -> Aabb {
+        if min.x > max.x || min.y > max.y {
+            panic!("Minimum corner {:?} of aabb is not below and left of maximum corner {:?}", min, max);
+        }
+        Aabb { min, max }
+    }
+
+    pub fn from_center(center: Vec2, half_size: Vec2) -> Aabb {
+        Aabb::new(center - half_size, center + half_size)
+    }
+
+    pub fn get_min(&self) -> &Vec2 {
+        &self.min
+    }
+
+    pub fn get_max(&self) -> &Vec2 {
+        &self.max
+    }
+
+    pub fn get_center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn get_half_size(&self) -> Vec2 {
+        (self.max - self.min) / 2.0
+    }
+
+    // touching boxes intersect
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    pub fn contains_point(&self, point: &Vec2) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    // corners in counterclockwise order starting from bottom left
+    pub fn get_corners(&self) -> Vec<Vec2> {
+        vec![
+            self.min,
+            glam::vec2(self.max.x, self.min.y),
+            self.max,
+            glam::vec2(self.min.x, self.max.y),
+        ]
+    }
+}
+
+/**
+Shape in world space used by collision queries.
+```
+let ring = CollisionShape::new_circle(glam::vec2(0.0, 0.0), 1.0);
+let wall = CollisionShape::new_aabb(glam::vec2(0.5, -2.0), glam::vec2(3.0, 2.0));
+if let Some(contact) = test_collision(&ring, &wall) {
+    // move ring out of the wall
+    ring_position -= contact.get_normal() * contact.get_penetration();
+}
+```
+ */
+#[derive(Clone, Debug)]
+pub enum CollisionShape {
+    AABB { aabb: Aabb },
+    CIRCLE { center: Vec2, radius: f32 },
+    // convex polygon, vertices can be in any winding order
+    POLYGON { vertices: Vec<Vec2> },
+}
+
+impl CollisionShape {
+    pub fn new_aabb(min: Vec2, max: Vec2) -> CollisionShape {
+        CollisionShape::AABB { aabb: Aabb::new(min, max) }
+    }
+
+    pub fn new_circle(center: Vec2, radius: f32) -> CollisionShape {
+        CollisionShape::CIRCLE { center, radius }
+    }
+
+    pub fn new_polygon(vertices: Vec<Vec2>) -> CollisionShape {
+        if vertices.len() < 3 {
+            panic!("Collision polygon needs at least 3 vertices, got {}", vertices.len());
+        }
+        CollisionShape::POLYGON { vertices }
+    }
+
+    pub fn get_bounds(&self) -> Aabb {
+        match self {
+            CollisionShape::AABB { aabb } => *aabb,
+            CollisionShape::CIRCLE { center, radius } => Aabb::from_center(*center, glam::vec2(*radius, *radius)),
+            CollisionShape::POLYGON { vertices } => {
+                let min = vertices.iter().fold(vertices[0], |min, vertex| min.min(*vertex));
+                let max = vertices.iter().fold(vertices[0], |max, vertex| max.max(*vertex));
+                Aabb::new(min, max)
+            }
+        }
+    }
+
+    pub fn get_center(&self) -> Vec2 {
+        match self {
+            CollisionShape::AABB { aabb } => aabb.get_center(),
+            CollisionShape::CIRCLE { center, .. } => *center,
+            CollisionShape::POLYGON { vertices } => vertices.iter().fold(glam::vec2(0.0, 0.0), |sum, vertex| sum + *vertex) / vertices.len() as f32,
+        }
+    }
+
+    pub fn contains_point(&self, point: &Vec2) -> bool {
+        match self {
+            CollisionShape::AABB { aabb } => aabb.contains_point(point),
+            CollisionShape::CIRCLE { center, radius } => center.distance_squared(*point) <= radius * radius,
+            CollisionShape::POLYGON { vertices } => {
+                get_outward_edges(vertices).iter()
+                    .all(|(vertex, normal)| normal.dot(*point - *vertex) <= 0.0)
+            }
+        }
+    }
+
+    // vertices of shapes that have them, aabb becomes polygon
+    pub(crate) fn get_polygon_vertices(&self) -> Option<Vec<Vec2>> {
+        match self {
+            CollisionShape::AABB { aabb } => Some(aabb.get_corners()),
+            CollisionShape::CIRCLE { .. } => None,
+            CollisionShape::POLYGON { vertices } => Some(vertices.clone()),
+        }
+    }
+}
+
+// start vertex and normal of every edge from vertex i to vertex i + 1, normal points out of polygon whatever its winding order is
+// edges between repeated vertices have no direction and are left out
+pub(crate) fn get_outward_edges(vertices: &[Vec2]) -> Vec<(Vec2, Vec2)> {
+    let winding_sign = if get_signed_area(vertices) >= 0.0 { 1.0 } else { -1.0 };
+    (0..vertices.len())
+        .filter_map(|idx| {
+            let edge = vertices[(idx + 1) % vertices.len()] - vertices[idx];
+            if edge.length_squared() == 0.0 {
+                return None;
+            }
+            Some((vertices[idx], glam::vec2(edge.y, -edge.x).normalize() * winding_sign))
+        })
+        .collect()
+}
+
+pub(crate) fn get_outward_edge_normals(vertices: &[Vec2]) -> Vec<Vec2> {
+    get_outward_edges(vertices).into_iter().map(|(_, normal)| normal).collect()
+}
+
+// positive for counterclockwise polygons
+fn get_signed_area(vertices: &[Vec2]) -> f32 {
+    (0..vertices.len())
+        .map(|idx| {
+            let v1 = vertices[idx];
+            let v2 = vertices[(idx + 1) % vertices.len()];
+            v1.x * v2.y - v2.x * v1.y
+        })
+        .sum::<f32>() / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CollisionShape, get_outward_edge_normals};
+
+    #[test]
+    fn normals_point_out_in_both_windings() {
+        let counterclockwise = [glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0), glam::vec2(1.0, 1.0), glam::vec2(0.0, 1.0)];
+        let clockwise: Vec<glam::Vec2> = counterclockwise.iter().rev().cloned().collect();
+        assert_eq!(get_outward_edge_normals(&counterclockwise)[0], glam::vec2(0.0, -1.0));
+        assert_eq!(get_outward_edge_normals(&clockwise)[0], glam::vec2(0.0, 1.0));
+    }
+
+    #[test]
+    fn repeated_vertices_have_no_edge() {
+        let vertices = [glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0), glam::vec2(1.0, 0.0), glam::vec2(0.0, 1.0), glam::vec2(0.0, 0.0)];
+        let normals = get_outward_edge_normals(&vertices);
+        assert_eq!(normals.len(), 3);
+        assert!(normals.iter().all(|normal| normal.is_finite()));
+
+        let triangle = CollisionShape::new_polygon(vertices.to_vec());
+        assert!(triangle.contains_point(&glam::vec2(0.25, 0.25)));
+        assert!(!triangle.contains_point(&glam::vec2(0.75, 0.75)));
+    }
+}
@@ -0,0 +1,342 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, VecDeque};
+use std::str::FromStr;
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+// older lines are dropped
+const MAX_OUTPUT_LINES: usize = 64;
+const MAX_HISTORY_LINES: usize = 32;
+// typed by console toggle key, never part of command
+const TOGGLE_CHARACTER: char = '`';
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsoleCommand {
+    name: String,
+    args: Vec<String>,
+}
+
+impl ConsoleCommand {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_args(&self) -> &Vec<String> {
+        &self.args
+    }
+
+    // error message is ready to be printed to console
+    pub fn parse_arg<T: FromStr>(&self, idx: usize) -> Result<T, String> {
+        match self.args.get(idx) {
+            None => Err(format!("{}: missing argument {}", self.name, idx + 1)),
+            Some(arg) => arg.parse().map_err(|_| format!("{}: could not parse argument {} '{}'", self.name, idx + 1, arg))
+        }
+    }
+}
+
+/**
+Drop-down developer console. Games register commands with short usage text and poll for submitted ones
+in update the same way as for input actions. Commands submitted while handling events are seen in next update.
+```
+console.register_command("camera", "camera <x> <y> - moves camera to given point");
+// in update
+for command in update_context.get_engine_utilities().get_console().take_commands("camera") {
+    match (command.parse_arg::<f32>(0), command.parse_arg::<f32>(1)) { ... }
+}
+```
+ */
+pub struct Console {
+    // command name to its usage
+    commands: RefCell<BTreeMap<String, String>>,
+    pending: RefCell<Vec<ConsoleCommand>>,
+    output: RefCell<VecDeque<String>>,
+    history: RefCell<VecDeque<String>>,
+    // position in history while browsing it with arrow keys, none when editing new line
+    history_idx: Cell<Option<usize>>,
+    input_line: RefCell<String>,
+    open: Cell<bool>,
+}
+
+impl Console {
+    pub fn new() -> Console {
+        let console = Console {
+            commands: RefCell::new(BTreeMap::new()),
+            pending: RefCell::new(vec!()),
+            output: RefCell::new(VecDeque::new()),
+            history: RefCell::new(VecDeque::new()),
+            history_idx: Cell::new(None),
+            input_line: RefCell::new(String::new()),
+            open: Cell::new(false),
+        };
+        console.register_command("help", "help - lists all commands");
+        console
+    }
+
+    // registering command again replaces its usage
+    pub fn register_command(&self, name: &str, usage: &str) {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            panic!("Console command name '{}' must be a single word", name);
+        }
+        self.commands.borrow_mut().insert(String::from(name), String::from(usage));
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.get()
+    }
+
+    pub fn toggle(&self) {
+        self.open.set(!self.open.get());
+    }
+
+    // runs line as if it was typed into console
+    pub fn submit(&self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.print(format!("> {}", line));
+        let mut history = self.history.borrow_mut();
+        history.push_back(String::from(line));
+        if history.len() > MAX_HISTORY_LINES {
+            history.pop_front();
+        }
+
+        let mut words = line.split_whitespace().map(String::from);
+        let name = words.next().unwrap();
+        if name == "help" {
+            let usages: Vec<String> = self.commands.borrow().values().cloned().collect();
+            usages.into_iter().for_each(|usage| self.print(usage));
+            return;
+        }
+        if !self.commands.borrow().contains_key(&name) {
+            self.print(format!("Unknown command {}, type help to list commands", name));
+            return;
+        }
+        self.pending.borrow_mut().push(ConsoleCommand { name, args: words.collect() });
+    }
+
+    // removes submitted commands with given name and returns them in order they were submitted
+    pub fn take_commands(&self, name: &str) -> Vec<ConsoleCommand> {
+        let mut taken = vec!();
+        self.pending.borrow_mut().retain(|command| {
+            if command.name == name {
+                taken.push(command.clone());
+                return false;
+            }
+            true
+        });
+        taken
+    }
+
+    // must be called after game update, commands nothing took are reported and dropped
+    pub fn finish_update(&self) {
+        let unhandled: Vec<ConsoleCommand> = self.pending.borrow_mut().drain(..).collect();
+        for command in unhandled {
+            self.print(format!("Nothing handled command {}", command.name));
+        }
+    }
+
+    pub fn print(&self, line: String) {
+        let mut output = self.output.borrow_mut();
+        output.push_back(line);
+        if output.len() > MAX_OUTPUT_LINES {
+            output.pop_front();
+        }
+    }
+
+    // last num_lines lines, oldest first
+    pub fn get_output(&self, num_lines: usize) -> Vec<String> {
+        let output = self.output.borrow();
+        output.iter().skip(output.len().saturating_sub(num_lines)).cloned().collect()
+    }
+
+    pub fn get_input_line(&self) -> String {
+        self.input_line.borrow().clone()
+    }
+
+    // open console takes typed text and key presses, returns true when event was used
+    pub fn handle_event(&self, event: &Event) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+        match event {
+            Event::TextInput { text, .. } => {
+                self.input_line.borrow_mut().extend(text.chars().filter(|ch| *ch != TOGGLE_CHARACTER));
+                true
+            }
+            Event::KeyDown { keycode: Some(keycode), .. } => {
+                match keycode {
+                    Keycode::Return | Keycode::KpEnter => {
+                        let line = self.input_line.replace(String::new());
+                        self.history_idx.set(None);
+                        self.submit(&line);
+                    }
+                    Keycode::Backspace => {
+                        self.input_line.borrow_mut().pop();
+                    }
+                    Keycode::Up => self.browse_history(true),
+                    Keycode::Down => self.browse_history(false),
+                    _ => {}
+                }
+                true
+            }
+            // key releases pass through so keys held when console opened do not stay held
+            Event::TextEditing { .. } => true,
+            _ => false
+        }
+    }
+
+    fn browse_history(&self, older: bool) {
+        let history = self.history.borrow();
+        if history.is_empty() {
+            return;
+        }
+        let history_idx = match (self.history_idx.get(), older) {
+            (None, true) => Some(history.len() - 1),
+            (None, false) => None,
+            (Some(idx), true) => Some(idx.saturating_sub(1)),
+            (Some(idx), false) => if idx + 1 < history.len() { Some(idx + 1) } else { None }
+        };
+        self.history_idx.set(history_idx);
+        self.input_line.replace(history_idx.map_or(String::new(), |idx| history[idx].clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2::event::Event;
+    use sdl2::keyboard::{Keycode, Mod};
+
+    use super::{Console, MAX_OUTPUT_LINES};
+
+    fn console_with_camera() -> Console {
+        let console = Console::new();
+        console.register_command("camera", "camera <x> <y> - moves camera to given point");
+        console
+    }
+
+    fn key_down(keycode: Keycode) -> Event {
+        Event::KeyDown { timestamp: 0, window_id: 0, keycode: Some(keycode), scancode: None, keymod: Mod::empty(), repeat: false }
+    }
+
+    fn text_input(text: &str) -> Event {
+        Event::TextInput { timestamp: 0, window_id: 0, text: String::from(text) }
+    }
+
+    #[test]
+    fn submitted_command_is_split_into_name_and_args() {
+        let console = console_with_camera();
+        console.submit("  camera 1.5   -2 ");
+        let commands = console.take_commands("camera");
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].get_name(), "camera");
+        assert_eq!(*commands[0].get_args(), vec!["1.5", "-2"]);
+        assert_eq!(console.get_output(1), vec!["> camera 1.5   -2"]);
+    }
+
+    #[test]
+    fn commands_are_taken_by_name_in_submission_order() {
+        let console = console_with_camera();
+        console.register_command("wireframe", "wireframe - toggles wireframe");
+        console.submit("camera 1 1");
+        console.submit("wireframe");
+        console.submit("camera 2 2");
+        let cameras: Vec<String> = console.take_commands("camera").iter().map(|command| command.get_args()[0].clone()).collect();
+        assert_eq!(cameras, vec!["1", "2"]);
+        assert!(console.take_commands("camera").is_empty());
+        assert_eq!(console.take_commands("wireframe").len(), 1);
+    }
+
+    #[test]
+    fn unknown_and_empty_lines_are_not_queued() {
+        let console = console_with_camera();
+        console.submit("teleport 1 2");
+        console.submit("   ");
+        assert!(console.take_commands("teleport").is_empty());
+        assert_eq!(console.get_output(10), vec!["> teleport 1 2", "Unknown command teleport, type help to list commands"]);
+    }
+
+    #[test]
+    fn help_lists_usages() {
+        let console = console_with_camera();
+        console.submit("help");
+        assert_eq!(console.get_output(2), vec!["camera <x> <y> - moves camera to given point", "help - lists all commands"]);
+    }
+
+    #[test]
+    fn args_are_parsed_with_readable_errors() {
+        let console = console_with_camera();
+        console.submit("camera 1.5 abc");
+        let command = console.take_commands("camera").remove(0);
+        assert_eq!(command.parse_arg::<f32>(0), Ok(1.5));
+        assert_eq!(command.parse_arg::<f32>(1), Err(String::from("camera: could not parse argument 2 'abc'")));
+        assert_eq!(command.parse_arg::<f32>(2), Err(String::from("camera: missing argument 3")));
+    }
+
+    #[test]
+    fn unhandled_commands_are_reported_after_update() {
+        let console = console_with_camera();
+        console.submit("camera 1 2");
+        console.finish_update();
+        assert!(console.take_commands("camera").is_empty());
+        assert_eq!(console.get_output(1), vec!["Nothing handled command camera"]);
+    }
+
+    #[test]
+    fn output_keeps_last_lines() {
+        let console = Console::new();
+        for idx in 0..MAX_OUTPUT_LINES + 5 {
+            console.print(idx.to_string());
+        }
+        let output = console.get_output(MAX_OUTPUT_LINES + 5);
+        assert_eq!(output.len(), MAX_OUTPUT_LINES);
+        assert_eq!(output[0], "5");
+        assert_eq!(console.get_output(2), vec![(MAX_OUTPUT_LINES + 3).to_string(), (MAX_OUTPUT_LINES + 4).to_string()]);
+    }
+
+    #[test]
+    fn closed_console_ignores_events() {
+        let console = console_with_camera();
+        assert!(!console.handle_event(&text_input("camera")));
+        assert_eq!(console.get_input_line(), "");
+    }
+
+    #[test]
+    fn typed_line_is_submitted_on_return() {
+        let console = console_with_camera();
+        console.toggle();
+        assert!(console.handle_event(&text_input("`camera 3")));
+        assert!(console.handle_event(&text_input(" 45")));
+        assert!(console.handle_event(&key_down(Keycode::Backspace)));
+        assert_eq!(console.get_input_line(), "camera 3 4");
+        assert!(console.handle_event(&key_down(Keycode::Return)));
+        assert_eq!(console.get_input_line(), "");
+        assert_eq!(*console.take_commands("camera")[0].get_args(), vec!["3", "4"]);
+    }
+
+    #[test]
+    fn arrows_browse_history() {
+        let console = console_with_camera();
+        console.toggle();
+        console.submit("camera 1 1");
+        console.submit("camera 2 2");
+        console.handle_event(&key_down(Keycode::Up));
+        assert_eq!(console.get_input_line(), "camera 2 2");
+        console.handle_event(&key_down(Keycode::Up));
+        console.handle_event(&key_down(Keycode::Up));
+        // oldest line stays selected
+        assert_eq!(console.get_input_line(), "camera 1 1");
+        console.handle_event(&key_down(Keycode::Down));
+        assert_eq!(console.get_input_line(), "camera 2 2");
+        // going past newest line goes back to empty line
+        console.handle_event(&key_down(Keycode::Down));
+        assert_eq!(console.get_input_line(), "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn command_name_must_be_single_word() {
+        Console::new().register_command("move camera", "move camera - does not work");
+    }
+}
@@ -0,0 +1,120 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// frame time graph shows that many last frames
+pub const FRAME_TIME_HISTORY: usize = 120;
+
+/**
+Immediate mode debug overlay drawn by engine over everything, unaffected by cameras and post processing.
+Engine clears lines before every update, so they must be added every update. Lines of last update are shown
+until next one, with fixed timestep frames without update show them again and frames with several updates
+show lines of the last one only.
+```
+// in update
+let debug_overlay = update_context.get_engine_utilities().get_debug_overlay();
+if debug_overlay.is_visible() {
+    debug_overlay.add_line(format!("Player position {:?}", player_position));
+}
+```
+ */
+pub struct DebugOverlay {
+    visible: Cell<bool>,
+    // replaced by engine every rendered frame, drawn above lines of game
+    engine_lines: RefCell<Vec<String>>,
+    lines: RefCell<Vec<String>>,
+    // oldest first
+    frame_times: RefCell<VecDeque<Duration>>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> DebugOverlay {
+        DebugOverlay {
+            visible: Cell::new(false),
+            engine_lines: RefCell::new(vec!()),
+            lines: RefCell::new(vec!()),
+            frame_times: RefCell::new(VecDeque::with_capacity(FRAME_TIME_HISTORY)),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+    }
+
+    pub fn toggle(&self) {
+        self.visible.set(!self.visible.get());
+    }
+
+    // lines added while overlay is hidden are not drawn, but are cleared all the same
+    pub fn add_line(&self, line: String) {
+        self.lines.borrow_mut().push(line);
+    }
+
+    // called by engine before every update
+    pub fn clear_lines(&self) {
+        self.lines.borrow_mut().clear();
+    }
+
+    pub fn set_engine_lines(&self, lines: Vec<String>) {
+        self.engine_lines.replace(lines);
+    }
+
+    // engine lines followed by lines of game
+    pub fn get_lines(&self) -> Vec<String> {
+        self.engine_lines.borrow().iter().chain(self.lines.borrow().iter()).cloned().collect()
+    }
+
+    // recorded by engine every frame, also while overlay is hidden
+    pub fn record_frame_time(&self, frame_time: Duration) {
+        let mut frame_times = self.frame_times.borrow_mut();
+        if frame_times.len() == FRAME_TIME_HISTORY {
+            frame_times.pop_front();
+        }
+        frame_times.push_back(frame_time);
+    }
+
+    pub fn get_frame_times(&self) -> Vec<Duration> {
+        self.frame_times.borrow().iter().cloned().collect()
+    }
+
+    // over recorded history, none before first frame
+    pub fn get_average_frame_time(&self) -> Option<Duration> {
+        let frame_times = self.frame_times.borrow();
+        match frame_times.len() {
+            0 => None,
+            len => Some(frame_times.iter().sum::<Duration>() / len as u32)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DebugOverlay;
+
+    #[test]
+    fn lines_stay_until_cleared() {
+        let debug_overlay = DebugOverlay::new();
+        debug_overlay.add_line(String::from("first update"));
+        // frame without update shows them again
+        assert_eq!(debug_overlay.get_lines(), vec!["first update"]);
+        assert_eq!(debug_overlay.get_lines(), vec!["first update"]);
+        debug_overlay.clear_lines();
+        debug_overlay.add_line(String::from("second update"));
+        assert_eq!(debug_overlay.get_lines(), vec!["second update"]);
+    }
+
+    #[test]
+    fn engine_lines_come_first_and_are_replaced() {
+        let debug_overlay = DebugOverlay::new();
+        debug_overlay.add_line(String::from("game"));
+        debug_overlay.set_engine_lines(vec![String::from("fps")]);
+        debug_overlay.set_engine_lines(vec![String::from("fps again")]);
+        debug_overlay.clear_lines();
+        debug_overlay.add_line(String::from("game"));
+        assert_eq!(debug_overlay.get_lines(), vec!["fps again", "game"]);
+    }
+}
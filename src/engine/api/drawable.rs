@@ -5,6 +5,7 @@ use sdl2::event::Event;
 
 use crate::engine::api::engine_utilities::EngineUtilities;
 use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::input::InputManager;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::opengl_context::OpenglContext;
 
@@ -12,8 +13,31 @@ use crate::engine::opengl_context::OpenglContext;
 pub trait Drawable {
     fn render(&mut self, render_util: &RenderUtil);
     fn update(&mut self, update_context: &UpdateContext) {}
-    // todo: this should re turn some result so that event is not propagated further once consumed
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {}
+    // containers offer event to children from topmost (drawn last) to bottommost and stop once it is consumed
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
+        EventResult::IGNORED
+    }
+    // hit test, containers offer mouse events to children under the mouse before the others
+    fn is_under_mouse(&self, context: &OpenglContext, update_context: &UpdateContext) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventResult {
+    // event was handled and must not be passed to anything else
+    CONSUMED,
+    IGNORED,
+}
+
+impl EventResult {
+    pub fn consumed_if(consumed: bool) -> EventResult {
+        if consumed { EventResult::CONSUMED } else { EventResult::IGNORED }
+    }
+
+    pub fn is_consumed(&self) -> bool {
+        *self == EventResult::CONSUMED
+    }
 }
 
 pub struct UpdateContext<> {
@@ -33,6 +57,16 @@ impl UpdateContext {
         }
     }
 
+    // same context seen through another camera, e.g. for game rendered in split screen pane
+    pub fn with_camera_config(&self, camera_config: CameraConfig) -> UpdateContext {
+        UpdateContext {
+            sdl_mouse_position: self.sdl_mouse_position,
+            camera_config,
+            engine_utilities: Rc::clone(&self.engine_utilities),
+            delta_time: self.delta_time,
+        }
+    }
+
     pub fn get_engine_utilities(&self) -> &EngineUtilities {
         &self.engine_utilities
     }
@@ -46,4 +80,9 @@ impl UpdateContext {
     }
 
     pub fn get_delta_time(&self) -> &Duration { &self.delta_time }
+
+    // action queries, e.g. `update_context.get_input().just_pressed("start_game")`
+    pub fn get_input(&self) -> Rc<InputManager> {
+        self.engine_utilities.get_input_manager()
+    }
 }
\ No newline at end of file
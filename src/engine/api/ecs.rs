@@ -0,0 +1,3 @@
+pub mod world;
+pub mod components;
+pub mod systems;
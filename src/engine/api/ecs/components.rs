@@ -0,0 +1,184 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::engine::api::audio::AudioResource;
+use crate::engine::api::collision::shapes::{Aabb, CollisionShape};
+use crate::engine::api::countdown_timer::CountdownTimer;
+use crate::engine::api::maths::rectangle::Rectangle;
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::render_util::RenderOrder;
+use crate::engine::api::text_game_object::TextGameObject;
+use crate::engine::api::texture::Sprite;
+use crate::engine::rendering::material::Material;
+
+// position of entity is its `Transform` component, all other components are expressed in its local space
+
+// textured rectangle centered at entity position
+pub struct SpriteComponent {
+    rectangle: Rectangle<TexturedVertexDataLayout>,
+    // sprites without it are drawn in order of entities in storage
+    render_order: Option<RenderOrder>,
+}
+
+impl SpriteComponent {
+    pub fn new(size: &glam::Vec2, material: Material, sprite: Sprite) -> SpriteComponent {
+        SpriteComponent {
+            rectangle: Rectangle::new_textured(&glam::vec3(-size.x / 2.0, -size.y / 2.0, 0.0), size, material, sprite),
+            render_order: None,
+        }
+    }
+
+    pub fn with_render_order(mut self, render_order: RenderOrder) -> SpriteComponent {
+        self.render_order = Some(render_order);
+        self
+    }
+
+    pub fn get_render_order(&self) -> &Option<RenderOrder> {
+        &self.render_order
+    }
+
+    pub fn get_rectangle(&self) -> &Rectangle<TexturedVertexDataLayout> {
+        &self.rectangle
+    }
+
+    pub fn get_rectangle_mut(&mut self) -> &mut Rectangle<TexturedVertexDataLayout> {
+        &mut self.rectangle
+    }
+}
+
+// text starts at entity position, position of text game object is an offset from it
+pub struct TextComponent {
+    text: TextGameObject,
+}
+
+impl TextComponent {
+    pub fn new(text: TextGameObject) -> TextComponent {
+        TextComponent {
+            text
+        }
+    }
+
+    pub fn get_text(&self) -> &TextGameObject {
+        &self.text
+    }
+
+    pub fn get_text_mut(&mut self) -> &mut TextGameObject {
+        &mut self.text
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ColliderShape {
+    CIRCLE { radius: f32 },
+    // centered at entity position
+    RECTANGLE { size: glam::Vec2 },
+}
+
+#[derive(Clone, Debug)]
+pub struct ColliderComponent {
+    shape: ColliderShape,
+}
+
+impl ColliderComponent {
+    pub fn new(shape: ColliderShape) -> ColliderComponent {
+        ColliderComponent {
+            shape
+        }
+    }
+
+    pub fn get_shape(&self) -> &ColliderShape {
+        &self.shape
+    }
+
+    // collider placed in world by entity transform, rotated rectangles become polygons
+    pub fn to_collision_shape(&self, transform: &Transform) -> CollisionShape {
+        let matrix = transform.get_local_matrix();
+        let center = matrix.transform_point3(glam::vec3(0.0, 0.0, 0.0)).truncate();
+        match self.shape {
+            ColliderShape::CIRCLE { radius } => {
+                let scale = transform.get_scale();
+                CollisionShape::new_circle(center, radius * scale.x.abs().max(scale.y.abs()))
+            }
+            ColliderShape::RECTANGLE { size } => {
+                let corners: Vec<glam::Vec2> = Aabb::from_center(glam::vec2(0.0, 0.0), size / 2.0).get_corners().iter()
+                    .map(|corner| matrix.transform_point3(corner.extend(0.0)).truncate())
+                    .collect();
+                if *transform.get_rotation() == glam::Quat::IDENTITY {
+                    let min = corners.iter().fold(corners[0], |min, corner| min.min(*corner));
+                    let max = corners.iter().fold(corners[0], |max, corner| max.max(*corner));
+                    CollisionShape::new_aabb(min, max)
+                } else {
+                    CollisionShape::new_polygon(corners)
+                }
+            }
+        }
+    }
+}
+
+// advanced by timer system, repeating timers restart as soon as they finish
+pub struct TimerComponent {
+    timer: CountdownTimer,
+    repeating: bool,
+    finished_in_last_update: bool,
+}
+
+impl TimerComponent {
+    pub fn new(duration: Duration, repeating: bool) -> TimerComponent {
+        TimerComponent {
+            timer: CountdownTimer::new(duration),
+            repeating,
+            finished_in_last_update: false,
+        }
+    }
+
+    pub fn get_timer(&self) -> &CountdownTimer {
+        &self.timer
+    }
+
+    pub fn get_timer_mut(&mut self) -> &mut CountdownTimer {
+        &mut self.timer
+    }
+
+    // true only in the update in which timer finished
+    pub fn just_finished(&self) -> bool {
+        self.finished_in_last_update
+    }
+
+    pub fn advance(&mut self, time_passed: Duration) {
+        let was_finished = self.timer.is_finished();
+        self.timer.advance(time_passed);
+        self.finished_in_last_update = !was_finished && self.timer.is_finished();
+        if self.finished_in_last_update && self.repeating {
+            self.timer.restart();
+        }
+    }
+}
+
+// sound played by audio emitter system once it is requested
+pub struct AudioEmitterComponent {
+    audio: Rc<AudioResource>,
+    play_requested: bool,
+}
+
+impl AudioEmitterComponent {
+    pub fn new(audio: Rc<AudioResource>) -> AudioEmitterComponent {
+        AudioEmitterComponent {
+            audio,
+            play_requested: false,
+        }
+    }
+
+    pub fn get_audio(&self) -> &Rc<AudioResource> {
+        &self.audio
+    }
+
+    pub fn request_play(&mut self) {
+        self.play_requested = true
+    }
+
+    // returns whether play was requested and clears the request
+    pub fn take_play_request(&mut self) -> bool {
+        std::mem::replace(&mut self.play_requested, false)
+    }
+}
@@ -0,0 +1,176 @@
+use std::rc::Rc;
+
+use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::ecs::components::{AudioEmitterComponent, SpriteComponent, TextComponent, TimerComponent};
+use crate::engine::api::ecs::world::World;
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::render_util::RenderUtil;
+
+// logic working on all entities that have components it needs
+pub trait System {
+    fn update(&mut self, world: &World, update_context: &UpdateContext) {}
+    fn render(&mut self, world: &World, render_util: &RenderUtil) {}
+}
+
+// systems run stage by stage, within a stage in order they were added
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SystemStage {
+    // built in timers are advanced here so that game systems see timers that just finished
+    PRE_UPDATE,
+    UPDATE,
+    // built in audio emitters play sounds requested in update
+    POST_UPDATE,
+    // built in sprite and text rendering, drawn in this order
+    RENDER,
+}
+
+/**
+Runs systems of the world in order of their stages. Engine owns one schedule and treats it as one more drawable,
+after games are updated systems are updated and world is drawn after games are drawn.
+```
+engine.add_system(SystemStage::UPDATE, Box::new(MovementSystem::new()));
+```
+ */
+pub struct SystemSchedule {
+    world: Rc<World>,
+    systems: Vec<(SystemStage, Box<dyn System>)>,
+}
+
+impl SystemSchedule {
+    pub fn new(world: Rc<World>) -> SystemSchedule {
+        let mut schedule = SystemSchedule {
+            world,
+            systems: vec!(),
+        };
+        schedule.add_system(SystemStage::PRE_UPDATE, Box::new(TimerSystem {}));
+        schedule.add_system(SystemStage::POST_UPDATE, Box::new(AudioEmitterSystem {}));
+        schedule.add_system(SystemStage::RENDER, Box::new(SpriteRenderSystem {}));
+        schedule.add_system(SystemStage::RENDER, Box::new(TextRenderSystem {}));
+        schedule
+    }
+
+    pub fn add_system(&mut self, stage: SystemStage, system: Box<dyn System>) {
+        let idx = self.systems.iter().position(|(system_stage, _)| *system_stage > stage).unwrap_or(self.systems.len());
+        self.systems.insert(idx, (stage, system));
+    }
+
+    pub fn get_world(&self) -> Rc<World> {
+        Rc::clone(&self.world)
+    }
+}
+
+impl Drawable for SystemSchedule {
+    fn render(&mut self, render_util: &RenderUtil) {
+        let world = &self.world;
+        self.systems.iter_mut().for_each(|(_, system)| system.render(world, render_util));
+    }
+
+    fn update(&mut self, update_context: &UpdateContext) {
+        let world = &self.world;
+        self.systems.iter_mut().for_each(|(_, system)| system.update(world, update_context));
+    }
+}
+
+struct TimerSystem {}
+
+impl System for TimerSystem {
+    fn update(&mut self, world: &World, update_context: &UpdateContext) {
+        world.get_storage::<TimerComponent>().borrow_mut()
+            .iter_mut()
+            .for_each(|(_, timer)| timer.advance(*update_context.get_delta_time()));
+    }
+}
+
+struct AudioEmitterSystem {}
+
+impl System for AudioEmitterSystem {
+    fn update(&mut self, world: &World, update_context: &UpdateContext) {
+        let audio_manager = update_context.get_engine_utilities().get_audio_manager();
+        for (_, emitter) in world.get_storage::<AudioEmitterComponent>().borrow_mut().iter_mut() {
+            if emitter.take_play_request() {
+                audio_manager.play(Rc::clone(emitter.get_audio()));
+            }
+        }
+    }
+}
+
+// entities without transform are drawn at world origin
+struct SpriteRenderSystem {}
+
+impl System for SpriteRenderSystem {
+    fn render(&mut self, world: &World, render_util: &RenderUtil) {
+        let transforms = world.get_storage::<Transform>();
+        let transforms = transforms.borrow();
+        let identity = Transform::new();
+        for (entity, sprite) in world.get_storage::<SpriteComponent>().borrow_mut().iter_mut() {
+            let transform = transforms.get(entity).unwrap_or(&identity);
+            let sprite_render_util = match sprite.get_render_order() {
+                None => render_util.with_parent_transform(transform),
+                Some(render_order) => render_util.with_parent_transform(transform).with_render_order(*render_order)
+            };
+            sprite.get_rectangle_mut().render(&sprite_render_util);
+        }
+    }
+}
+
+struct TextRenderSystem {}
+
+impl System for TextRenderSystem {
+    fn render(&mut self, world: &World, render_util: &RenderUtil) {
+        let transforms = world.get_storage::<Transform>();
+        let transforms = transforms.borrow();
+        let identity = Transform::new();
+        for (entity, text) in world.get_storage::<TextComponent>().borrow_mut().iter_mut() {
+            let transform = transforms.get(entity).unwrap_or(&identity);
+            text.get_text_mut().render(&render_util.with_parent_transform(transform));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::engine::api::ecs::world::World;
+
+    use super::{System, SystemSchedule, SystemStage};
+
+    struct EmptySystem {}
+
+    impl System for EmptySystem {}
+
+    fn add_system(schedule: &mut SystemSchedule, stage: SystemStage) -> *const u8 {
+        let system: Box<dyn System> = Box::new(EmptySystem {});
+        let address = &*system as *const dyn System as *const u8;
+        schedule.add_system(stage, system);
+        address
+    }
+
+    fn get_addresses(schedule: &SystemSchedule, stage: SystemStage) -> Vec<*const u8> {
+        schedule.systems.iter()
+            .filter(|(system_stage, _)| *system_stage == stage)
+            .map(|(_, system)| &**system as *const dyn System as *const u8)
+            .collect()
+    }
+
+    #[test]
+    fn systems_run_by_stage_then_in_order_added() {
+        let mut schedule = SystemSchedule::new(Rc::new(World::new()));
+        let first_update = add_system(&mut schedule, SystemStage::UPDATE);
+        let game_render = add_system(&mut schedule, SystemStage::RENDER);
+        let pre_update = add_system(&mut schedule, SystemStage::PRE_UPDATE);
+        let second_update = add_system(&mut schedule, SystemStage::UPDATE);
+
+        let stages: Vec<SystemStage> = schedule.systems.iter().map(|(stage, _)| *stage).collect();
+        let mut sorted_stages = stages.clone();
+        sorted_stages.sort();
+        assert_eq!(stages, sorted_stages);
+
+        assert_eq!(get_addresses(&schedule, SystemStage::UPDATE), vec![first_update, second_update]);
+        // built in systems were added first so they run before game systems of the same stage
+        assert_eq!(*get_addresses(&schedule, SystemStage::PRE_UPDATE).last().unwrap(), pre_update);
+        assert_eq!(get_addresses(&schedule, SystemStage::PRE_UPDATE).len(), 2);
+        assert_eq!(*get_addresses(&schedule, SystemStage::RENDER).last().unwrap(), game_render);
+        assert_eq!(get_addresses(&schedule, SystemStage::RENDER).len(), 3);
+    }
+}
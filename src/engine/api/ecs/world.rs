@@ -0,0 +1,309 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+// index of entity together with generation, so that handles to despawned entities never point at entities reusing their index
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+}
+
+/**
+Container of entities and their components, every component type is kept in its own storage.
+Storages are behind separate RefCells so that system can read one kind of components while modifying another.
+```
+let world = engine_utilities.get_world();
+let ship = world.spawn();
+world.add_component(ship, Transform::from_translation(glam::vec3(1.0, 2.0, 0.0)));
+world.add_component(ship, TimerComponent::new(Duration::from_secs(2), false));
+
+let transforms = world.get_storage::<Transform>();
+for (entity, transform) in transforms.borrow_mut().iter_mut() {
+    transform.translate_by(glam::vec3(0.1, 0.0, 0.0));
+}
+```
+Storages must not be borrowed while entities are despawned or components of their type are added or removed.
+ */
+pub struct World {
+    entities: RefCell<EntityAllocator>,
+    storages: RefCell<HashMap<TypeId, Rc<dyn ErasedStorage>>>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World {
+            entities: RefCell::new(EntityAllocator {
+                generations: vec!(),
+                alive: vec!(),
+                free_indices: vec!(),
+            }),
+            storages: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn spawn(&self) -> Entity {
+        self.entities.borrow_mut().allocate()
+    }
+
+    // removes entity with all its components, does nothing for entities that are already despawned
+    pub fn despawn(&self, entity: Entity) {
+        if !self.entities.borrow_mut().free(entity) {
+            return;
+        }
+        let storages: Vec<Rc<dyn ErasedStorage>> = self.storages.borrow().values().cloned().collect();
+        storages.iter().for_each(|storage| storage.remove_entity(&entity));
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.borrow().is_alive(entity)
+    }
+
+    pub fn get_entities(&self) -> Vec<Entity> {
+        self.entities.borrow().get_alive()
+    }
+
+    // replaces component of the same type if entity already has one
+    pub fn add_component<T: 'static>(&self, entity: Entity, component: T) {
+        if !self.is_alive(entity) {
+            panic!("Adding component to entity {:?} that is not alive", entity);
+        }
+        self.get_storage::<T>().borrow_mut().insert(entity, component);
+    }
+
+    pub fn remove_component<T: 'static>(&self, entity: Entity) -> Option<T> {
+        self.get_storage::<T>().borrow_mut().remove(&entity)
+    }
+
+    pub fn has_component<T: 'static>(&self, entity: Entity) -> bool {
+        self.get_storage::<T>().borrow().contains(&entity)
+    }
+
+    pub fn with_component<T: 'static, R, F: FnOnce(&T) -> R>(&self, entity: Entity, f: F) -> Option<R> {
+        self.get_storage::<T>().borrow().get(&entity).map(f)
+    }
+
+    pub fn with_component_mut<T: 'static, R, F: FnOnce(&mut T) -> R>(&self, entity: Entity, f: F) -> Option<R> {
+        self.get_storage::<T>().borrow_mut().get_mut(&entity).map(f)
+    }
+
+    // storage is created on first access
+    pub fn get_storage<T: 'static>(&self) -> Rc<RefCell<ComponentStorage<T>>> {
+        let storage = Rc::clone(
+            self.storages.borrow_mut()
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| -> Rc<dyn ErasedStorage> { Rc::new(RefCell::new(ComponentStorage::<T> { components: BTreeMap::new() })) })
+        );
+        storage.into_any().downcast::<RefCell<ComponentStorage<T>>>().unwrap()
+    }
+}
+
+// components are ordered by entity so that systems process them in the same order on every run
+pub struct ComponentStorage<T> {
+    components: BTreeMap<Entity, T>,
+}
+
+impl<T> ComponentStorage<T> {
+    pub fn get(&self, entity: &Entity) -> Option<&T> {
+        self.components.get(entity)
+    }
+
+    pub fn get_mut(&mut self, entity: &Entity) -> Option<&mut T> {
+        self.components.get_mut(entity)
+    }
+
+    pub fn contains(&self, entity: &Entity) -> bool {
+        self.components.contains_key(entity)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&Entity, &T)> {
+        self.components.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=(&Entity, &mut T)> {
+        self.components.iter_mut()
+    }
+
+    pub fn get_entities(&self) -> Vec<Entity> {
+        self.components.keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    fn insert(&mut self, entity: Entity, component: T) {
+        self.components.insert(entity, component);
+    }
+
+    fn remove(&mut self, entity: &Entity) -> Option<T> {
+        self.components.remove(entity)
+    }
+}
+
+// lets world remove despawned entities from storages without knowing their component types
+trait ErasedStorage {
+    fn remove_entity(&self, entity: &Entity);
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any>;
+}
+
+impl<T: 'static> ErasedStorage for RefCell<ComponentStorage<T>> {
+    fn remove_entity(&self, entity: &Entity) {
+        self.borrow_mut().remove(entity);
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
+struct EntityAllocator {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free_indices: Vec<u32>,
+}
+
+impl EntityAllocator {
+    fn allocate(&mut self) -> Entity {
+        match self.free_indices.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity { index, generation: self.generations[index as usize] }
+            }
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity { index: (self.generations.len() - 1) as u32, generation: 0 }
+            }
+        }
+    }
+
+    // returns false when entity was not alive
+    fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        self.alive[entity.index as usize] = false;
+        self.generations[entity.index as usize] += 1;
+        self.free_indices.push(entity.index);
+        true
+    }
+
+    fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        index < self.alive.len() && self.alive[index] && self.generations[index] == entity.generation
+    }
+
+    fn get_alive(&self) -> Vec<Entity> {
+        self.alive.iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(index, _)| Entity { index: index as u32, generation: self.generations[index] })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entity, World};
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+
+    #[test]
+    fn despawned_index_is_reused_with_new_generation() {
+        let world = World::new();
+        let first = world.spawn();
+        let second = world.spawn();
+        assert_ne!(first, second);
+
+        world.despawn(first);
+        let reused = world.spawn();
+        assert_eq!(reused.get_index(), first.get_index());
+        assert_ne!(reused, first);
+        assert!(world.is_alive(reused));
+        assert!(!world.is_alive(first));
+        assert_eq!(world.get_entities(), vec![reused, second]);
+    }
+
+    #[test]
+    fn stale_handle_does_not_touch_entity_reusing_its_index() {
+        let world = World::new();
+        let stale = world.spawn();
+        world.despawn(stale);
+        let reused = world.spawn();
+        world.add_component(reused, Health(10));
+
+        world.despawn(stale);
+        assert!(world.is_alive(reused));
+        assert!(!world.has_component::<Health>(stale));
+        assert_eq!(world.with_component(reused, |health: &Health| health.0), Some(10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn adding_component_to_stale_handle_panics() {
+        let world = World::new();
+        let stale = world.spawn();
+        world.despawn(stale);
+        world.add_component(stale, Health(1));
+    }
+
+    #[test]
+    fn despawn_removes_components_of_every_type() {
+        let world = World::new();
+        let entity = world.spawn();
+        let other = world.spawn();
+        world.add_component(entity, Health(5));
+        world.add_component(entity, Name("ship"));
+        world.add_component(other, Health(7));
+
+        world.despawn(entity);
+        assert!(!world.has_component::<Health>(entity));
+        assert!(!world.has_component::<Name>(entity));
+        assert_eq!(world.get_storage::<Health>().borrow().len(), 1);
+        assert_eq!(world.get_storage::<Name>().borrow().len(), 0);
+    }
+
+    #[test]
+    fn components_are_replaced_and_removed() {
+        let world = World::new();
+        let entity = world.spawn();
+        world.add_component(entity, Health(1));
+        world.add_component(entity, Health(2));
+        assert_eq!(world.get_storage::<Health>().borrow().len(), 1);
+        world.with_component_mut(entity, |health: &mut Health| health.0 += 1);
+
+        assert_eq!(world.remove_component::<Health>(entity), Some(Health(3)));
+        assert_eq!(world.remove_component::<Health>(entity), None);
+        assert!(world.is_alive(entity));
+    }
+
+    #[test]
+    fn storage_iterates_in_entity_order() {
+        let world = World::new();
+        let entities: Vec<Entity> = (0..4).map(|_| world.spawn()).collect();
+        for entity in entities.iter().rev() {
+            world.add_component(*entity, Health(entity.get_index()));
+        }
+        // freed index comes back with higher generation, it is still ordered by index
+        world.despawn(entities[1]);
+        let reused = world.spawn();
+        world.add_component(reused, Health(100));
+
+        let storage = world.get_storage::<Health>();
+        let order: Vec<u32> = storage.borrow().iter().map(|(entity, _)| entity.get_index()).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+        assert_eq!(storage.borrow().get_entities()[1], reused);
+    }
+}
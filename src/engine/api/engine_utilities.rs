@@ -1,18 +1,44 @@
 use std::rc::Rc;
 
 use crate::engine::api::audio::AudioManager;
+use crate::engine::api::console::Console;
+use crate::engine::api::debug_overlay::DebugOverlay;
+use crate::engine::api::ecs::world::World;
+use crate::engine::api::input::InputManager;
+use crate::engine::api::post_processing::PostProcessing;
+use crate::engine::api::random::RandomGenerator;
 use crate::engine::api::resource_manager::ResourceManager;
 
 pub struct EngineUtilities {
     resource_manager: Rc<dyn ResourceManager>,
     audio_manager: Rc<AudioManager>,
+    post_processing: Rc<PostProcessing>,
+    input_manager: Rc<InputManager>,
+    random: Rc<RandomGenerator>,
+    world: Rc<World>,
+    console: Rc<Console>,
+    debug_overlay: Rc<DebugOverlay>,
 }
 
 impl EngineUtilities {
-    pub fn new(resource_manager: Rc<dyn ResourceManager>, audio_manager: Rc<AudioManager>) -> EngineUtilities {
+    pub fn new(resource_manager: Rc<dyn ResourceManager>,
+               audio_manager: Rc<AudioManager>,
+               post_processing: Rc<PostProcessing>,
+               input_manager: Rc<InputManager>,
+               random: Rc<RandomGenerator>,
+               world: Rc<World>,
+               console: Rc<Console>,
+               debug_overlay: Rc<DebugOverlay>,
+    ) -> EngineUtilities {
         EngineUtilities {
             resource_manager,
             audio_manager,
+            post_processing,
+            input_manager,
+            random,
+            world,
+            console,
+            debug_overlay,
         }
     }
 
@@ -23,4 +49,28 @@ impl EngineUtilities {
     pub fn get_audio_manager(&self) -> Rc<AudioManager> {
         Rc::clone(&self.audio_manager)
     }
+
+    pub fn get_post_processing(&self) -> Rc<PostProcessing> {
+        Rc::clone(&self.post_processing)
+    }
+
+    pub fn get_input_manager(&self) -> Rc<InputManager> {
+        Rc::clone(&self.input_manager)
+    }
+
+    pub fn get_random(&self) -> Rc<RandomGenerator> {
+        Rc::clone(&self.random)
+    }
+
+    pub fn get_world(&self) -> Rc<World> {
+        Rc::clone(&self.world)
+    }
+
+    pub fn get_console(&self) -> Rc<Console> {
+        Rc::clone(&self.console)
+    }
+
+    pub fn get_debug_overlay(&self) -> Rc<DebugOverlay> {
+        Rc::clone(&self.debug_overlay)
+    }
 }
\ No newline at end of file
@@ -1,5 +1,153 @@
+use std::rc::Rc;
+
+use crate::engine::api::colour::Colour;
+use crate::engine::api::post_processing::PostProcessing;
+
 pub trait GameController {
+    // main camera of the game, mouse positions in update context are resolved with it
     fn get_camera_config(&self) -> CameraConfig;
+
+    // cameras are rendered in order so later ones are drawn over earlier ones, e.g. hud camera goes last
+    fn get_cameras(&self) -> Vec<CameraConfig> {
+        vec![self.get_camera_config()]
+    }
+}
+
+pub const DEFAULT_FOV: f32 = 0.78;
+pub const DEFAULT_NEAR: f32 = 0.1;
+pub const DEFAULT_FAR: f32 = 100.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    // fov is vertical field of view in radians
+    PERSPECTIVE { fov: f32, near: f32, far: f32 },
+    // visible part of world grows with viewport, camera distance does not change size of objects
+    ORTHOGRAPHIC { world_units_per_pixel: f32, near: f32, far: f32 },
+    // one world unit is one pixel of viewport and origin is at its bottom left corner, used for screen space ui
+    SCREEN_SPACE { near: f32, far: f32 },
+}
+
+impl Projection {
+    pub fn new_perspective() -> Projection {
+        Projection::PERSPECTIVE { fov: DEFAULT_FOV, near: DEFAULT_NEAR, far: DEFAULT_FAR }
+    }
+
+    pub fn new_orthographic(world_units_per_pixel: f32) -> Projection {
+        if world_units_per_pixel <= 0.0 {
+            panic!("World units per pixel of orthographic projection must be positive, got {}", world_units_per_pixel);
+        }
+        Projection::ORTHOGRAPHIC { world_units_per_pixel, near: DEFAULT_NEAR, far: DEFAULT_FAR }
+    }
+
+    pub fn new_screen_space() -> Projection {
+        Projection::SCREEN_SPACE { near: DEFAULT_NEAR, far: DEFAULT_FAR }
+    }
+
+    pub fn get_matrix(&self, viewport_size: glam::Vec2) -> glam::Mat4 {
+        match *self {
+            Projection::PERSPECTIVE { fov, near, far } => {
+                glam::Mat4::perspective_rh_gl(fov, viewport_size.x / viewport_size.y, near, far)
+            }
+            Projection::ORTHOGRAPHIC { world_units_per_pixel, near, far } => {
+                let half_size = viewport_size * world_units_per_pixel / 2.0;
+                glam::Mat4::orthographic_rh_gl(-half_size.x, half_size.x, -half_size.y, half_size.y, near, far)
+            }
+            Projection::SCREEN_SPACE { near, far } => {
+                glam::Mat4::orthographic_rh_gl(0.0, viewport_size.x, 0.0, viewport_size.y, near, far)
+            }
+        }
+    }
+}
+
+// everything is drawn on default layer unless drawable checks layers itself
+pub const DEFAULT_LAYER: u32 = 0;
+// screen space overlay that is usually rendered by separate camera
+pub const HUD_LAYER: u32 = 1;
+pub const MAX_LAYERS: u32 = 32;
+
+/**
+Set of render layers that camera shows, drawables ask `RenderUtil::is_layer_visible` before rendering.
+```
+let world_mask = LayerMask::all().without(HUD_LAYER);
+let hud_mask = LayerMask::only(HUD_LAYER);
+```
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayerMask {
+    bits: u32,
+}
+
+impl LayerMask {
+    pub fn all() -> LayerMask {
+        LayerMask { bits: u32::MAX }
+    }
+
+    pub fn none() -> LayerMask {
+        LayerMask { bits: 0 }
+    }
+
+    pub fn only(layer: u32) -> LayerMask {
+        LayerMask::none().with(layer)
+    }
+
+    pub fn with(self, layer: u32) -> LayerMask {
+        LayerMask { bits: self.bits | LayerMask::get_layer_bit(layer) }
+    }
+
+    pub fn without(self, layer: u32) -> LayerMask {
+        LayerMask { bits: self.bits & !LayerMask::get_layer_bit(layer) }
+    }
+
+    pub fn contains(&self, layer: u32) -> bool {
+        self.bits & LayerMask::get_layer_bit(layer) != 0
+    }
+
+    fn get_layer_bit(layer: u32) -> u32 {
+        if layer >= MAX_LAYERS {
+            panic!("Layer {} is out of range, there are only {} layers", layer, MAX_LAYERS);
+        }
+        1 << layer
+    }
+}
+
+const VIEWPORT_RECT_TOLERANCE: f32 = 0.0001;
+
+/**
+Part of window that camera renders to, in fractions of window size with origin at bottom left corner like in opengl.
+```
+let left_half = ViewportRect::new(0.0, 0.0, 0.5, 1.0);
+```
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewportRect {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl ViewportRect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> ViewportRect {
+        // fractions like thirds do not add up to exactly 1
+        let max_edge = 1.0 + VIEWPORT_RECT_TOLERANCE;
+        if x < 0.0 || y < 0.0 || width <= 0.0 || height <= 0.0 || x + width > max_edge || y + height > max_edge {
+            panic!("Viewport rect at ({}, {}) with size ({}, {}) does not fit in window", x, y, width, height);
+        }
+        ViewportRect { x, y, width, height }
+    }
+
+    pub fn full() -> ViewportRect {
+        ViewportRect { x: 0.0, y: 0.0, width: 1.0, height: 1.0 }
+    }
+
+    // x, y, width and height in pixels of surface with given size, neighbouring rects do not overlap nor leave gaps
+    pub fn to_pixels(&self, surface_size: glam::Vec2) -> (i32, i32, i32, i32) {
+        let left = (self.x * surface_size.x).round() as i32;
+        let bottom = (self.y * surface_size.y).round() as i32;
+        let right = ((self.x + self.width) * surface_size.x).round() as i32;
+        let top = ((self.y + self.height) * surface_size.y).round() as i32;
+        (left, bottom, right - left, top - bottom)
+    }
 }
 
 #[derive(Clone)]
@@ -7,17 +155,87 @@ pub struct CameraConfig {
     eye_position: glam::Vec3,
     up_vector: glam::Vec3,
     look_at: glam::Vec3,
+    projection: Projection,
+    viewport: ViewportRect,
+    // part of window covered by viewport is cleared before camera renders, nothing is cleared when not set
+    clear_colour: Option<Colour>,
+    layer_mask: LayerMask,
+    // passes applied only to what this camera draws, inside its viewport
+    post_processing: Option<Rc<PostProcessing>>,
 }
 
 impl CameraConfig {
+    // perspective camera with default field of view, see `with_projection` for other projections
     pub fn new(eye_position: glam::Vec3, up_vector: glam::Vec3, look_at: glam::Vec3) -> CameraConfig {
         CameraConfig {
             eye_position,
             up_vector,
             look_at,
+            projection: Projection::new_perspective(),
+            viewport: ViewportRect::full(),
+            clear_colour: None,
+            layer_mask: LayerMask::all(),
+            post_processing: None,
         }
     }
 
+    // camera for hud drawn over world, sees only hud layer and positions are in pixels of its viewport
+    pub fn new_screen_space() -> CameraConfig {
+        CameraConfig::new(glam::vec3(0.0, 0.0, 1.0), glam::vec3(0.0, 1.0, 0.0), glam::vec3(0.0, 0.0, 0.0))
+            .with_projection(Projection::new_screen_space())
+            .with_layer_mask(LayerMask::only(HUD_LAYER))
+    }
+
+    pub fn with_projection(mut self, projection: Projection) -> CameraConfig {
+        self.projection = projection;
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: ViewportRect) -> CameraConfig {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn with_clear_colour(mut self, clear_colour: Colour) -> CameraConfig {
+        self.clear_colour = Some(clear_colour);
+        self
+    }
+
+    pub fn with_layer_mask(mut self, layer_mask: LayerMask) -> CameraConfig {
+        self.layer_mask = layer_mask;
+        self
+    }
+
+    pub fn with_post_processing(mut self, post_processing: Rc<PostProcessing>) -> CameraConfig {
+        self.post_processing = Some(post_processing);
+        self
+    }
+
+    pub fn without_post_processing(mut self) -> CameraConfig {
+        self.post_processing = None;
+        self
+    }
+
+    pub fn get_viewport(&self) -> &ViewportRect {
+        &self.viewport
+    }
+
+    pub fn get_clear_colour(&self) -> &Option<Colour> {
+        &self.clear_colour
+    }
+
+    pub fn get_layer_mask(&self) -> &LayerMask {
+        &self.layer_mask
+    }
+
+    pub fn get_post_processing(&self) -> &Option<Rc<PostProcessing>> {
+        &self.post_processing
+    }
+
+    pub fn get_projection(&self) -> &Projection {
+        &self.projection
+    }
+
     pub fn get_eye_position(&self) -> &glam::Vec3 {
         &self.eye_position
     }
@@ -38,7 +256,8 @@ impl CameraConfig {
         )
     }
 
-    pub fn get_projection_matrix(&self, aspect_ratio: f32) -> glam::Mat4 {
-       glam::Mat4::perspective_rh_gl(0.78, aspect_ratio, 0.1, 100.0)
+    // viewport size is in pixels, size of the part of window given by viewport rect and not whole window
+    pub fn get_projection_matrix(&self, viewport_size: glam::Vec2) -> glam::Mat4 {
+        self.projection.get_matrix(viewport_size)
     }
 }
\ No newline at end of file
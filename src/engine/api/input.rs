@@ -0,0 +1,350 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use sdl2::controller::{Axis, Button};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::{MouseButton, MouseWheelDirection};
+
+// gamepad sticks never rest exactly at 0
+const GAMEPAD_AXIS_DEAD_ZONE: i16 = 8000;
+
+/**
+Maps named actions to physical inputs so that games do not need to match on raw sdl events.
+Every action can have many bindings, each with a scale that is applied to binding value when computing axis value,
+negative scale turns binding into the negative half of an axis.
+Bindings are loaded from config file with lines in format `action = binding, binding, ...`:
+```
+camera_horizontal = -key:Left, key:Right, axis:leftx
+zoom = wheel:up, -wheel:down
+start_game = key:Return, mouse:Left, gamepad:start
+```
+State is fed by engine with every polled event, presses that happened since previous update are reported by
+`just_pressed` in the next update only, also when engine runs many updates per frame.
+ */
+pub struct InputManager {
+    bindings: RefCell<HashMap<String, Vec<ActionBinding>>>,
+    state: RefCell<InputState>,
+}
+
+struct InputState {
+    held: HashSet<InputBinding>,
+    pressed_since_update: HashSet<InputBinding>,
+    released_since_update: HashSet<InputBinding>,
+    // wheel does not have held state, it only accumulates ticks scrolled since last update
+    wheel_since_update: HashMap<InputBinding, f32>,
+    gamepad_axes: HashMap<Axis, i16>,
+}
+
+impl InputManager {
+    pub fn new() -> InputManager {
+        InputManager {
+            bindings: RefCell::new(HashMap::new()),
+            state: RefCell::new(InputState {
+                held: HashSet::new(),
+                pressed_since_update: HashSet::new(),
+                released_since_update: HashSet::new(),
+                wheel_since_update: HashMap::new(),
+                gamepad_axes: HashMap::new(),
+            }),
+        }
+    }
+
+    // lines are added to existing bindings, empty lines and lines starting with # are skipped
+    // nothing is added when any line is wrong
+    pub fn load_bindings<I: Iterator<Item=String>>(&self, lines: I) -> Result<(), InputConfigError> {
+        let loaded = InputManager::parse_bindings(lines)?;
+        let mut bindings = self.bindings.borrow_mut();
+        for (action, action_bindings) in loaded {
+            bindings.entry(action).or_insert_with(|| vec!()).extend(action_bindings);
+        }
+        Ok(())
+    }
+
+    // replaces all bindings with loaded ones, current bindings are kept when any line is wrong
+    pub fn reload_bindings<I: Iterator<Item=String>>(&self, lines: I) -> Result<(), InputConfigError> {
+        let loaded = InputManager::parse_bindings(lines)?;
+        self.bindings.replace(loaded);
+        Ok(())
+    }
+
+    fn parse_bindings<I: Iterator<Item=String>>(lines: I) -> Result<HashMap<String, Vec<ActionBinding>>, InputConfigError> {
+        let mut bindings: HashMap<String, Vec<ActionBinding>> = HashMap::new();
+        for (line_idx, line) in lines.enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.splitn(2, '=').collect();
+            if parts.len() != 2 || parts[0].trim().is_empty() {
+                return Err(InputConfigError::MalformedLine { line_number: line_idx + 1, line: String::from(line) });
+            }
+            let action = parts[0].trim();
+            for binding in parts[1].split(',').map(|binding| binding.trim()).filter(|binding| !binding.is_empty()) {
+                let (scale, binding) = match binding.strip_prefix('-') {
+                    None => (1.0, binding),
+                    Some(negated) => (-1.0, negated)
+                };
+                bindings.entry(String::from(action))
+                    .or_insert_with(|| vec!())
+                    .push(ActionBinding { input: InputBinding::from_config(binding)?, scale });
+            }
+        }
+        Ok(bindings)
+    }
+
+    pub fn bind(&self, action: &str, input: InputBinding, scale: f32) {
+        self.bindings.borrow_mut()
+            .entry(String::from(action))
+            .or_insert_with(|| vec!())
+            .push(ActionBinding { input, scale });
+    }
+
+    // replaces all bindings of action with given one
+    pub fn rebind(&self, action: &str, input: InputBinding, scale: f32) {
+        self.clear_bindings(action);
+        self.bind(action, input, scale);
+    }
+
+    pub fn clear_bindings(&self, action: &str) {
+        self.bindings.borrow_mut().remove(action);
+    }
+
+    pub fn clear_all_bindings(&self) {
+        self.bindings.borrow_mut().clear();
+    }
+
+    pub fn get_bindings(&self, action: &str) -> Vec<ActionBinding> {
+        self.bindings.borrow().get(action).cloned().unwrap_or_else(|| vec!())
+    }
+
+    pub fn is_pressed(&self, action: &str) -> bool {
+        let state = self.state.borrow();
+        self.any_binding(action, |binding| {
+            state.held.contains(&binding.input)
+                || state.wheel_since_update.contains_key(&binding.input)
+                || state.is_axis_active(&binding.input, binding.scale)
+        })
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        let state = self.state.borrow();
+        self.any_binding(action, |binding| state.pressed_since_update.contains(&binding.input))
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        let state = self.state.borrow();
+        self.any_binding(action, |binding| state.released_since_update.contains(&binding.input))
+    }
+
+    // sum of scaled values of all bindings clamped to [-1, 1], wheel ticks are not clamped
+    pub fn axis_value(&self, action: &str) -> f32 {
+        let state = self.state.borrow();
+        let mut value = 0.0;
+        let mut wheel_value = 0.0;
+        for binding in self.bindings.borrow().get(action).iter().flat_map(|bindings| bindings.iter()) {
+            match &binding.input {
+                InputBinding::MOUSE_WHEEL { .. } => {
+                    wheel_value += state.wheel_since_update.get(&binding.input).cloned().unwrap_or(0.0) * binding.scale;
+                }
+                input => {
+                    value += state.get_value(input) * binding.scale;
+                }
+            }
+        }
+        value.max(-1.0).min(1.0) + wheel_value
+    }
+
+    // engine calls this for every polled event
+    pub fn handle_event(&self, event: &Event) {
+        let mut state = self.state.borrow_mut();
+        match event {
+            Event::KeyDown { keycode: Some(keycode), repeat: false, .. } => {
+                state.press(InputBinding::KEY { keycode: *keycode })
+            }
+            Event::KeyUp { keycode: Some(keycode), .. } => {
+                state.release(InputBinding::KEY { keycode: *keycode })
+            }
+            Event::MouseButtonDown { mouse_btn, .. } => {
+                state.press(InputBinding::MOUSE_BUTTON { button: *mouse_btn })
+            }
+            Event::MouseButtonUp { mouse_btn, .. } => {
+                state.release(InputBinding::MOUSE_BUTTON { button: *mouse_btn })
+            }
+            Event::MouseWheel { y, direction, .. } => {
+                let ticks = *y as f32 * if direction == &MouseWheelDirection::Flipped { -1.0 } else { 1.0 };
+                if ticks != 0.0 {
+                    let wheel = InputBinding::MOUSE_WHEEL { up: ticks > 0.0 };
+                    *state.wheel_since_update.entry(wheel.clone()).or_insert(0.0) += ticks.abs();
+                    state.pressed_since_update.insert(wheel);
+                }
+            }
+            Event::ControllerButtonDown { button, .. } => {
+                state.press(InputBinding::GAMEPAD_BUTTON { button: *button })
+            }
+            Event::ControllerButtonUp { button, .. } => {
+                state.release(InputBinding::GAMEPAD_BUTTON { button: *button })
+            }
+            Event::ControllerAxisMotion { axis, value, .. } => {
+                state.gamepad_axes.insert(*axis, *value);
+            }
+            _ => {}
+        }
+    }
+
+    // engine calls this after every update so that presses are reported once
+    pub fn finish_update(&self) {
+        let mut state = self.state.borrow_mut();
+        state.pressed_since_update.clear();
+        state.released_since_update.clear();
+        state.wheel_since_update.clear();
+    }
+
+    fn any_binding<F: Fn(&ActionBinding) -> bool>(&self, action: &str, predicate: F) -> bool {
+        match self.bindings.borrow().get(action) {
+            None => false,
+            Some(bindings) => bindings.iter().any(|binding| predicate(binding))
+        }
+    }
+}
+
+impl InputState {
+    fn press(&mut self, input: InputBinding) {
+        self.pressed_since_update.insert(input.clone());
+        self.held.insert(input);
+    }
+
+    fn release(&mut self, input: InputBinding) {
+        if self.held.remove(&input) {
+            self.released_since_update.insert(input);
+        }
+    }
+
+    fn get_value(&self, input: &InputBinding) -> f32 {
+        match input {
+            InputBinding::GAMEPAD_AXIS { axis } => {
+                let value = self.gamepad_axes.get(axis).cloned().unwrap_or(0);
+                if value.abs() < GAMEPAD_AXIS_DEAD_ZONE { 0.0 } else { value as f32 / i16::MAX as f32 }
+            }
+            input => {
+                if self.held.contains(input) { 1.0 } else { 0.0 }
+            }
+        }
+    }
+
+    // axis counts as pressed when pushed in direction of binding scale
+    fn is_axis_active(&self, input: &InputBinding, scale: f32) -> bool {
+        match input {
+            InputBinding::GAMEPAD_AXIS { .. } => self.get_value(input) * scale > 0.0,
+            _ => false
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ActionBinding {
+    input: InputBinding,
+    scale: f32,
+}
+
+impl ActionBinding {
+    pub fn get_input(&self) -> &InputBinding {
+        &self.input
+    }
+
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    KEY { keycode: Keycode },
+    MOUSE_BUTTON { button: MouseButton },
+    MOUSE_WHEEL { up: bool },
+    GAMEPAD_BUTTON { button: Button },
+    GAMEPAD_AXIS { axis: Axis },
+}
+
+impl InputBinding {
+    // format is `kind:name`, for example `key:Left`, `mouse:Right`, `wheel:up`, `gamepad:a`, `axis:leftx`
+    // key names are sdl key names, gamepad button and axis names are sdl game controller names
+    pub fn from_config(binding: &str) -> Result<InputBinding, InputConfigError> {
+        let unknown = || InputConfigError::UnknownInput { input: String::from(binding) };
+        let parts: Vec<&str> = binding.splitn(2, ':').collect();
+        if parts.len() != 2 {
+            return Err(unknown());
+        }
+        let name = parts[1].trim();
+        match parts[0].trim() {
+            "key" => Keycode::from_name(name).map(|keycode| InputBinding::KEY { keycode }).ok_or_else(unknown),
+            "mouse" => {
+                let button = match name.to_lowercase().as_str() {
+                    "left" => MouseButton::Left,
+                    "middle" => MouseButton::Middle,
+                    "right" => MouseButton::Right,
+                    "x1" => MouseButton::X1,
+                    "x2" => MouseButton::X2,
+                    _ => return Err(unknown())
+                };
+                Ok(InputBinding::MOUSE_BUTTON { button })
+            }
+            "wheel" => {
+                match name.to_lowercase().as_str() {
+                    "up" => Ok(InputBinding::MOUSE_WHEEL { up: true }),
+                    "down" => Ok(InputBinding::MOUSE_WHEEL { up: false }),
+                    _ => Err(unknown())
+                }
+            }
+            "gamepad" => Button::from_string(name).map(|button| InputBinding::GAMEPAD_BUTTON { button }).ok_or_else(unknown),
+            "axis" => Axis::from_string(name).map(|axis| InputBinding::GAMEPAD_AXIS { axis }).ok_or_else(unknown),
+            _ => Err(unknown())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputConfigError {
+    MalformedLine { line_number: usize, line: String },
+    UnknownInput { input: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2::mouse::MouseButton;
+
+    use super::{InputBinding, InputManager};
+
+    fn lines(config: &str) -> impl Iterator<Item=String> + '_ {
+        config.lines().map(String::from)
+    }
+
+    #[test]
+    fn reload_replaces_bindings() {
+        let input_manager = InputManager::new();
+        input_manager.load_bindings(lines("fire = mouse:left\nzoom = wheel:up")).unwrap();
+        input_manager.reload_bindings(lines("fire = mouse:right")).unwrap();
+        let fire = input_manager.get_bindings("fire");
+        assert_eq!(fire.len(), 1);
+        assert_eq!(*fire[0].get_input(), InputBinding::MOUSE_BUTTON { button: MouseButton::Right });
+        assert!(input_manager.get_bindings("zoom").is_empty());
+    }
+
+    #[test]
+    fn failed_reload_keeps_bindings() {
+        let input_manager = InputManager::new();
+        input_manager.load_bindings(lines("fire = mouse:left\nzoom = -wheel:down")).unwrap();
+        assert!(input_manager.reload_bindings(lines("fire = mouse:right\nthis line is malformed")).is_err());
+        assert!(input_manager.reload_bindings(lines("fire = mouse:nope")).is_err());
+        assert_eq!(*input_manager.get_bindings("fire")[0].get_input(), InputBinding::MOUSE_BUTTON { button: MouseButton::Left });
+        assert_eq!(input_manager.get_bindings("fire").len(), 1);
+        assert_eq!(input_manager.get_bindings("zoom")[0].get_scale(), -1.0);
+    }
+
+    #[test]
+    fn failed_load_adds_nothing() {
+        let input_manager = InputManager::new();
+        assert!(input_manager.load_bindings(lines("fire = mouse:left\nzoom = wheel:sideways")).is_err());
+        assert!(input_manager.get_bindings("fire").is_empty());
+    }
+}
@@ -7,3 +7,4 @@ pub mod point;
 pub mod circle;
 pub mod rectangle;
 pub mod util;
+pub mod transform;
@@ -1,15 +1,12 @@
 use std::convert::TryInto;
 use std::f32::consts::PI;
-use std::ops::Add;
-
-use glam::Vec3;
 
 use crate::engine::api::colour::Colour;
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
 use crate::engine::api::render_util::RenderUtil;
-use crate::engine::rendering::data::f32_f32_f32;
 use crate::engine::rendering::material::Material;
 use crate::engine::rendering::shape_drawing_component::ShapeDrawingComponent;
 
@@ -21,18 +18,18 @@ pub struct Circle {
     drawing_component: ShapeDrawingComponent<ColoredVertexDataLayout>,
     vertices: [ColoredVertexDataLayout; 32],
     indices: [i32; 93],
-    middle: glam::Vec3,
     radius: f32,
     material: Material,
     color: Colour,
-    scale: glam::Vec3,
+    // translation of transform is the middle of a circle
+    transform: Transform,
 }
 
 impl Circle {
     // position here is the middle of a circle
     pub fn new_colored(position: &glam::Vec3, color: Colour, radius: f32, material: Material) -> Circle {
         // todo: unhadrcode it, right now those values are hardcoded in vertics and indices types definitions
-        let vertices = Circle::init_vertices(NUM_VERTICES, position, color, radius);
+        let vertices = Circle::init_vertices(NUM_VERTICES, color, radius);
         let indices = Circle::init_indices(NUM_VERTICES);
 
         let drawing_component = ShapeDrawingComponent::new(
@@ -45,21 +42,19 @@ impl Circle {
             drawing_component,
             vertices: vertices.try_into().unwrap(),
             indices: indices.try_into().unwrap(),
-            middle: position.clone(),
             radius,
             material,
             color,
-            scale: glam::vec3(1.0, 1.0, 1.0),
+            transform: Transform::from_translation(position.clone()),
         }
     }
 
-    fn init_vertices(num_vertices: i32, position: &glam::Vec3, color: Colour, radius: f32) -> Vec<ColoredVertexDataLayout> {
+    // vertices are around local origin, circle is moved to its position by transform
+    fn init_vertices(num_vertices: i32, color: Colour, radius: f32) -> Vec<ColoredVertexDataLayout> {
         let mut vertices = vec!();
-        // todo: is it possible to use macro instead of this?
-        let p: f32_f32_f32 = position.clone().into();
         vertices.push(
             ColoredVertexDataLayout {
-                pos: p,
+                pos: (0.0, 0.0, 0.0).into(),
                 clr: color.into(),
             }
         );
@@ -68,11 +63,7 @@ impl Circle {
             let angle = 2.0 * PI * (i as f32) / (num_vertices - 1) as f32;
             vertices.push(
                 ColoredVertexDataLayout {
-                    pos: position.add(glam::vec3(
-                        angle.cos() * radius,
-                        angle.sin() * radius,
-                        0.0)
-                    ).into(),
+                    pos: (angle.cos() * radius, angle.sin() * radius, 0.0).into(),
                     clr: color.into(),
                 }
             )
@@ -103,20 +94,17 @@ impl Drawable for Circle {
         self.drawing_component.render(
             self.indices.len() as i32,
             gl::TRIANGLES,
-            *self.get_pos(),
+            render_util.get_world_matrix(self.transform.get_local_matrix()),
             render_util,
             &mut self.material,
-            self.scale.clone(),
-            glam::vec3(0.0, 0.0, 0.0),
-
         )
     }
 }
 
 impl Area for Circle {
-    // todo does not work with scale
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        (self.middle.x - point.0).powf(2.0) + (self.middle.y - point.1).powf(2.0) <= self.radius.powf(2.0)
+    // in local space circle is centered at origin, scaled circle becomes an ellipse in world space
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        local_point.0.powf(2.0) + local_point.1.powf(2.0) <= self.radius.powf(2.0)
     }
 
     fn area(&self) -> f32 {
@@ -127,23 +115,11 @@ impl Area for Circle {
         panic!("Circle does not have any vertices!")
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        &self.middle
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn move_to(&mut self, final_position: Vec3) {
-        self.middle = final_position
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
-
-    fn move_by(&mut self, offset: Vec3) {
-        self.middle = self.middle.add(offset)
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        &self.scale
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        self.scale = new_scale
-    }
-}
\ No newline at end of file
+}
@@ -1,9 +1,6 @@
-use std::ops::{Add, Sub};
-
-use glam::Vec3;
-
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex::VertexShaderDataLayout;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::glam_utils::to_glam_vec;
@@ -24,7 +21,7 @@ pub struct Point<T> where T: VertexShaderDataLayout {
     drawing_component: ShapeDrawingComponent<T>,
     vertices: [T; 1],
     material: Material,
-    world_position: glam::Vec3,
+    transform: Transform,
 }
 
 impl<T: VertexShaderDataLayout> Point<T> {
@@ -38,7 +35,7 @@ impl<T: VertexShaderDataLayout> Point<T> {
             drawing_component,
             vertices,
             material,
-            world_position,
+            transform: Transform::from_translation(world_position),
         };
     }
 }
@@ -48,19 +45,16 @@ impl<T: VertexShaderDataLayout> Drawable for Point<T> {
         self.drawing_component.render(
             1,
             gl::POINTS,
-            *self.get_pos(),
+            render_util.get_world_matrix(self.transform.get_local_matrix()),
             render_util,
             &mut self.material,
-            glam::vec3(1.0, 1.0, 1.0),
-            glam::vec3(0.0, 0.0, 0.0),
-
         )
     }
 }
 
 impl<T: VertexShaderDataLayout> Area for Point<T> {
     // todo this needs 3d
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
         false
     }
 
@@ -72,23 +66,11 @@ impl<T: VertexShaderDataLayout> Area for Point<T> {
         self.vertices.len()
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        &self.world_position
-    }
-
-    fn move_to(&mut self, final_position: Vec3) {
-        self.world_position = final_position
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn move_by(&mut self, offset: Vec3) {
-        self.world_position = self.world_position.add(offset)
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
-
-    fn get_scale(&self) -> &Vec3 {
-       todo!()
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        todo!()
-    }
-}
\ No newline at end of file
+}
@@ -1,15 +1,12 @@
-use std::ops::{Add, Sub};
-
-use glam::Vec3;
-
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::shapes_common::{Area, is_point_within_convex_polygon};
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::render_util::RenderUtil;
-use crate::engine::api::texture::Sprite;
+use crate::engine::api::texture::{Sprite, TextureCoords};
 use crate::engine::glam_utils::to_glam_vec;
 use crate::engine::rendering::material::{Material, UniformKind};
 use crate::engine::rendering::shape_drawing_component::ShapeDrawingComponent;
-use crate::vertex::VertexShaderDataLayout;
+use crate::vertex::{TexturedVertexDataLayout, VertexShaderDataLayout};
 
 // todo: reduce duplication https://users.rust-lang.org/t/how-to-implement-inheritance-like-feature-for-rust/31159
 pub struct Quadrangle<T> where T: VertexShaderDataLayout {
@@ -17,13 +14,9 @@ pub struct Quadrangle<T> where T: VertexShaderDataLayout {
     vertices: [T; 4],
     indices: [i32; 6],
     material: Material,
-    world_position: glam::Vec3,
-    scale: glam::Vec3,
-    scaling_point: glam::Vec3
+    transform: Transform,
 }
 
-const REFERENCE_INDEX: usize = 2;
-
 impl<T: VertexShaderDataLayout> Quadrangle<T> {
     pub fn new(vertices: [T; 4],
                indices: [i32; 6],
@@ -37,15 +30,12 @@ impl<T: VertexShaderDataLayout> Quadrangle<T> {
             sprite,
         );
 
-        let scaling_point = vertices[REFERENCE_INDEX].get_pos();
         Quadrangle {
             drawing_component,
             vertices,
             indices,
             material,
-            world_position,
-            scale: glam::vec3(1.0, 1.0, 1.0),
-            scaling_point
+            transform: Transform::from_translation(world_position),
         }
     }
 
@@ -53,33 +43,63 @@ impl<T: VertexShaderDataLayout> Quadrangle<T> {
         self.material.set_variable(name, kind);
     }
 
-    pub fn set_scaling_point(&mut self, scaling_point: glam::Vec3) {
-        self.scaling_point = scaling_point
+    // point in coordinates of vertices around which quadrangle is scaled
+    pub fn set_pivot(&mut self, pivot: glam::Vec3) {
+        self.transform.set_pivot(pivot)
+    }
+}
+
+impl Quadrangle<TexturedVertexDataLayout> {
+    // only cpu side vertices change, batched quadrangles pick them up in next render
+    pub fn set_texture_coords(&mut self, coords: &TextureCoords) {
+        let corners = [coords.top_right, coords.bottom_right, coords.bottom_left, coords.top_left];
+        for (vertex, corner) in self.vertices.iter_mut().zip(corners.iter()) {
+            vertex.tx_coords = (*corner).into();
+        }
+    }
+}
+
+impl<T: VertexShaderDataLayout> Quadrangle<T> {
+    // textured quadrangles are drawn through sprite batch with vertices transformed on cpu
+    fn render_batched(&mut self, render_util: &RenderUtil) {
+        let sprite = self.drawing_component.get_sprite().as_ref().unwrap();
+        let model = render_util.get_world_matrix(self.transform.get_local_matrix());
+        let world_vertices: Vec<TexturedVertexDataLayout> = self.vertices.iter()
+            .map(|v| TexturedVertexDataLayout {
+                pos: model.transform_point3(v.get_pos()).into(),
+                clr: v.get_clr().into(),
+                tx_coords: v.get_tx_coords().unwrap().into(),
+            })
+            .collect();
+        // batch reads time of the material from its copy, same as unbatched draw does
+        self.material.mark_activated();
+        render_util.submit_to_sprite_batch(&world_vertices, &self.indices, &self.material, sprite);
     }
 }
 
 impl<T: VertexShaderDataLayout> Drawable for Quadrangle<T> {
     fn render(&mut self, render_util: &RenderUtil) {
+        if let (Some(_), Some(_)) = (self.drawing_component.get_sprite(), self.vertices[0].get_tx_coords()) {
+            self.render_batched(render_util);
+            return;
+        }
         self.drawing_component.render(
             self.indices.len() as i32,
             gl::TRIANGLES,
-            *self.get_pos(),
+            render_util.get_world_matrix(self.transform.get_local_matrix()),
             render_util,
             &mut self.material,
-            self.scale.clone(),
-            self.vertices[REFERENCE_INDEX].get_pos().sub(self.scaling_point),
         )
     }
 }
 
 impl<T: VertexShaderDataLayout> Area for Quadrangle<T> {
-    // todo does not work with scale
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        return is_point_within_convex_polygon(point,
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        return is_point_within_convex_polygon(local_point,
                                               &self.vertices.iter()
                                                   .map(|v| -> (f32, f32){
                                                       let p = v.get_pos_deprecated();
-                                                      (p.0 + self.world_position.x, p.1 + self.world_position.y)
+                                                      (p.0, p.1)
                                                   })
                                                   .collect(),
         );
@@ -93,23 +113,11 @@ impl<T: VertexShaderDataLayout> Area for Quadrangle<T> {
         return self.vertices.len();
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        &self.world_position
-    }
-
-    fn move_to(&mut self, final_position: Vec3) {
-        self.world_position = final_position
-    }
-
-    fn move_by(&mut self, offset: Vec3) {
-        self.world_position = self.world_position.add(offset)
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        &self.scale
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn set_scale(&mut self, new_scale: Vec3) {
-        self.scale = new_scale
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
 }
@@ -1,10 +1,10 @@
-use glam::Vec3;
 use sdl2::event::Event;
 
 use crate::engine::api::colour::{Colour, WHITE};
-use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
 use crate::engine::api::maths::quadrangle::Quadrangle;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex;
 use crate::engine::api::maths::vertex::{ColoredVertexDataLayout, TexturedVertexDataLayout, VertexShaderDataLayout};
 use crate::engine::api::render_util::RenderUtil;
@@ -50,7 +50,7 @@ impl Rectangle<TexturedVertexDataLayout> {
             bottom_left.clone(),
         );
 
-        quad.set_scaling_point(glam::vec3(size.x / 2.0, size.y / 2.0, 0.0));
+        quad.set_pivot(glam::vec3(size.x / 2.0, size.y / 2.0, 0.0));
 
         Rectangle {
             quad
@@ -71,12 +71,17 @@ impl Rectangle<TexturedVertexDataLayout> {
            bottom_left.clone(),
        );
 
-        quad.set_scaling_point(glam::vec3(size.x / 2.0, size.y / 2.0, 0.0));
+        quad.set_pivot(glam::vec3(size.x / 2.0, size.y / 2.0, 0.0));
 
         Rectangle {
             quad
         }
     }
+
+    // changes texture region shown by rectangle, e.g. to show other cell of spritesheet
+    pub fn set_texture_coords(&mut self, coords: &TextureCoords) {
+        self.quad.set_texture_coords(coords);
+    }
 }
 
 impl<T: VertexShaderDataLayout> Rectangle<T> {
@@ -112,14 +117,14 @@ impl<T: VertexShaderDataLayout> Drawable for Rectangle<T> {
         self.quad.update(update_context)
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
         self.quad.handle_event(event, context, update_context)
     }
 }
 
 impl<T: VertexShaderDataLayout> Area for Rectangle<T> {
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        self.quad.contains_point(point)
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        self.quad.contains_local_point(local_point)
     }
 
     fn area(&self) -> f32 {
@@ -130,23 +135,11 @@ impl<T: VertexShaderDataLayout> Area for Rectangle<T> {
         self.quad.num_vertices()
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        self.quad.get_pos()
+    fn get_transform(&self) -> &Transform {
+        self.quad.get_transform()
     }
 
-    fn move_to(&mut self, final_position: Vec3) {
-        self.quad.move_to(final_position)
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        self.quad.get_transform_mut()
     }
-
-    fn move_by(&mut self, offset: Vec3) {
-        self.quad.move_by(offset)
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        self.quad.get_scale()
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        self.quad.set_scale(new_scale)
-    }
-}
\ No newline at end of file
+}
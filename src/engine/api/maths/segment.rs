@@ -1,22 +1,22 @@
-use std::ops::Add;
-
-use glam::Vec3;
-
 use crate::engine::api::drawable::Drawable;
-use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::shapes_common::{Area, distance_to_segment};
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex::VertexShaderDataLayout;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::glam_utils::to_glam_vec;
 use crate::engine::rendering::material::Material;
 use crate::engine::rendering::shape_drawing_component::ShapeDrawingComponent;
 
+// how far from the segment, in its local space, points still hit it
+pub const DEFAULT_HIT_DISTANCE: f32 = 0.1;
+
 pub struct Segment<T> where T: VertexShaderDataLayout {
     drawing_component: ShapeDrawingComponent<T>,
     vertices: [T; 2],
     indices: [i32; 2],
     material: Material,
-    world_position: glam::Vec3,
-    scale: glam::Vec3,
+    transform: Transform,
+    hit_distance: f32,
 }
 
 /**
@@ -46,10 +46,14 @@ impl<'a, T: VertexShaderDataLayout> Segment<T> {
             vertices,
             indices,
             material,
-            world_position,
-            scale: glam::vec3(1.0, 1.0, 1.0),
+            transform: Transform::from_translation(world_position),
+            hit_distance: DEFAULT_HIT_DISTANCE,
         }
     }
+
+    pub fn set_hit_distance(&mut self, hit_distance: f32) {
+        self.hit_distance = hit_distance
+    }
 }
 
 impl<'a, T: VertexShaderDataLayout> Drawable for Segment<T> {
@@ -57,18 +61,19 @@ impl<'a, T: VertexShaderDataLayout> Drawable for Segment<T> {
         self.drawing_component.render(
             self.indices.len() as i32,
             gl::LINES,
-            *self.get_pos(),
+            render_util.get_world_matrix(self.transform.get_local_matrix()),
             render_util,
             &mut self.material,
-            self.scale.clone(),
-            glam::vec3(0.0, 0.0, 0.0),
         )
     }
 }
 
 impl<T: VertexShaderDataLayout> Area for Segment<T> {
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        todo!()
+    // segment has no area, points close enough to it count as inside
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        let start = self.vertices[0].get_pos();
+        let end = self.vertices[1].get_pos();
+        distance_to_segment(local_point, &(start.x, start.y), &(end.x, end.y)) <= self.hit_distance
     }
 
     fn area(&self) -> f32 {
@@ -79,24 +84,11 @@ impl<T: VertexShaderDataLayout> Area for Segment<T> {
         self.vertices.len()
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        &self.world_position
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn move_to(&mut self, final_position: Vec3) {
-        self.world_position = final_position
-    }
-
-    fn move_by(&mut self, offset: Vec3) {
-        self.world_position = self.world_position.add(offset)
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        &self.scale
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        self.scale = new_scale
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
 }
-
@@ -1,14 +1,79 @@
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::shapes_common::Side::{LEFT, NONE, RIGHT};
 
+/**
+Shape with a local transform. Hit testing moves the queried point into local space of the shape
+with inverse of its model matrix, so implementors only test against their untransformed vertices.
+```
+let mut rect = Rectangle::new_colored(&glam::vec3(0.0, 0.0, 0.0), &glam::vec2(2.0, 1.0), material, WHITE);
+rect.set_scale(glam::vec3(2.0, 2.0, 1.0));
+assert!(rect.contains_point(&(3.0, 1.0)));
+```
+ */
 pub trait Area {
-    fn contains_point(&self, point: &(f32, f32)) -> bool;
+    // point is in the same space as the shape position, usually world space
+    fn contains_point(&self, point: &(f32, f32)) -> bool {
+        let local_point = self.get_transform().to_local_point(&glam::vec3(point.0, point.1, 0.0));
+        self.contains_local_point(&(local_point.x, local_point.y))
+    }
+    // point is in coordinates of shape vertices
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool;
     fn area(&self) -> f32;
     fn num_vertices(&self) -> usize;
-    fn get_pos(&self) -> &glam::Vec3;
-    fn move_to(&mut self, final_position: glam::Vec3);
-    fn move_by(&mut self, offset: glam::Vec3);
-    fn get_scale(&self) -> &glam::Vec3;
-    fn set_scale(&mut self, new_scale: glam::Vec3);
+    fn get_transform(&self) -> &Transform;
+    fn get_transform_mut(&mut self) -> &mut Transform;
+
+    fn get_pos(&self) -> &glam::Vec3 {
+        self.get_transform().get_translation()
+    }
+
+    fn move_to(&mut self, final_position: glam::Vec3) {
+        self.get_transform_mut().set_translation(final_position)
+    }
+
+    fn move_by(&mut self, offset: glam::Vec3) {
+        self.get_transform_mut().translate_by(offset)
+    }
+
+    fn get_scale(&self) -> &glam::Vec3 {
+        self.get_transform().get_scale()
+    }
+
+    fn set_scale(&mut self, new_scale: glam::Vec3) {
+        self.get_transform_mut().set_scale(new_scale)
+    }
+
+    fn get_rotation(&self) -> &glam::Quat {
+        self.get_transform().get_rotation()
+    }
+
+    fn set_rotation(&mut self, new_rotation: glam::Quat) {
+        self.get_transform_mut().set_rotation(new_rotation)
+    }
+
+    // 2d rotation around z axis in radians, counterclockwise
+    fn get_rotation_angle(&self) -> f32 {
+        let rotation = self.get_rotation();
+        2.0 * rotation.z.atan2(rotation.w)
+    }
+
+    fn set_rotation_angle(&mut self, angle: f32) {
+        self.set_rotation(glam::Quat::from_rotation_z(angle))
+    }
+}
+
+pub fn distance_to_segment(point: &(f32, f32), segment_start: &(f32, f32), segment_end: &(f32, f32)) -> f32 {
+    let point = glam::vec2(point.0, point.1);
+    let start = glam::vec2(segment_start.0, segment_start.1);
+    let end = glam::vec2(segment_end.0, segment_end.1);
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+    // projection of point onto segment line, clamped to segment ends
+    let t = ((point - start).dot(segment) / length_squared).max(0.0).min(1.0);
+    point.distance(start + segment * t)
 }
 
 pub fn is_point_within_convex_polygon(point: &(f32, f32), vertices: &Vec<(f32, f32)>) -> bool {
@@ -71,3 +136,176 @@ impl Side {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+    use std::rc::Rc;
+    use std::sync::Once;
+
+    use crate::engine::api::colour::WHITE;
+    use crate::engine::api::maths::circle::Circle;
+    use crate::engine::api::maths::quadrangle::Quadrangle;
+    use crate::engine::api::maths::rectangle::Rectangle;
+    use crate::engine::api::maths::segment::{DEFAULT_HIT_DISTANCE, Segment};
+    use crate::engine::api::maths::transform::Transform;
+    use crate::engine::api::maths::triangle::Triangle;
+    use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
+    use crate::engine::rendering::material::Material;
+    use crate::engine::rendering::ShaderProgram;
+
+    use super::{Area, distance_to_segment};
+
+    static LOAD_GL: Once = Once::new();
+
+    extern "system" fn noop_gl_function() {}
+
+    // there is no gl context in unit tests, shapes create their buffers with gl functions that do nothing
+    fn load_noop_gl() {
+        LOAD_GL.call_once(|| gl::load_with(|_| noop_gl_function as *const std::os::raw::c_void));
+    }
+
+    fn material() -> Material {
+        load_noop_gl();
+        Material::new(Rc::new(ShaderProgram::from_shaders(&[], "test").unwrap()))
+    }
+
+    fn vertex(x: f32, y: f32) -> ColoredVertexDataLayout {
+        ColoredVertexDataLayout { pos: (x, y, 0.0).into(), clr: WHITE.into() }
+    }
+
+    // 2 by 1 rectangle with bottom left corner at its position
+    fn rectangle(bottom_left: glam::Vec3) -> Rectangle<ColoredVertexDataLayout> {
+        Rectangle::new_colored(&bottom_left, &glam::vec2(2.0, 1.0), material(), WHITE)
+    }
+
+    // from (-2, 0) to (2, 0) in local space
+    fn segment(position: glam::Vec3) -> Segment<ColoredVertexDataLayout> {
+        Segment::new([vertex(-2.0, 0.0), vertex(2.0, 0.0)], [0, 1], material(), position)
+    }
+
+    #[test]
+    fn translated_rectangle() {
+        let rect = rectangle(glam::vec3(3.0, 2.0, 0.0));
+        assert!(rect.contains_point(&(4.0, 2.5)));
+        // inside of rectangle before it was moved
+        assert!(!rect.contains_point(&(1.0, 0.5)));
+    }
+
+    #[test]
+    fn non_uniformly_scaled_rectangle() {
+        let mut rect = rectangle(glam::vec3(0.0, 0.0, 0.0));
+        rect.set_scale(glam::vec3(3.0, 0.5, 1.0));
+        assert!(rect.contains_point(&(5.0, 0.4)));
+        assert!(!rect.contains_point(&(1.0, 0.7)));
+    }
+
+    #[test]
+    fn rotated_rectangle() {
+        let mut rect = rectangle(glam::vec3(0.0, 0.0, 0.0));
+        rect.set_rotation_angle(FRAC_PI_2);
+        // quarter turn around bottom left corner moves rectangle to x in [-1, 0] and y in [0, 2]
+        assert!(rect.contains_point(&(-0.5, 1.5)));
+        assert!(!rect.contains_point(&(1.5, 0.5)));
+    }
+
+    #[test]
+    fn rectangle_rotated_around_offset_pivot() {
+        let mut rect = rectangle(glam::vec3(0.0, 0.0, 0.0));
+        rect.get_transform_mut().set_pivot(glam::vec3(1.0, 0.5, 0.0));
+        rect.set_rotation_angle(FRAC_PI_2);
+        // quarter turn around centre moves rectangle to x in [0.5, 1.5] and y in [-0.5, 1.5]
+        assert!(rect.contains_point(&(1.0, 1.3)));
+        assert!(!rect.contains_point(&(1.8, 0.5)));
+    }
+
+    #[test]
+    fn rectangle_in_offset_parent() {
+        let mut parent = Transform::from_translation(glam::vec3(100.0, 50.0, 0.0));
+        parent.set_scale(glam::vec3(2.0, 2.0, 1.0));
+        let rect = rectangle(glam::vec3(1.0, 1.0, 0.0));
+        // world point is moved into parent space first, as scene nodes do
+        let contains = |world_point: (f32, f32)| {
+            let parent_point = parent.to_local_point(&glam::vec3(world_point.0, world_point.1, 0.0));
+            rect.contains_point(&(parent_point.x, parent_point.y))
+        };
+        // in parent space rectangle spans x in [1, 3] and y in [1, 2]
+        assert!(contains((104.0, 53.0)));
+        // rectangle position without parent offset
+        assert!(!contains((2.0, 1.5)));
+        assert!(!contains((101.0, 51.0)));
+    }
+
+    #[test]
+    fn scaled_circle_becomes_ellipse() {
+        let mut circle = Circle::new_colored(&glam::vec3(5.0, 5.0, 0.0), WHITE, 1.0, material());
+        circle.set_scale(glam::vec3(2.0, 1.0, 1.0));
+        assert!(circle.contains_point(&(6.5, 5.0)));
+        assert!(!circle.contains_point(&(5.0, 6.5)));
+        assert!(!circle.contains_point(&(7.5, 5.0)));
+    }
+
+    #[test]
+    fn rotated_triangle_away_from_origin() {
+        let mut triangle = Triangle::new(
+            [vertex(0.0, 0.0), vertex(2.0, 0.0), vertex(0.0, 2.0)],
+            [0, 1, 2],
+            material(),
+            None,
+            glam::vec3(10.0, 0.0, 0.0),
+        );
+        triangle.set_rotation_angle(PI);
+        // half turn around its position flips triangle into x in [8, 10] and y in [-2, 0]
+        assert!(triangle.contains_point(&(9.5, -0.5)));
+        assert!(!triangle.contains_point(&(10.5, 0.5)));
+    }
+
+    #[test]
+    fn scaled_quadrangle() {
+        let mut quad = Quadrangle::new(
+            [vertex(1.0, 1.0), vertex(1.0, -1.0), vertex(-1.0, -1.0), vertex(-1.0, 1.0)],
+            [0, 1, 3, 1, 2, 3],
+            material(),
+            None,
+            glam::vec3(-4.0, 0.0, 0.0),
+        );
+        quad.set_scale(glam::vec3(0.5, 4.0, 1.0));
+        assert!(quad.contains_point(&(-4.3, 3.5)));
+        assert!(!quad.contains_point(&(-3.3, 0.0)));
+    }
+
+    #[test]
+    fn segment_is_hit_within_hit_distance() {
+        let segment = segment(glam::vec3(0.0, 5.0, 0.0));
+        assert!(segment.contains_point(&(1.0, 5.0 + DEFAULT_HIT_DISTANCE * 0.9)));
+        assert!(!segment.contains_point(&(1.0, 5.0 - DEFAULT_HIT_DISTANCE * 1.1)));
+        // beyond segment ends distance is measured to the nearest end
+        assert!(segment.contains_point(&(2.0 + DEFAULT_HIT_DISTANCE * 0.9, 5.0)));
+        assert!(!segment.contains_point(&(-2.0 - DEFAULT_HIT_DISTANCE * 1.1, 5.0)));
+    }
+
+    #[test]
+    fn rotated_segment_with_custom_hit_distance() {
+        let mut segment = segment(glam::vec3(0.0, 5.0, 0.0));
+        segment.set_hit_distance(0.5);
+        segment.set_rotation_angle(FRAC_PI_2);
+        // quarter turn makes segment vertical, from (0, 3) to (0, 7)
+        assert!(segment.contains_point(&(0.45, 6.0)));
+        assert!(!segment.contains_point(&(0.55, 6.0)));
+        assert!(!segment.contains_point(&(1.0, 5.0)));
+    }
+
+    #[test]
+    fn hit_distance_is_scaled_with_segment() {
+        let mut segment = segment(glam::vec3(0.0, 0.0, 0.0));
+        segment.set_scale(glam::vec3(1.0, 2.0, 1.0));
+        // hit distance is in local space, so it doubles along y
+        assert!(segment.contains_point(&(0.0, DEFAULT_HIT_DISTANCE * 1.8)));
+        assert!(!segment.contains_point(&(0.0, DEFAULT_HIT_DISTANCE * 2.2)));
+    }
+
+    #[test]
+    fn degenerate_segment_is_a_point() {
+        assert_eq!(distance_to_segment(&(3.0, 4.0), &(0.0, 0.0), &(0.0, 0.0)), 5.0);
+    }
+}
@@ -0,0 +1,137 @@
+use glam::{Mat4, Quat, Vec3};
+
+/**
+Local transform of a shape or scene node, relative to its parent.
+Rotation and scale are applied around pivot, which is expressed in local coordinates,
+then everything is moved by translation:
+```
+let mut transform = Transform::from_translation(glam::vec3(2.0, 1.0, 0.0));
+transform.set_pivot(glam::vec3(0.5, 0.5, 0.0));
+transform.set_scale(glam::vec3(2.0, 2.0, 1.0));
+let world_matrix = parent_world_matrix * transform.get_local_matrix();
+```
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transform {
+    translation: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+    pivot: Vec3,
+}
+
+impl Transform {
+    pub fn new() -> Transform {
+        Transform::from_translation(glam::vec3(0.0, 0.0, 0.0))
+    }
+
+    pub fn from_translation(translation: Vec3) -> Transform {
+        Transform {
+            translation,
+            rotation: Quat::IDENTITY,
+            scale: glam::vec3(1.0, 1.0, 1.0),
+            pivot: glam::vec3(0.0, 0.0, 0.0),
+        }
+    }
+
+    pub fn get_local_matrix(&self) -> Mat4 {
+        Mat4::from_translation(self.translation + self.pivot)
+            * Mat4::from_quat(self.rotation)
+            * Mat4::from_scale(self.scale)
+            * Mat4::from_translation(-self.pivot)
+    }
+
+    // maps point given in parent space into local space of this transform
+    pub fn to_local_point(&self, parent_point: &Vec3) -> Vec3 {
+        self.get_local_matrix().inverse().transform_point3(*parent_point)
+    }
+
+    pub fn get_translation(&self) -> &Vec3 {
+        &self.translation
+    }
+
+    pub fn set_translation(&mut self, translation: Vec3) {
+        self.translation = translation
+    }
+
+    pub fn translate_by(&mut self, offset: Vec3) {
+        self.translation += offset
+    }
+
+    pub fn get_rotation(&self) -> &Quat {
+        &self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: Quat) {
+        self.rotation = rotation
+    }
+
+    pub fn get_scale(&self) -> &Vec3 {
+        &self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: Vec3) {
+        self.scale = scale
+    }
+
+    pub fn get_pivot(&self) -> &Vec3 {
+        &self.pivot
+    }
+
+    pub fn set_pivot(&mut self, pivot: Vec3) {
+        self.pivot = pivot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::Transform;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn assert_close(actual: glam::Vec3, expected: glam::Vec3) {
+        assert!(actual.abs_diff_eq(expected, EPSILON), "{:?} is not {:?}", actual, expected);
+    }
+
+    #[test]
+    fn translation_is_undone() {
+        let transform = Transform::from_translation(glam::vec3(2.0, -1.0, 0.0));
+        assert_close(transform.to_local_point(&glam::vec3(3.0, 1.0, 0.0)), glam::vec3(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn non_uniform_scale_is_undone() {
+        let mut transform = Transform::new();
+        transform.set_scale(glam::vec3(4.0, 0.5, 1.0));
+        assert_close(transform.to_local_point(&glam::vec3(2.0, 2.0, 0.0)), glam::vec3(0.5, 4.0, 0.0));
+    }
+
+    #[test]
+    fn rotation_is_undone() {
+        let mut transform = Transform::new();
+        transform.set_rotation(glam::Quat::from_rotation_z(FRAC_PI_2));
+        assert_close(transform.to_local_point(&glam::vec3(0.0, 1.0, 0.0)), glam::vec3(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn pivot_stays_in_place() {
+        let mut transform = Transform::from_translation(glam::vec3(5.0, 0.0, 0.0));
+        transform.set_pivot(glam::vec3(1.0, 1.0, 0.0));
+        transform.set_scale(glam::vec3(3.0, 2.0, 1.0));
+        transform.set_rotation(glam::Quat::from_rotation_z(FRAC_PI_2));
+        // pivot is only moved by translation
+        assert_close(transform.to_local_point(&glam::vec3(6.0, 1.0, 0.0)), glam::vec3(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn local_point_maps_back_to_parent_point() {
+        let mut transform = Transform::from_translation(glam::vec3(-2.0, 3.0, 0.0));
+        transform.set_pivot(glam::vec3(0.5, 0.25, 0.0));
+        transform.set_scale(glam::vec3(2.0, 0.5, 1.0));
+        transform.set_rotation(glam::Quat::from_rotation_z(0.7));
+        let parent_point = glam::vec3(1.5, -4.0, 0.0);
+        let local_point = transform.to_local_point(&parent_point);
+        assert_close(transform.get_local_matrix().transform_point3(local_point), parent_point);
+    }
+}
@@ -1,9 +1,6 @@
-use std::ops::Add;
-
-use glam::Vec3;
-
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::shapes_common::{Area, is_point_within_convex_polygon};
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::api::texture::Sprite;
 use crate::engine::glam_utils::to_glam_vec;
@@ -16,8 +13,7 @@ pub struct Triangle<T: VertexShaderDataLayout> {
     vertices: [T; 3],
     indices: [i32; 3],
     material: Material,
-    world_position: glam::Vec3,
-    scale: glam::Vec3,
+    transform: Transform,
 }
 
 // todo: pass reference of texture here
@@ -34,8 +30,7 @@ impl<'a, T: VertexShaderDataLayout> Triangle<T> {
             vertices,
             indices,
             material,
-            world_position,
-            scale: glam::vec3(1.0, 1.0, 1.0),
+            transform: Transform::from_translation(world_position),
         }
     }
 }
@@ -45,18 +40,16 @@ impl<'a, T: VertexShaderDataLayout> Drawable for Triangle<T> {
         self.open_gl_context.render(
             self.indices.len() as i32,
             gl::TRIANGLES,
-            *self.get_pos(),
+            render_util.get_world_matrix(self.transform.get_local_matrix()),
             render_util,
             &mut self.material,
-            self.scale.clone(),
-            glam::vec3(0.0, 0.0, 0.0),
         )
     }
 }
 
 impl<'a, T: VertexShaderDataLayout> Area for Triangle<T> {
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        return is_point_within_convex_polygon(point,
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        return is_point_within_convex_polygon(local_point,
                                               &self.vertices.iter()
                                                   .map(|v| -> (f32, f32) {
                                                       let p = v.get_pos_deprecated();
@@ -73,24 +66,11 @@ impl<'a, T: VertexShaderDataLayout> Area for Triangle<T> {
         return self.vertices.len();
     }
 
-    fn get_pos(&self) -> &glam::Vec3 {
-        &self.world_position
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn move_to(&mut self, final_position: Vec3) {
-        self.world_position = final_position
-    }
-
-    fn move_by(&mut self, offset: Vec3) {
-        self.world_position = self.world_position.add(offset)
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        &self.scale
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        self.scale = new_scale
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
 }
-
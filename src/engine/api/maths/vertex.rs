@@ -47,6 +47,14 @@ impl VertexShaderDataLayout for ColoredVertexDataLayout {
     fn get_pos(&self) -> Vec3 {
         glam::vec3(self.pos.d0, self.pos.d1, self.pos.d2)
     }
+
+    fn get_clr(&self) -> glam::Vec4 {
+        glam::vec4(self.clr.d0, self.clr.d1, self.clr.d2, self.clr.d3)
+    }
+
+    fn get_tx_coords(&self) -> Option<(f32, f32)> {
+        None
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -98,6 +106,14 @@ impl VertexShaderDataLayout for TexturedVertexDataLayout {
     fn get_pos(&self) -> Vec3 {
         glam::vec3(self.pos.d0, self.pos.d1, self.pos.d2)
     }
+
+    fn get_clr(&self) -> glam::Vec4 {
+        glam::vec4(self.clr.d0, self.clr.d1, self.clr.d2, self.clr.d3)
+    }
+
+    fn get_tx_coords(&self) -> Option<(f32, f32)> {
+        Some((self.tx_coords.d0, self.tx_coords.d1))
+    }
 }
 
 pub trait VertexShaderDataLayout {
@@ -109,5 +125,8 @@ pub trait VertexShaderDataLayout {
 
     fn get_pos_deprecated(&self) -> (f32, f32, f32);
     fn get_pos(&self) -> glam::Vec3;
+    fn get_clr(&self) -> glam::Vec4;
+    // none for layouts without texture
+    fn get_tx_coords(&self) -> Option<(f32, f32)>;
 
 }
\ No newline at end of file
@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use crate::engine::api::animation::tween::Tweenable;
+use crate::engine::api::colour::{Colour, WHITE};
+use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::random::RandomGenerator;
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::resource_manager::ResourceManager;
+use crate::engine::api::texture::Sprite;
+use crate::engine::rendering::material::Material;
+
+pub const PARTICLE_TEXTURE: &str = "engine/textures/particle.png";
+pub const PARTICLE_SHADER: &str = "engine/shaders/particles/particle";
+
+const QUAD_INDICES: [i32; 6] = [0, 1, 3, 1, 2, 3];
+const DEFAULT_MAX_PARTICLES: usize = 1000;
+
+/**
+Value that changes over lifetime of a particle, keys are given for normalized age from 0 to 1
+and values between them are interpolated linearly.
+```
+let fade = Curve::new(WHITE).with_key(0.7, WHITE).with_key(1.0, TRANSPARENT);
+```
+ */
+#[derive(Clone, Debug)]
+pub struct Curve<V: Tweenable> {
+    keys: Vec<(f32, V)>,
+}
+
+impl<V: Tweenable> Curve<V> {
+    pub fn new(start: V) -> Curve<V> {
+        Curve { keys: vec![(0.0, start)] }
+    }
+
+    pub fn linear(start: V, end: V) -> Curve<V> {
+        Curve::new(start).with_key(1.0, end)
+    }
+
+    // keys must be added in order of age
+    pub fn with_key(mut self, age: f32, value: V) -> Curve<V> {
+        let last_age = self.keys.last().unwrap().0;
+        if age <= last_age || age > 1.0 {
+            panic!("Curve key at age {} must come after previous key at {} and be at most 1", age, last_age);
+        }
+        self.keys.push((age, value));
+        self
+    }
+
+    pub fn sample(&self, age: f32) -> V {
+        match self.keys.iter().position(|(key_age, _)| *key_age > age) {
+            None => self.keys.last().unwrap().1.clone(),
+            Some(0) => self.keys[0].1.clone(),
+            Some(idx) => {
+                let (from_age, from) = &self.keys[idx - 1];
+                let (to_age, to) = &self.keys[idx];
+                from.interpolate(to, (age - from_age) / (to_age - from_age))
+            }
+        }
+    }
+}
+
+/**
+Describes how emitter spawns and moves particles. Directions are angles in radians measured from x axis.
+```
+let sparks = ParticleEmitterConfig::new(sprite, material)
+    .with_lifetime(Duration::from_millis(200), Duration::from_millis(500))
+    .with_velocity_cone(PI / 2.0, PI / 4.0, 2.0, 4.0)
+    .with_gravity(glam::vec2(0.0, -9.81))
+    .with_size_over_lifetime(Curve::linear(0.2, 0.0));
+```
+ */
+#[derive(Clone)]
+pub struct ParticleEmitterConfig {
+    sprite: Sprite,
+    material: Material,
+    // particles per second spawned while emitter is emitting
+    spawn_rate: f32,
+    min_lifetime: Duration,
+    max_lifetime: Duration,
+    direction: f32,
+    // particles fly within direction +- spread
+    spread: f32,
+    min_speed: f32,
+    max_speed: f32,
+    gravity: glam::Vec2,
+    // particles spawn at random point of rectangle centered at emitter
+    spawn_half_extents: glam::Vec2,
+    size_over_lifetime: Curve<f32>,
+    // multiplied by start colour of particle
+    colour_over_lifetime: Curve<Colour>,
+    // each particle picks one of them at random
+    start_colours: Vec<Colour>,
+    max_particles: usize,
+}
+
+impl ParticleEmitterConfig {
+    pub fn new(sprite: Sprite, material: Material) -> ParticleEmitterConfig {
+        ParticleEmitterConfig {
+            sprite,
+            material,
+            spawn_rate: 10.0,
+            min_lifetime: Duration::from_secs(1),
+            max_lifetime: Duration::from_secs(1),
+            direction: std::f32::consts::FRAC_PI_2,
+            spread: std::f32::consts::PI,
+            min_speed: 1.0,
+            max_speed: 1.0,
+            gravity: glam::vec2(0.0, 0.0),
+            spawn_half_extents: glam::vec2(0.0, 0.0),
+            size_over_lifetime: Curve::new(0.1),
+            colour_over_lifetime: Curve::new(WHITE),
+            start_colours: vec![WHITE],
+            max_particles: DEFAULT_MAX_PARTICLES,
+        }
+    }
+
+    // white round particle texture shipped with engine
+    pub fn new_default(resource_manager: &dyn ResourceManager) -> ParticleEmitterConfig {
+        ParticleEmitterConfig::new(
+            resource_manager.fetch_sprite(PARTICLE_TEXTURE),
+            resource_manager.fetch_shader_material(PARTICLE_SHADER),
+        )
+    }
+
+    pub fn with_spawn_rate(mut self, particles_per_second: f32) -> ParticleEmitterConfig {
+        self.spawn_rate = particles_per_second;
+        self
+    }
+
+    pub fn with_lifetime(mut self, min_lifetime: Duration, max_lifetime: Duration) -> ParticleEmitterConfig {
+        self.min_lifetime = min_lifetime;
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    pub fn with_velocity_cone(mut self, direction: f32, spread: f32, min_speed: f32, max_speed: f32) -> ParticleEmitterConfig {
+        self.direction = direction;
+        self.spread = spread;
+        self.min_speed = min_speed;
+        self.max_speed = max_speed;
+        self
+    }
+
+    pub fn with_gravity(mut self, gravity: glam::Vec2) -> ParticleEmitterConfig {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_spawn_area(mut self, half_extents: glam::Vec2) -> ParticleEmitterConfig {
+        self.spawn_half_extents = half_extents;
+        self
+    }
+
+    pub fn with_size_over_lifetime(mut self, size: Curve<f32>) -> ParticleEmitterConfig {
+        self.size_over_lifetime = size;
+        self
+    }
+
+    pub fn with_colour_over_lifetime(mut self, colour: Curve<Colour>) -> ParticleEmitterConfig {
+        self.colour_over_lifetime = colour;
+        self
+    }
+
+    pub fn with_start_colours(mut self, colours: Vec<Colour>) -> ParticleEmitterConfig {
+        if colours.is_empty() {
+            panic!("Particle emitter needs at least one start colour");
+        }
+        self.start_colours = colours;
+        self
+    }
+
+    pub fn with_max_particles(mut self, max_particles: usize) -> ParticleEmitterConfig {
+        self.max_particles = max_particles;
+        self
+    }
+}
+
+struct Particle {
+    position: glam::Vec3,
+    velocity: glam::Vec2,
+    age: Duration,
+    lifetime: Duration,
+    start_colour: Colour,
+}
+
+impl Particle {
+    fn get_normalized_age(&self) -> f32 {
+        (self.age.as_secs_f32() / self.lifetime.as_secs_f32()).min(1.0)
+    }
+}
+
+/**
+Spawns particles continuously and in bursts and draws all of them with a single batched draw call.
+Particles live in the space emitter is rendered in, moving emitter does not drag already spawned particles.
+Random values come from `EngineUtilities::get_random` so that replays reproduce particles.
+```
+let mut sparks = ParticleEmitter::new(config, glam::vec3(0.0, 0.0, 0.0));
+sparks.set_position(ring_position);
+sparks.burst(30);
+```
+ */
+pub struct ParticleEmitter {
+    config: ParticleEmitterConfig,
+    position: glam::Vec3,
+    particles: Vec<Particle>,
+    emitting: bool,
+    // fraction of particle carried over between updates so that low spawn rates work with short frames
+    spawn_accumulator: f32,
+    pending_bursts: Vec<(glam::Vec3, u32)>,
+}
+
+impl ParticleEmitter {
+    pub fn new(config: ParticleEmitterConfig, position: glam::Vec3) -> ParticleEmitter {
+        ParticleEmitter {
+            config,
+            position,
+            particles: vec!(),
+            emitting: false,
+            spawn_accumulator: 0.0,
+            pending_bursts: vec!(),
+        }
+    }
+
+    pub fn get_position(&self) -> &glam::Vec3 {
+        &self.position
+    }
+
+    pub fn set_position(&mut self, position: glam::Vec3) {
+        self.position = position;
+    }
+
+    pub fn start_emitting(&mut self) {
+        self.emitting = true;
+    }
+
+    // particles that are alive keep flying
+    pub fn stop_emitting(&mut self) {
+        self.emitting = false;
+        self.spawn_accumulator = 0.0;
+    }
+
+    pub fn is_emitting(&self) -> bool {
+        self.emitting
+    }
+
+    // particles are spawned at current emitter position during next update
+    pub fn burst(&mut self, count: u32) {
+        self.pending_bursts.push((self.position, count));
+    }
+
+    pub fn get_num_particles(&self) -> usize {
+        self.particles.len()
+    }
+
+    // nothing to draw and nothing will be spawned
+    pub fn is_idle(&self) -> bool {
+        !self.emitting && self.particles.is_empty() && self.pending_bursts.is_empty()
+    }
+
+    pub fn advance(&mut self, time_passed: Duration, random: &RandomGenerator) {
+        let delta_seconds = time_passed.as_secs_f32();
+        let gravity = self.config.gravity;
+        for particle in self.particles.iter_mut() {
+            particle.age += time_passed;
+            particle.velocity += gravity * delta_seconds;
+            particle.position += particle.velocity.extend(0.0) * delta_seconds;
+        }
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+
+        for (position, count) in std::mem::take(&mut self.pending_bursts) {
+            (0..count).for_each(|_| self.spawn(&position, random));
+        }
+        if self.emitting {
+            self.spawn_accumulator += self.config.spawn_rate * delta_seconds;
+            let position = self.position;
+            while self.spawn_accumulator >= 1.0 {
+                self.spawn_accumulator -= 1.0;
+                self.spawn(&position, random);
+            }
+        }
+    }
+
+    fn spawn(&mut self, position: &glam::Vec3, random: &RandomGenerator) {
+        if self.particles.len() >= self.config.max_particles {
+            return;
+        }
+        let config = &self.config;
+        let angle = config.direction + random_between(random, -config.spread, config.spread);
+        let speed = random_between(random, config.min_speed, config.max_speed);
+        let offset = glam::vec3(
+            random_between(random, -config.spawn_half_extents.x, config.spawn_half_extents.x),
+            random_between(random, -config.spawn_half_extents.y, config.spawn_half_extents.y),
+            0.0,
+        );
+        let lifetime = random_between(random, config.min_lifetime.as_secs_f32(), config.max_lifetime.as_secs_f32());
+        let start_colour = config.start_colours[random.gen_range(0..config.start_colours.len())];
+        self.particles.push(Particle {
+            position: *position + offset,
+            velocity: glam::vec2(angle.cos(), angle.sin()) * speed,
+            age: Duration::ZERO,
+            lifetime: Duration::from_secs_f32(lifetime.max(f32::EPSILON)),
+            start_colour,
+        });
+    }
+}
+
+// gen_range panics on empty ranges
+fn random_between(random: &RandomGenerator, min: f32, max: f32) -> f32 {
+    match min < max {
+        true => random.gen_range(min..max),
+        false => min
+    }
+}
+
+impl Drawable for ParticleEmitter {
+    fn render(&mut self, render_util: &RenderUtil) {
+        if self.particles.is_empty() {
+            return;
+        }
+        let world_matrix = render_util.get_world_matrix(glam::Mat4::IDENTITY);
+        let coords = self.config.sprite.get_texture_coords();
+        let corners = [
+            (glam::vec2(0.5, 0.5), coords.top_right),
+            (glam::vec2(0.5, -0.5), coords.bottom_right),
+            (glam::vec2(-0.5, -0.5), coords.bottom_left),
+            (glam::vec2(-0.5, 0.5), coords.top_left),
+        ];
+        let mut vertices: Vec<TexturedVertexDataLayout> = Vec::with_capacity(self.particles.len() * 4);
+        let mut indices: Vec<i32> = Vec::with_capacity(self.particles.len() * 6);
+        for particle in self.particles.iter() {
+            let age = particle.get_normalized_age();
+            let size = self.config.size_over_lifetime.sample(age);
+            let start_colour: glam::Vec4 = particle.start_colour.into();
+            let colour: glam::Vec4 = self.config.colour_over_lifetime.sample(age).into();
+            let center = world_matrix.transform_point3(particle.position);
+            let base_index = vertices.len() as i32;
+            for (corner, tx_coords) in corners.iter() {
+                vertices.push(TexturedVertexDataLayout {
+                    pos: (center + (*corner * size).extend(0.0)).into(),
+                    clr: (start_colour * colour).into(),
+                    tx_coords: (*tx_coords).into(),
+                });
+            }
+            indices.extend(QUAD_INDICES.iter().map(|idx| idx + base_index));
+        }
+        render_util.submit_to_sprite_batch(&vertices, &indices, &self.config.material, &self.config.sprite);
+    }
+
+    fn update(&mut self, update_context: &UpdateContext) {
+        self.advance(*update_context.get_delta_time(), &update_context.get_engine_utilities().get_random());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod rigid_body;
+pub mod physics_world;
@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use glam::Vec2;
+
+use crate::engine::api::collision::broad_phase::SpatialGrid;
+use crate::engine::api::collision::narrow_phase::{Contact, test_collision};
+use crate::engine::api::collision::shapes::CollisionShape;
+use crate::engine::api::physics::rigid_body::RigidBody;
+
+// long frames are split into steps of at most this length so that fast bodies do not pass through tiles
+const MAX_SUBSTEP_SECONDS: f32 = 1.0 / 60.0;
+const DEFAULT_CELL_SIZE: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BodyHandle(usize);
+
+// collision found in last step, normal points from body towards other body or static geometry
+#[derive(Clone, Copy, Debug)]
+pub struct BodyContact {
+    pub body: BodyHandle,
+    // none when body hit static geometry
+    pub other: Option<BodyHandle>,
+    pub contact: Contact,
+}
+
+/**
+Moves rigid bodies and resolves their collisions with each other and with static geometry.
+Owner calls `step` from its `update`, then copies body positions to whatever it draws.
+```
+let mut physics_world = PhysicsWorld::new(glam::vec2(0.0, -9.81));
+physics_world.add_static_tiles(glam::vec2(-10.0, -5.0), glam::vec2(1.0, 1.0), &[(0, 0), (1, 0), (2, 0)]);
+let ball = physics_world.add_body(RigidBody::new(glam::vec2(-8.0, 0.0), ColliderShape::CIRCLE { radius: 0.5 }, 1.0));
+...
+physics_world.step(update_context.get_delta_time());
+let ball_position = physics_world.get_body(ball).get_position();
+```
+ */
+pub struct PhysicsWorld {
+    gravity: Vec2,
+    bodies: Vec<RigidBody>,
+    static_shapes: Vec<CollisionShape>,
+    // static geometry does not move so its grid is built once
+    static_grid: SpatialGrid,
+    body_grid: SpatialGrid,
+    last_contacts: Vec<BodyContact>,
+}
+
+impl PhysicsWorld {
+    pub fn new(gravity: Vec2) -> PhysicsWorld {
+        PhysicsWorld {
+            gravity,
+            bodies: vec!(),
+            static_shapes: vec!(),
+            static_grid: SpatialGrid::new(DEFAULT_CELL_SIZE),
+            body_grid: SpatialGrid::new(DEFAULT_CELL_SIZE),
+            last_contacts: vec!(),
+        }
+    }
+
+    pub fn get_gravity(&self) -> &Vec2 {
+        &self.gravity
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vec2) {
+        self.gravity = gravity;
+    }
+
+    pub fn add_body(&mut self, body: RigidBody) -> BodyHandle {
+        self.bodies.push(body);
+        BodyHandle(self.bodies.len() - 1)
+    }
+
+    pub fn get_body(&self, handle: BodyHandle) -> &RigidBody {
+        &self.bodies[handle.0]
+    }
+
+    pub fn get_body_mut(&mut self, handle: BodyHandle) -> &mut RigidBody {
+        &mut self.bodies[handle.0]
+    }
+
+    pub fn add_static_shape(&mut self, shape: CollisionShape) {
+        self.static_grid.insert(self.static_shapes.len(), shape.get_bounds());
+        self.static_shapes.push(shape);
+    }
+
+    // solid tiles are given as (column, row) counted from bottom left corner of tile grid at origin
+    pub fn add_static_tiles(&mut self, origin: Vec2, tile_size: Vec2, solid_tiles: &[(i32, i32)]) {
+        for (column, row) in solid_tiles {
+            let min = origin + glam::vec2(*column as f32, *row as f32) * tile_size;
+            self.add_static_shape(CollisionShape::new_aabb(min, min + tile_size));
+        }
+    }
+
+    pub fn get_static_shapes(&self) -> &Vec<CollisionShape> {
+        &self.static_shapes
+    }
+
+    pub fn get_last_contacts(&self) -> &Vec<BodyContact> {
+        &self.last_contacts
+    }
+
+    pub fn step(&mut self, delta_time: &Duration) {
+        self.last_contacts.clear();
+        let delta_seconds = delta_time.as_secs_f32();
+        if delta_seconds <= 0.0 {
+            return;
+        }
+        let num_substeps = (delta_seconds / MAX_SUBSTEP_SECONDS).ceil() as u32;
+        let substep_seconds = delta_seconds / num_substeps as f32;
+        for _ in 0..num_substeps {
+            let gravity = self.gravity;
+            self.bodies.iter_mut().for_each(|body| body.integrate(&gravity, substep_seconds));
+            self.resolve_body_collisions();
+            self.resolve_static_collisions();
+        }
+        self.bodies.iter_mut().for_each(|body| body.clear_forces());
+    }
+
+    fn resolve_body_collisions(&mut self) {
+        self.body_grid.clear();
+        for (idx, body) in self.bodies.iter().enumerate() {
+            self.body_grid.insert(idx, body.get_collision_shape().get_bounds());
+        }
+        for (first, second) in self.body_grid.find_candidate_pairs() {
+            let contact = match test_collision(&self.bodies[first].get_collision_shape(), &self.bodies[second].get_collision_shape()) {
+                None => continue,
+                Some(contact) => contact
+            };
+            let restitution = self.bodies[first].get_restitution().min(self.bodies[second].get_restitution());
+            let friction = (self.bodies[first].get_friction() * self.bodies[second].get_friction()).sqrt();
+            let (first_body, second_body) = get_pair_mut(&mut self.bodies, first, second);
+            resolve_contact(first_body, Some(second_body), &contact, restitution, friction);
+            self.last_contacts.push(BodyContact { body: BodyHandle(first), other: Some(BodyHandle(second)), contact });
+        }
+    }
+
+    fn resolve_static_collisions(&mut self) {
+        for (idx, body) in self.bodies.iter_mut().enumerate() {
+            for static_idx in self.static_grid.query(&body.get_collision_shape().get_bounds()) {
+                // shape is taken again since previous tile could have pushed body away
+                let contact = match test_collision(&body.get_collision_shape(), &self.static_shapes[static_idx]) {
+                    None => continue,
+                    Some(contact) => contact
+                };
+                let restitution = body.get_restitution();
+                let friction = body.get_friction();
+                resolve_contact(body, None, &contact, restitution, friction);
+                self.last_contacts.push(BodyContact { body: BodyHandle(idx), other: None, contact });
+            }
+        }
+    }
+}
+
+// second body is none for static geometry, which has infinite mass and does not move
+fn resolve_contact(first: &mut RigidBody, mut second: Option<&mut RigidBody>, contact: &Contact, restitution: f32, friction: f32) {
+    let normal = *contact.get_normal();
+    let first_inverse_mass = first.get_inverse_mass();
+    let second_inverse_mass = second.as_ref().map_or(0.0, |body| body.get_inverse_mass());
+    let total_inverse_mass = first_inverse_mass + second_inverse_mass;
+
+    // push bodies apart proportionally to their inverse masses
+    let correction = normal * contact.get_penetration() / total_inverse_mass;
+    first.set_position(*first.get_position() - correction * first_inverse_mass);
+    if let Some(body) = second.as_mut() {
+        body.set_position(*body.get_position() + correction * second_inverse_mass);
+    }
+
+    let second_velocity = second.as_ref().map_or(glam::vec2(0.0, 0.0), |body| *body.get_velocity());
+    let relative_velocity = second_velocity - *first.get_velocity();
+    let normal_speed = relative_velocity.dot(normal);
+    // already separating
+    if normal_speed >= 0.0 {
+        return;
+    }
+    let normal_impulse = -(1.0 + restitution) * normal_speed / total_inverse_mass;
+    let mut impulse = normal * normal_impulse;
+
+    // friction removes tangential velocity, but no more than coulomb limit allows
+    let tangent_velocity = relative_velocity - normal * normal_speed;
+    let tangent_speed = tangent_velocity.length();
+    if tangent_speed > 0.0 {
+        let friction_impulse = (tangent_speed / total_inverse_mass).min(friction * normal_impulse);
+        impulse -= tangent_velocity / tangent_speed * friction_impulse;
+    }
+
+    first.apply_impulse(-impulse);
+    if let Some(body) = second.as_mut() {
+        body.apply_impulse(impulse);
+    }
+}
+
+fn get_pair_mut(bodies: &mut Vec<RigidBody>, first: usize, second: usize) -> (&mut RigidBody, &mut RigidBody) {
+    let (head, tail) = bodies.split_at_mut(second);
+    (&mut head[first], &mut tail[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::api::ecs::components::ColliderShape;
+    use crate::engine::api::physics::rigid_body::RigidBody;
+
+    use super::{MAX_SUBSTEP_SECONDS, PhysicsWorld};
+
+    const FORCE: f32 = 6.0;
+
+    // body far from others in world without gravity, pushed by force before every step
+    fn velocity_after_steps(num_steps: u32, step_seconds: f32) -> f32 {
+        let mut physics_world = PhysicsWorld::new(glam::vec2(0.0, 0.0));
+        let body = physics_world.add_body(RigidBody::new(glam::vec2(0.0, 0.0), ColliderShape::CIRCLE { radius: 0.5 }, 2.0));
+        for _ in 0..num_steps {
+            physics_world.get_body_mut(body).apply_force(glam::vec2(FORCE, 0.0));
+            physics_world.step(&Duration::from_secs_f32(step_seconds));
+        }
+        physics_world.get_body(body).get_velocity().x
+    }
+
+    #[test]
+    fn force_acts_over_whole_step_with_substeps() {
+        let step_seconds = 4.0 * MAX_SUBSTEP_SECONDS;
+        // a = F / m
+        assert!((velocity_after_steps(1, step_seconds) - FORCE / 2.0 * step_seconds).abs() < 1e-4);
+    }
+
+    #[test]
+    fn force_does_not_depend_on_frame_rate() {
+        let slow_frames = velocity_after_steps(1, 4.0 * MAX_SUBSTEP_SECONDS);
+        let fast_frames = velocity_after_steps(4, MAX_SUBSTEP_SECONDS);
+        assert!((slow_frames - fast_frames).abs() < 1e-4);
+    }
+
+    #[test]
+    fn force_is_cleared_after_step() {
+        let mut physics_world = PhysicsWorld::new(glam::vec2(0.0, 0.0));
+        let body = physics_world.add_body(RigidBody::new(glam::vec2(0.0, 0.0), ColliderShape::CIRCLE { radius: 0.5 }, 1.0));
+        physics_world.get_body_mut(body).apply_force(glam::vec2(FORCE, 0.0));
+        physics_world.step(&Duration::from_secs_f32(MAX_SUBSTEP_SECONDS));
+        let velocity = *physics_world.get_body(body).get_velocity();
+        physics_world.step(&Duration::from_secs_f32(MAX_SUBSTEP_SECONDS));
+        assert_eq!(*physics_world.get_body(body).get_velocity(), velocity);
+    }
+}
@@ -0,0 +1,151 @@
+use glam::Vec2;
+
+use crate::engine::api::collision::shapes::CollisionShape;
+use crate::engine::api::ecs::components::ColliderShape;
+
+const DEFAULT_RESTITUTION: f32 = 0.2;
+const DEFAULT_FRICTION: f32 = 0.3;
+
+/**
+Body moved by physics world. Bodies do not rotate, position is center of their collider shape.
+```
+let mut body = RigidBody::new(glam::vec2(0.0, 5.0), ColliderShape::CIRCLE { radius: 0.5 }, 1.0);
+body.set_restitution(0.8);
+body.apply_impulse(glam::vec2(2.0, 0.0));
+let handle = physics_world.add_body(body);
+```
+ */
+#[derive(Clone, Debug)]
+pub struct RigidBody {
+    position: Vec2,
+    velocity: Vec2,
+    // constant acceleration on top of world gravity, e.g. engine thrust
+    acceleration: Vec2,
+    // forces applied since last step, cleared after integration
+    accumulated_force: Vec2,
+    inverse_mass: f32,
+    // 0 means no bounce, 1 means perfectly elastic bounce
+    restitution: f32,
+    // coulomb friction coefficient used when sliding along other bodies and static geometry
+    friction: f32,
+    // fraction of velocity lost per second, drag of air or water
+    linear_damping: f32,
+    shape: ColliderShape,
+}
+
+impl RigidBody {
+    pub fn new(position: Vec2, shape: ColliderShape, mass: f32) -> RigidBody {
+        if mass <= 0.0 {
+            panic!("Mass of rigid body must be positive, got {}", mass);
+        }
+        RigidBody {
+            position,
+            velocity: glam::vec2(0.0, 0.0),
+            acceleration: glam::vec2(0.0, 0.0),
+            accumulated_force: glam::vec2(0.0, 0.0),
+            inverse_mass: 1.0 / mass,
+            restitution: DEFAULT_RESTITUTION,
+            friction: DEFAULT_FRICTION,
+            linear_damping: 0.0,
+            shape,
+        }
+    }
+
+    pub fn get_position(&self) -> &Vec2 {
+        &self.position
+    }
+
+    // teleports body, velocity is kept
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+
+    pub fn get_velocity(&self) -> &Vec2 {
+        &self.velocity
+    }
+
+    pub fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity = velocity;
+    }
+
+    pub fn get_acceleration(&self) -> &Vec2 {
+        &self.acceleration
+    }
+
+    pub fn set_acceleration(&mut self, acceleration: Vec2) {
+        self.acceleration = acceleration;
+    }
+
+    pub fn get_mass(&self) -> f32 {
+        1.0 / self.inverse_mass
+    }
+
+    pub fn set_mass(&mut self, mass: f32) {
+        if mass <= 0.0 {
+            panic!("Mass of rigid body must be positive, got {}", mass);
+        }
+        self.inverse_mass = 1.0 / mass;
+    }
+
+    pub fn get_restitution(&self) -> f32 {
+        self.restitution
+    }
+
+    pub fn set_restitution(&mut self, restitution: f32) {
+        self.restitution = restitution;
+    }
+
+    pub fn get_friction(&self) -> f32 {
+        self.friction
+    }
+
+    pub fn set_friction(&mut self, friction: f32) {
+        self.friction = friction;
+    }
+
+    pub fn get_linear_damping(&self) -> f32 {
+        self.linear_damping
+    }
+
+    pub fn set_linear_damping(&mut self, linear_damping: f32) {
+        self.linear_damping = linear_damping;
+    }
+
+    pub fn get_shape(&self) -> &ColliderShape {
+        &self.shape
+    }
+
+    // force is applied during next step only
+    pub fn apply_force(&mut self, force: Vec2) {
+        self.accumulated_force += force;
+    }
+
+    // instant change of momentum
+    pub fn apply_impulse(&mut self, impulse: Vec2) {
+        self.velocity += impulse * self.inverse_mass;
+    }
+
+    pub fn get_collision_shape(&self) -> CollisionShape {
+        match self.shape {
+            ColliderShape::CIRCLE { radius } => CollisionShape::new_circle(self.position, radius),
+            ColliderShape::RECTANGLE { size } => CollisionShape::new_aabb(self.position - size / 2.0, self.position + size / 2.0),
+        }
+    }
+
+    // semi implicit euler, velocity is updated first and new velocity moves body
+    pub(crate) fn integrate(&mut self, gravity: &Vec2, delta_seconds: f32) {
+        let total_acceleration = *gravity + self.acceleration + self.accumulated_force * self.inverse_mass;
+        self.velocity += total_acceleration * delta_seconds;
+        self.velocity *= (1.0 - self.linear_damping * delta_seconds).max(0.0);
+        self.position += self.velocity * delta_seconds;
+    }
+
+    // forces act over whole step, so they are cleared after its last substep
+    pub(crate) fn clear_forces(&mut self) {
+        self.accumulated_force = glam::vec2(0.0, 0.0);
+    }
+
+    pub(crate) fn get_inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+}
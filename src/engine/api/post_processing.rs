@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+
+use crate::engine::api::colour::Colour;
+use crate::engine::api::drawable::Drawable;
+use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::resource_manager::ResourceManager;
+use crate::engine::rendering::material::{Material, UniformKind};
+use crate::engine::rendering::post_processing::PostProcessingChain;
+
+/**
+Passes applied after scene is drawn, in order of registration, previous pass output is bound as texture to sampler `screenTexture`.
+Passes of engine (`EngineUtilities::get_post_processing`) cover whole frame, passes given to a camera cover only what that camera draws.
+```
+let post_processing = Rc::new(PostProcessing::new());
+post_processing.register_built_in_passes(&*resource_manager);
+post_processing.set_enabled(BuiltInPass::BLOOM.get_name(), true);
+let camera_config = camera_config.with_post_processing(Rc::clone(&post_processing));
+```
+ */
+pub struct PostProcessing {
+    passes: RefCell<Vec<PostProcessPass>>,
+    // created on first render, its targets follow size of the viewport passes are applied to
+    chain: RefCell<Option<PostProcessingChain>>,
+}
+
+impl PostProcessing {
+    pub fn new() -> PostProcessing {
+        PostProcessing {
+            passes: RefCell::new(vec!()),
+            chain: RefCell::new(None),
+        }
+    }
+
+    // registers all built in passes, disabled
+    pub fn register_built_in_passes(&self, resource_manager: &dyn ResourceManager) {
+        for built_in in BuiltInPass::all().iter() {
+            self.add_pass(built_in.get_name(), resource_manager.fetch_shader_material(built_in.get_shader_id()), false);
+        }
+    }
+
+    pub fn add_pass(&self, name: &str, material: Material, enabled: bool) {
+        if self.has_pass(name) {
+            panic!("Post processing pass {:?} is already registered", name);
+        }
+        self.passes.borrow_mut().push(PostProcessPass {
+            name: String::from(name),
+            material,
+            enabled,
+        });
+    }
+
+    pub fn has_pass(&self, name: &str) -> bool {
+        self.passes.borrow().iter().any(|pass| pass.name == name)
+    }
+
+    pub fn set_enabled(&self, name: &str, enabled: bool) {
+        self.with_pass(name, |pass| pass.enabled = enabled)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.passes.borrow().iter().any(|pass| pass.name == name && pass.enabled)
+    }
+
+    pub fn has_enabled_passes(&self) -> bool {
+        self.passes.borrow().iter().any(|pass| pass.enabled)
+    }
+
+    pub fn set_pass_variable(&self, name: &str, variable_name: &str, kind: UniformKind) {
+        self.with_pass(name, |pass| pass.material.set_variable(variable_name, kind))
+    }
+
+    // copies of enabled pass materials in order, used by renderer
+    // copied so that passes can be changed while drawables are rendered, time of each pass keeps counting from its first use
+    pub fn get_enabled_materials(&self) -> Vec<Material> {
+        self.passes.borrow_mut().iter_mut()
+            .filter(|pass| pass.enabled)
+            .map(|pass| {
+                pass.material.mark_activated();
+                pass.material.clone()
+            })
+            .collect()
+    }
+
+    // renders cameras through enabled passes into part of currently bound framebuffer given by viewport (x, y, width, height) in pixels
+    // camera viewports are relative to that part, outside of it framebuffer is kept and so is it below transparent background
+    pub fn render(&self,
+                  objects: &mut [&mut dyn Drawable],
+                  cameras: &[CameraConfig],
+                  render_util: &RenderUtil,
+                  viewport: (i32, i32, i32, i32),
+                  background: Colour,
+    ) {
+        let mut materials = self.get_enabled_materials();
+        if materials.is_empty() {
+            panic!("Post processing has no enabled passes to render with");
+        }
+        // passes stay unborrowed, drawables may toggle them while being rendered
+        self.chain.borrow_mut()
+            .get_or_insert_with(PostProcessingChain::new)
+            .render(objects, cameras, render_util, &mut materials, viewport, background);
+    }
+
+    fn with_pass<F: FnOnce(&mut PostProcessPass)>(&self, name: &str, action: F) {
+        match self.passes.borrow_mut().iter_mut().find(|pass| pass.name == name) {
+            None => { panic!("Post processing pass {:?} is not registered", name) }
+            Some(pass) => action(pass)
+        }
+    }
+}
+
+pub struct PostProcessPass {
+    name: String,
+    material: Material,
+    enabled: bool,
+}
+
+impl PostProcessPass {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuiltInPass {
+    VIGNETTE,
+    // glow around bright pixels
+    BLOOM,
+    GREYSCALE,
+    // scanlines and screen curvature
+    CRT,
+}
+
+impl BuiltInPass {
+    pub fn all() -> [BuiltInPass; 4] {
+        [BuiltInPass::VIGNETTE, BuiltInPass::BLOOM, BuiltInPass::GREYSCALE, BuiltInPass::CRT]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            BuiltInPass::VIGNETTE => { "vignette" }
+            BuiltInPass::BLOOM => { "bloom" }
+            BuiltInPass::GREYSCALE => { "greyscale" }
+            BuiltInPass::CRT => { "crt" }
+        }
+    }
+
+    fn get_shader_id(&self) -> &'static str {
+        match self {
+            BuiltInPass::VIGNETTE => { "engine/shaders/post_processing/vignette" }
+            BuiltInPass::BLOOM => { "engine/shaders/post_processing/bloom" }
+            BuiltInPass::GREYSCALE => { "engine/shaders/post_processing/greyscale" }
+            BuiltInPass::CRT => { "engine/shaders/post_processing/crt" }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::cell::RefCell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::distributions::uniform::{SampleRange, SampleUniform};
+use rand::prelude::*;
+
+// games should take all randomness from here so that seeded runs, e.g. input replays, are reproducible
+pub struct RandomGenerator {
+    seed: u64,
+    rng: RefCell<StdRng>,
+}
+
+impl RandomGenerator {
+    pub fn new(seed: u64) -> RandomGenerator {
+        RandomGenerator {
+            seed,
+            rng: RefCell::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn from_current_time() -> RandomGenerator {
+        RandomGenerator::new(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64)
+    }
+
+    // seed generator was created with, not its current state
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn gen_range<T: SampleUniform, R: SampleRange<T>>(&self, range: R) -> T {
+        self.rng.borrow_mut().gen_range(range)
+    }
+}
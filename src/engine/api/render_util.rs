@@ -1,32 +1,201 @@
-use glam::{Mat4, Vec3};
-use crate::engine::api::game_api::CameraConfig;
+
+use std::cell::RefCell;
+
+use glam::Mat4;
+use crate::engine::api::game_api::{CameraConfig, DEFAULT_LAYER};
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
+use crate::engine::api::texture::Sprite;
 use crate::engine::opengl_context::OpenglContext;
+use crate::engine::rendering::material::Material;
+use crate::engine::rendering::sprite_batch::{DeferredDraw, SpriteBatch};
+
+/**
+Place of drawn geometry in draw order. Higher layers are drawn over lower ones and inside of a layer higher order is drawn
+over lower one, geometry with equal order keeps order in which it was rendered. Layer also decides which cameras show it.
+Opaque geometry writes depth and is drawn front to back before transparent geometry with the same order, which is drawn back to front.
+```
+// dragged piece is drawn over all other pieces no matter where it is in the vector
+piece.render(&render_util.with_render_order(RenderOrder::new(DEFAULT_LAYER, 1)));
+```
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOrder {
+    layer: u32,
+    order_in_layer: i32,
+    opaque: bool,
+}
 
+impl RenderOrder {
+    // transparent by default, that is safe for anything with alpha
+    pub fn new(layer: u32, order_in_layer: i32) -> RenderOrder {
+        RenderOrder {
+            layer,
+            order_in_layer,
+            opaque: false,
+        }
+    }
+
+    // same layer with order moved by offset, lets composites order their parts relative to the order they are given
+    pub fn offset_by(&self, offset: i32) -> RenderOrder {
+        RenderOrder {
+            layer: self.layer,
+            order_in_layer: self.order_in_layer + offset,
+            opaque: self.opaque,
+        }
+    }
+
+    pub fn with_opaque(mut self, opaque: bool) -> RenderOrder {
+        self.opaque = opaque;
+        self
+    }
+
+    pub fn get_layer(&self) -> u32 {
+        self.layer
+    }
+
+    pub fn get_order_in_layer(&self) -> i32 {
+        self.order_in_layer
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.opaque
+    }
+}
+
+#[derive(Clone)]
 pub struct RenderUtil<'a> {
     camera_config: CameraConfig,
     opengl_context: &'a OpenglContext,
+    // size of part of surface currently rendered to, window or render target, that camera viewport covers
+    viewport_size: glam::Vec2,
+    sprite_batch: &'a RefCell<SpriteBatch>,
+    // with fixed timestep tells how far rendered frame is between last and next update, always 1 otherwise
+    interpolation_alpha: f32,
+    // world matrix of scene node that is currently rendered, everything drawn with this util is its child
+    parent_world_matrix: Mat4,
+    // everything drawn with this util is placed in draw order according to this
+    render_order: RenderOrder,
+    // polygons of camera views are drawn as outlines
+    wireframe: bool,
 }
 
 impl<'a> RenderUtil<'a> {
-    pub fn new(camera_config: CameraConfig, opengl_context: &'a OpenglContext) -> RenderUtil<'a> {
+    pub fn new(camera_config: CameraConfig, opengl_context: &'a OpenglContext, sprite_batch: &'a RefCell<SpriteBatch>) -> RenderUtil<'a> {
+        let win_size = opengl_context.get_window_size();
+        RenderUtil::with_viewport_size(camera_config, opengl_context, sprite_batch, glam::Vec2::new(win_size.0 as f32, win_size.1 as f32))
+    }
+
+    pub fn with_viewport_size(camera_config: CameraConfig, opengl_context: &'a OpenglContext, sprite_batch: &'a RefCell<SpriteBatch>, viewport_size: glam::Vec2) -> RenderUtil<'a> {
         RenderUtil {
             camera_config,
             opengl_context,
+            viewport_size,
+            sprite_batch,
+            interpolation_alpha: 1.0,
+            parent_world_matrix: Mat4::IDENTITY,
+            render_order: RenderOrder::new(DEFAULT_LAYER, 0),
+            wireframe: false,
         }
     }
 
-    pub fn calculate_camera_MVP(&self, position: glam::Vec3, scale: glam::Vec3, scale_point_offset: Vec3) -> glam::Mat4 {
-        let projection = self.camera_config.get_projection_matrix(self.opengl_context.get_aspect_ratio());
+    pub fn with_interpolation_alpha(mut self, interpolation_alpha: f32) -> RenderUtil<'a> {
+        self.interpolation_alpha = interpolation_alpha;
+        self
+    }
+
+    pub fn with_wireframe(mut self, wireframe: bool) -> RenderUtil<'a> {
+        self.wireframe = wireframe;
+        self
+    }
+
+    // render util for another camera drawing to part of the same surface, viewport size is size of that part
+    pub fn with_camera(&self, camera_config: CameraConfig, viewport_size: glam::Vec2) -> RenderUtil<'a> {
+        let mut camera_render_util = self.clone();
+        camera_render_util.camera_config = camera_config;
+        camera_render_util.viewport_size = viewport_size;
+        camera_render_util
+    }
+
+    // render util for drawables placed elsewhere in draw order, children of the drawable inherit it
+    pub fn with_render_order(&self, render_order: RenderOrder) -> RenderUtil<'a> {
+        let mut ordered_render_util = self.clone();
+        ordered_render_util.render_order = render_order;
+        ordered_render_util
+    }
+
+    // render util for children of scene node with given local transform
+    pub fn with_parent_transform(&self, transform: &Transform) -> RenderUtil<'a> {
+        let mut child_render_util = self.clone();
+        child_render_util.parent_world_matrix = self.get_world_matrix(transform.get_local_matrix());
+        child_render_util
+    }
+
+    // composes local matrix of drawn object with world matrices of all its parents
+    pub fn get_world_matrix(&self, local_matrix: Mat4) -> Mat4 {
+        self.parent_world_matrix * local_matrix
+    }
+
+    pub fn calculate_camera_MVP(&self, world_matrix: Mat4) -> Mat4 {
+        self.get_view_projection() * world_matrix
+    }
+
+    pub fn get_view_projection(&self) -> glam::Mat4 {
+        let projection = self.camera_config.get_projection_matrix(self.viewport_size);
         let view = self.camera_config.get_view_matrix();
-        let scaling_conjugate = glam::Mat4::from_translation(scale_point_offset);
-        let scaling_conjugate_inverse = scaling_conjugate.clone().inverse();
-        let model = glam::Mat4::from_translation(position) * scaling_conjugate_inverse * glam::Mat4::from_scale(scale) * scaling_conjugate;
-        // let model = glam::Mat4::from_scale_rotation_translation(scale, glam::quat(0.0, 0.0, 0.0, 0.0), position);
-        return projection * view * model;
+        projection * view
+    }
+
+    // vertices must be already in world space, geometry on layers hidden from camera is dropped
+    pub fn submit_to_sprite_batch(&self, vertices: &[TexturedVertexDataLayout], indices: &[i32], material: &Material, sprite: &Sprite) {
+        if !self.is_layer_visible(self.render_order.get_layer()) {
+            return;
+        }
+        self.sprite_batch.borrow_mut().submit(vertices, indices, material, sprite, self.get_view_projection(), self.viewport_size, self.render_order);
+    }
+
+    // geometry hidden from camera is dropped, depth of deferred draw must be already projected with this camera
+    pub fn submit_deferred_draw(&self, deferred_draw: DeferredDraw) {
+        if !self.is_layer_visible(self.render_order.get_layer()) {
+            return;
+        }
+        self.sprite_batch.borrow_mut().submit_deferred(deferred_draw, self.render_order);
+    }
+
+    pub fn flush_sprite_batch(&self) {
+        self.sprite_batch.borrow_mut().flush();
+    }
+
+    pub fn get_sprite_batch(&self) -> &'a RefCell<SpriteBatch> {
+        self.sprite_batch
+    }
+
+    pub fn get_render_order(&self) -> &RenderOrder {
+        &self.render_order
+    }
+
+    pub fn get_camera_config(&self) -> &CameraConfig {
+        &self.camera_config
+    }
+
+    // drawables that belong to a layer skip rendering when camera does not show it
+    pub fn is_layer_visible(&self, layer: u32) -> bool {
+        self.camera_config.get_layer_mask().contains(layer)
     }
 
     pub fn get_window_size(&self) -> glam::Vec2 {
-        let win_size = self.opengl_context.window.size();
-        glam::Vec2::new(win_size.0 as f32, win_size.1 as f32)
+        self.viewport_size
+    }
+
+    pub fn is_wireframe(&self) -> bool {
+        self.wireframe
+    }
+
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+
+    pub fn get_opengl_context(&self) -> &'a OpenglContext {
+        self.opengl_context
     }
 }
@@ -6,13 +6,31 @@ use crate::engine::api::texture::{Sprite};
 use crate::engine::rendering::material::Material;
 use crate::engine::rendering::ShaderProgram;
 use crate::engine::resources::fonts::SizedFont;
+use crate::engine::resources::texture_atlas::TextureAtlas;
 
 pub trait ResourceManager {
     fn fetch_shader_program(&self, id: &str) -> Rc<ShaderProgram>;
     fn fetch_shader_material(&self, id: &str) -> Material;
     fn fetch_sprite(&self, id: &str) -> Sprite;
     fn fetch_sprite_sheet(&self, id: &str, n_rows: u32, n_cols: u32) -> Sprite;
+    // packs images into shared textures, atlas is built once per id and entries are keyed by image ids
+    // every fetch of the same id must list the same images, otherwise it panics
+    fn fetch_atlas(&self, id: &str, image_ids: &[&str]) -> Rc<TextureAtlas>;
     fn fetch_audio(&self, id: &str) -> Rc<AudioResource>;
     fn fetch_font(&self, id: &str) -> Rc<SizedFont>;
     fn read_file_lines(&self, id: &str) -> io::Lines<io::BufReader<File>>;
+    fn get_cache_stats(&self) -> ResourceCacheStats;
+    // resources are loaded again on next fetch, already fetched ones stay alive as long as they are used
+    // returns number of dropped cache entries
+    fn clear_caches(&self) -> usize;
+}
+
+// number of cached entries of each resource kind
+#[derive(Clone, Debug)]
+pub struct ResourceCacheStats {
+    pub textures: usize,
+    pub atlases: usize,
+    pub shaders: usize,
+    pub audio: usize,
+    pub fonts: usize,
 }
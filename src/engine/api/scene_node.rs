@@ -0,0 +1,86 @@
+use sdl2::event::Event;
+
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::render_util::{RenderOrder, RenderUtil};
+use crate::engine::opengl_context::OpenglContext;
+
+/**
+Node of scene graph, everything drawn by its content is expressed in local space of the node
+and inherits world matrix of the node, so moving, scaling or rotating node moves all of its content.
+Nodes can be nested by using composites containing other nodes as content.
+```
+let mut panel = SceneNode::new(Transform::from_translation(glam::vec3(5.0, 0.0, 0.0)), ScorePanel::new(...));
+// moves every label inside of the panel
+panel.get_transform_mut().translate_by(glam::vec3(1.0, 0.0, 0.0));
+```
+Events are not transformed, mouse positions must be moved into local space of the node with `to_local_point`.
+Node with render order places all of its content at that order, otherwise content keeps order of the parent.
+ */
+pub struct SceneNode<T: Drawable> {
+    transform: Transform,
+    content: T,
+    render_order: Option<RenderOrder>,
+}
+
+impl<T: Drawable> SceneNode<T> {
+    pub fn new(transform: Transform, content: T) -> SceneNode<T> {
+        SceneNode {
+            transform,
+            content,
+            render_order: None,
+        }
+    }
+
+    pub fn with_render_order(mut self, render_order: RenderOrder) -> SceneNode<T> {
+        self.render_order = Some(render_order);
+        self
+    }
+
+    pub fn set_render_order(&mut self, render_order: Option<RenderOrder>) {
+        self.render_order = render_order;
+    }
+
+    pub fn get_render_order(&self) -> &Option<RenderOrder> {
+        &self.render_order
+    }
+
+    pub fn get_transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    pub fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
+    }
+
+    pub fn get_content(&self) -> &T {
+        &self.content
+    }
+
+    pub fn get_content_mut(&mut self) -> &mut T {
+        &mut self.content
+    }
+
+    // point must be in space of the parent of this node, world space for nodes at the root
+    pub fn to_local_point(&self, parent_point: &glam::Vec3) -> glam::Vec3 {
+        self.transform.to_local_point(parent_point)
+    }
+}
+
+impl<T: Drawable> Drawable for SceneNode<T> {
+    fn render(&mut self, render_util: &RenderUtil) {
+        let child_render_util = render_util.with_parent_transform(&self.transform);
+        match &self.render_order {
+            None => self.content.render(&child_render_util),
+            Some(render_order) => self.content.render(&child_render_util.with_render_order(*render_order))
+        }
+    }
+
+    fn update(&mut self, update_context: &UpdateContext) {
+        self.content.update(update_context)
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
+        self.content.handle_event(event, context, update_context)
+    }
+}
@@ -6,17 +6,23 @@ use crate::engine::api::colour::{Colour, WHITE};
 use crate::engine::api::drawable::Drawable;
 use crate::engine::api::maths::rectangle::Rectangle;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::rendering::material::{Material, UniformKind};
 use crate::engine::resources::fonts::SizedFont;
 
+// size of one pixel of font glyphs in local space of text, text scaled by its inverse is drawn
+// at font pixel size by screen space camera
+pub const TEXT_SCALE: f32 = 0.01;
+
 pub struct TextGameObject {
     sized_font: Rc<SizedFont>,
     text: String,
     rects: Vec<Rectangle<TexturedVertexDataLayout>>,
     material: Material,
-    position: Vec3,
+    // characters are laid out from local origin, which is placed at text position by transform
+    transform: Transform,
     colour: Colour,
 }
 
@@ -29,25 +35,25 @@ impl TextGameObject {
         TextGameObject {
             sized_font: Rc::clone(&sized_font),
             text: String::from(text),
-            rects: TextGameObject::init_rects(sized_font, text, position, material.clone(), &colour),
+            rects: TextGameObject::init_rects(sized_font, text, material.clone(), &colour),
             material: material.clone(),
-            position,
+            transform: Transform::from_translation(position),
             colour,
         }
     }
 
-    fn init_rects(sized_font: Rc<SizedFont>, text: &str, position: Vec3, mut material: Material, colour: &Colour) -> Vec<Rectangle<TexturedVertexDataLayout>> {
+    fn init_rects(sized_font: Rc<SizedFont>, text: &str, mut material: Material, colour: &Colour) -> Vec<Rectangle<TexturedVertexDataLayout>> {
         let mut shift = 0.0;
-        let scale = 0.01;
+        let scale = TEXT_SCALE;
         let mut rects = vec!();
         for ch in text.chars() {
             let font_character = sized_font.get_char(ch);
 
             let scaled_bearing = font_character.get_bearing().x * scale;
-            let x_pos = position.x + shift + scaled_bearing;
+            let x_pos = shift + scaled_bearing;
 
             // for characters below baseline like p or g
-            let y_pos = position.y - (font_character.get_size().y - font_character.get_bearing().y) * scale;
+            let y_pos = -(font_character.get_size().y - font_character.get_bearing().y) * scale;
 
             let w = font_character.get_size().x * scale;
             let h = font_character.get_size().y * scale;
@@ -63,7 +69,7 @@ impl TextGameObject {
             material.set_variable("color", UniformKind::VEC_4 { value: colour.clone().into() });
 
             let rect = Rectangle::new_textured(
-                &glam::vec3(x_pos, y_pos, position.z),
+                &glam::vec3(x_pos, y_pos, 0.0),
                 &glam::vec2(w, h),
                 material.clone(),
                 font_character.get_sprite().clone(),
@@ -79,24 +85,26 @@ impl TextGameObject {
             let new_quads = TextGameObject::init_rects(
                 Rc::clone(&self.sized_font),
                 new_text.as_str(),
-                self.position,
                 self.material.clone(),
                 &self.colour,
             );
             self.rects = new_quads;
+            self.text = new_text;
         }
     }
 }
 
 impl Drawable for TextGameObject {
     fn render(&mut self, render_util: &RenderUtil) {
-        self.rects.iter_mut().for_each(|q| q.render(render_util))
+        let text_render_util = render_util.with_parent_transform(&self.transform);
+        self.rects.iter_mut().for_each(|q| q.render(&text_render_util))
     }
 }
 
 impl Area for TextGameObject {
-    fn contains_point(&self, point: &(f32, f32)) -> bool {
-        self.rects.iter().any(|rect| rect.contains_point(point))
+    // characters have their own transforms inside of text local space
+    fn contains_local_point(&self, local_point: &(f32, f32)) -> bool {
+        self.rects.iter().any(|rect| rect.contains_point(local_point))
     }
 
     fn area(&self) -> f32 {
@@ -107,23 +115,11 @@ impl Area for TextGameObject {
         todo!()
     }
 
-    fn get_pos(&self) -> &Vec3 {
-        todo!()
+    fn get_transform(&self) -> &Transform {
+        &self.transform
     }
 
-    fn move_to(&mut self, final_position: Vec3) {
-        todo!()
+    fn get_transform_mut(&mut self) -> &mut Transform {
+        &mut self.transform
     }
-
-    fn move_by(&mut self, offset: Vec3) {
-        todo!()
-    }
-
-    fn get_scale(&self) -> &Vec3 {
-        todo!()
-    }
-
-    fn set_scale(&mut self, new_scale: Vec3) {
-        todo!()
-    }
-}
\ No newline at end of file
+}
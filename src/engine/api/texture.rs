@@ -18,6 +18,15 @@ impl Texture {
     }
 
     pub fn from_raw_data(data: Vec<u8>, width: i32, height: i32, texture_params: TextureParams, internal_format: InternalFormat) -> Texture {
+        Texture::allocate(data.as_ptr() as *const _ as *const c_void, width, height, texture_params, internal_format)
+    }
+
+    // texture with uninitialized content, meant to be rendered to
+    pub fn new_empty(width: i32, height: i32, texture_params: TextureParams, internal_format: InternalFormat) -> Texture {
+        Texture::allocate(std::ptr::null(), width, height, texture_params, internal_format)
+    }
+
+    fn allocate(data: *const c_void, width: i32, height: i32, texture_params: TextureParams, internal_format: InternalFormat) -> Texture {
         let mut texture_id: gl::types::GLuint = 0;
         unsafe {
             gl::GenTextures(1, &mut texture_id);
@@ -32,7 +41,7 @@ impl Texture {
                 0,
                 internal_format.to_gl_type(),
                 gl::UNSIGNED_BYTE,
-                data.as_ptr() as *const _ as *const c_void,
+                data,
             );
             texture_params.set_params();
             gl::BindTexture(gl::TEXTURE_2D, 0);
@@ -60,7 +69,18 @@ impl Texture {
             gl::BindTexture(gl::TEXTURE_2D, 0);
         }
     }
-    // todo drop
+
+    pub fn get_id(&self) -> gl::types::GLuint {
+        self.texture_id
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &mut self.texture_id);
+        }
+    }
 }
 
 // sprite sheet should be separate class maybe?
@@ -85,6 +105,28 @@ impl Sprite {
         }
     }
 
+    // sprite covering only part of texture, region is given in normalized texture coordinates
+    pub fn new_region(texture: Rc<Texture>, region_min: glam::Vec2, region_max: glam::Vec2) -> Sprite {
+        Sprite {
+            texture: Rc::clone(&texture),
+            topology: SpriteSheetTopology::new_in_region(texture.get_size().clone(), 1, 1, region_min, region_max),
+        }
+    }
+
+    // spritesheet that occupies part of texture, for example spritesheet packed into atlas
+    pub fn as_spritesheet(&self, n_rows: u32, n_cols: u32) -> Sprite {
+        Sprite {
+            texture: Rc::clone(&self.texture),
+            topology: SpriteSheetTopology::new_in_region(
+                self.topology.spritesheet_size,
+                n_rows,
+                n_cols,
+                self.topology.region_min,
+                self.topology.region_max,
+            ),
+        }
+    }
+
     pub fn start_drawing(&self) {
         self.texture.bind()
     }
@@ -93,10 +135,19 @@ impl Sprite {
         self.texture.unbind()
     }
 
+    pub fn get_texture_id(&self) -> gl::types::GLuint {
+        self.texture.get_id()
+    }
+
     pub fn get_texture_coords(&self) -> TextureCoords {
         self.topology.get_texture_coords(0, 0).unwrap()
     }
 
+    // number of rows and columns of spritesheet
+    pub fn get_spritesheet_dimensions(&self) -> (u32, u32) {
+        (self.topology.n_rows, self.topology.n_cols)
+    }
+
     pub fn get_texture_coords_from_spritesheet(&self, row: u32, col: u32) -> TextureCoords {
         self.topology.get_texture_coords(row, col).unwrap()
     }
@@ -107,14 +158,23 @@ struct SpriteSheetTopology {
     spritesheet_size: glam::Vec2,
     n_rows: u32,
     n_cols: u32,
+    // part of texture that spritesheet occupies, in normalized texture coordinates
+    region_min: glam::Vec2,
+    region_max: glam::Vec2,
 }
 
 impl SpriteSheetTopology {
     fn new(spritesheet_size: glam::Vec2, n_rows: u32, n_cols: u32) -> SpriteSheetTopology {
+        SpriteSheetTopology::new_in_region(spritesheet_size, n_rows, n_cols, glam::vec2(0.0, 0.0), glam::vec2(1.0, 1.0))
+    }
+
+    fn new_in_region(spritesheet_size: glam::Vec2, n_rows: u32, n_cols: u32, region_min: glam::Vec2, region_max: glam::Vec2) -> SpriteSheetTopology {
         SpriteSheetTopology {
             spritesheet_size,
             n_rows,
             n_cols,
+            region_min,
+            region_max,
         }
     }
 
@@ -124,12 +184,13 @@ impl SpriteSheetTopology {
         }
         // todo: cache in member variable
         let single_sprite_size = (self.spritesheet_size.x as f32 / self.n_cols as f32, self.spritesheet_size.y as f32 / self.n_rows as f32);
-        return Ok(TextureCoords {
+        let coords = TextureCoords {
             top_right: ((single_sprite_size.0 * col as f32 + single_sprite_size.0) / self.spritesheet_size.x as f32, (single_sprite_size.1 * row as f32 + single_sprite_size.1) / self.spritesheet_size.y as f32),
             bottom_right: ((single_sprite_size.0 * col as f32 + single_sprite_size.0) / self.spritesheet_size.x as f32, (single_sprite_size.1 * row as f32) / self.spritesheet_size.y as f32),
             bottom_left: ((single_sprite_size.0 * col as f32) / self.spritesheet_size.x as f32, (single_sprite_size.1 * row as f32) / self.spritesheet_size.y as f32),
             top_left: ((single_sprite_size.0 * col as f32) / self.spritesheet_size.x as f32, (single_sprite_size.1 * row as f32 + single_sprite_size.1) / self.spritesheet_size.y as f32),
-        });
+        };
+        return Ok(coords.remap_to_region(self.region_min, self.region_max));
     }
 }
 
@@ -146,6 +207,20 @@ pub struct TextureCoords {
     pub top_left: (f32, f32),
 }
 
+impl TextureCoords {
+    // coords relative to region are turned into coords relative to whole texture
+    fn remap_to_region(&self, region_min: glam::Vec2, region_max: glam::Vec2) -> TextureCoords {
+        let region_size = region_max - region_min;
+        let remap = |c: (f32, f32)| (region_min.x + c.0 * region_size.x, region_min.y + c.1 * region_size.y);
+        TextureCoords {
+            top_right: remap(self.top_right),
+            bottom_right: remap(self.bottom_right),
+            bottom_left: remap(self.bottom_left),
+            top_left: remap(self.top_left),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TextureParams {
     texture_min_filter: Option<TextureFilterType>,
@@ -273,6 +348,7 @@ impl TextureFilterType {
     }
 }
 
+#[derive(Clone)]
 pub enum InternalFormat {
     RED,
     RGBA,
@@ -287,4 +363,12 @@ impl InternalFormat {
             InternalFormat::RGB => { gl::RGB }
         }
     }
+
+    pub fn get_bytes_per_pixel(&self) -> usize {
+        match self {
+            InternalFormat::RED => { 1 }
+            InternalFormat::RGBA => { 4 }
+            InternalFormat::RGB => { 3 }
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+use sdl2::event::Event;
+
+use crate::engine::api::collision::shapes::Aabb;
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
+use crate::engine::api::game_api::{CameraConfig, HUD_LAYER};
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::render_util::{RenderOrder, RenderUtil};
+use crate::engine::opengl_context::OpenglContext;
+
+// point of viewport and of ui element that are placed at each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Anchor {
+    TOP_LEFT,
+    TOP_CENTRE,
+    TOP_RIGHT,
+    CENTRE_LEFT,
+    CENTRE,
+    CENTRE_RIGHT,
+    BOTTOM_LEFT,
+    BOTTOM_CENTRE,
+    BOTTOM_RIGHT,
+}
+
+impl Anchor {
+    // fraction of width and height measured from bottom left corner
+    pub fn get_fraction(&self) -> glam::Vec2 {
+        match self {
+            Anchor::TOP_LEFT => glam::vec2(0.0, 1.0),
+            Anchor::TOP_CENTRE => glam::vec2(0.5, 1.0),
+            Anchor::TOP_RIGHT => glam::vec2(1.0, 1.0),
+            Anchor::CENTRE_LEFT => glam::vec2(0.0, 0.5),
+            Anchor::CENTRE => glam::vec2(0.5, 0.5),
+            Anchor::CENTRE_RIGHT => glam::vec2(1.0, 0.5),
+            Anchor::BOTTOM_LEFT => glam::vec2(0.0, 0.0),
+            Anchor::BOTTOM_CENTRE => glam::vec2(0.5, 0.0),
+            Anchor::BOTTOM_RIGHT => glam::vec2(1.0, 0.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UiLength {
+    PIXELS(f32),
+    // percent of viewport width for horizontal lengths and of its height for vertical ones
+    PERCENT(f32),
+}
+
+impl UiLength {
+    pub fn resolve(&self, viewport_length: f32) -> f32 {
+        match *self {
+            UiLength::PIXELS(pixels) => pixels,
+            UiLength::PERCENT(percent) => viewport_length * percent / 100.0,
+        }
+    }
+}
+
+/**
+Size and placement of ui element relative to viewport of screen space camera, resolved again for every viewport size
+so elements follow window resizes. Offset moves element away from the viewport edges it is anchored to,
+along centred axes it moves element right or up.
+```
+// 20 pixels from top right corner, fifth of window wide
+let layout = UiLayout::new(Anchor::TOP_RIGHT, UiLength::PERCENT(20.0), UiLength::PIXELS(120.0))
+    .with_offset(UiLength::PIXELS(20.0), UiLength::PIXELS(20.0));
+```
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiLayout {
+    anchor: Anchor,
+    width: UiLength,
+    height: UiLength,
+    offset_x: UiLength,
+    offset_y: UiLength,
+}
+
+impl UiLayout {
+    pub fn new(anchor: Anchor, width: UiLength, height: UiLength) -> UiLayout {
+        UiLayout {
+            anchor,
+            width,
+            height,
+            offset_x: UiLength::PIXELS(0.0),
+            offset_y: UiLength::PIXELS(0.0),
+        }
+    }
+
+    pub fn with_offset(mut self, offset_x: UiLength, offset_y: UiLength) -> UiLayout {
+        self.offset_x = offset_x;
+        self.offset_y = offset_y;
+        self
+    }
+
+    pub fn get_anchor(&self) -> &Anchor {
+        &self.anchor
+    }
+
+    // rectangle in pixels from bottom left corner of viewport
+    pub fn resolve(&self, viewport_size: glam::Vec2) -> Aabb {
+        let fraction = self.anchor.get_fraction();
+        let size = glam::vec2(self.width.resolve(viewport_size.x), self.height.resolve(viewport_size.y));
+        // elements anchored to right or top edge are moved left or down by offset
+        let direction = glam::vec2(
+            if fraction.x == 1.0 { -1.0 } else { 1.0 },
+            if fraction.y == 1.0 { -1.0 } else { 1.0 },
+        );
+        let offset = glam::vec2(self.offset_x.resolve(viewport_size.x), self.offset_y.resolve(viewport_size.y)) * direction;
+        let min = viewport_size * fraction - size * fraction + offset;
+        Aabb::new(min, min + size)
+    }
+}
+
+/**
+Ui element drawn by screen space camera, its content is laid out in pixels from bottom left corner of element rectangle.
+Element is drawn on hud layer so world cameras skip it, and mouse is tested against it in window coordinates.
+```
+let mut start_button = UiNode::new(
+    UiLayout::new(Anchor::BOTTOM_RIGHT, UiLength::PIXELS(140.0), UiLength::PIXELS(50.0)),
+    start_text,
+);
+// in handle_event
+if start_button.contains_sdl_point(update_context.get_sdl_mouse_position(), context, update_context.get_camera_config()) { ... }
+```
+ */
+pub struct UiNode<T: Drawable> {
+    layout: UiLayout,
+    content: T,
+    render_order: RenderOrder,
+}
+
+impl<T: Drawable> UiNode<T> {
+    pub fn new(layout: UiLayout, content: T) -> UiNode<T> {
+        UiNode {
+            layout,
+            content,
+            render_order: RenderOrder::new(HUD_LAYER, 0),
+        }
+    }
+
+    // elements with higher order are drawn over ones with lower order
+    pub fn with_order_in_layer(mut self, order_in_layer: i32) -> UiNode<T> {
+        self.render_order = RenderOrder::new(self.render_order.get_layer(), order_in_layer);
+        self
+    }
+
+    pub fn get_layout(&self) -> &UiLayout {
+        &self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: UiLayout) {
+        self.layout = layout;
+    }
+
+    pub fn get_content(&self) -> &T {
+        &self.content
+    }
+
+    pub fn get_content_mut(&mut self) -> &mut T {
+        &mut self.content
+    }
+
+    // point in pixels from bottom left corner of viewport
+    pub fn contains_viewport_point(&self, point: &glam::Vec2, viewport_size: glam::Vec2) -> bool {
+        self.layout.resolve(viewport_size).contains_point(point)
+    }
+
+    // camera is screen space camera drawing this node or any camera sharing its viewport, e.g. world camera of the game
+    pub fn contains_sdl_point(&self, sdl_point: &glam::Vec2, context: &OpenglContext, camera_config: &CameraConfig) -> bool {
+        let window_size = context.get_window_size();
+        let (_, _, w, h) = camera_config.get_viewport().to_pixels(glam::vec2(window_size.0 as f32, window_size.1 as f32));
+        match context.sdl_window_to_viewport_pixels(sdl_point, camera_config) {
+            None => false,
+            Some(viewport_point) => self.contains_viewport_point(&viewport_point, glam::vec2(w as f32, h as f32))
+        }
+    }
+}
+
+impl<T: Drawable> Drawable for UiNode<T> {
+    fn render(&mut self, render_util: &RenderUtil) {
+        if !render_util.is_layer_visible(self.render_order.get_layer()) {
+            return;
+        }
+        let rect = self.layout.resolve(render_util.get_window_size());
+        let transform = Transform::from_translation(rect.get_min().extend(0.0));
+        self.content.render(&render_util.with_render_order(self.render_order).with_parent_transform(&transform))
+    }
+
+    fn update(&mut self, update_context: &UpdateContext) {
+        self.content.update(update_context)
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
+        self.content.handle_event(event, context, update_context)
+    }
+}
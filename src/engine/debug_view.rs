@@ -0,0 +1,195 @@
+use std::rc::Rc;
+
+use crate::engine::api::colour::{Colour, WHITE};
+use crate::engine::api::console::Console;
+use crate::engine::api::debug_overlay::{DebugOverlay, FRAME_TIME_HISTORY};
+use crate::engine::api::drawable::Drawable;
+use crate::engine::api::maths::rectangle::Rectangle;
+use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
+use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::resource_manager::ResourceManager;
+use crate::engine::api::text_game_object::{TEXT_SCALE, TextGameObject};
+use crate::engine::api::ui::{Anchor, UiLayout, UiLength};
+use crate::engine::rendering::material::Material;
+use crate::engine::rendering::shape_drawing_component::ShapeDrawingComponent;
+use crate::engine::resources::fonts::SizedFont;
+
+// all sizes in window pixels
+const LINE_HEIGHT: f32 = 18.0;
+const PADDING: f32 = 8.0;
+const PANEL_WIDTH: f32 = 460.0;
+// font glyphs are drawn at quarter of their pixel size
+const DEBUG_TEXT_SCALE: f32 = 0.25 / TEXT_SCALE;
+const GRAPH_HEIGHT: f32 = 60.0;
+const GRAPH_BAR_WIDTH: f32 = (PANEL_WIDTH - 2.0 * PADDING) / FRAME_TIME_HISTORY as f32;
+// frame times above this are cut at the top of the graph
+const GRAPH_MAX_MILLIS: f32 = 50.0;
+// frames slower than these are drawn in warning colours, 60 and 30 fps
+const GRAPH_SLOW_MILLIS: f32 = 1000.0 / 60.0;
+const GRAPH_VERY_SLOW_MILLIS: f32 = 1000.0 / 30.0;
+const CONSOLE_OUTPUT_LINES: usize = 12;
+
+// overlay ships its own assets so that it does not depend on any game
+const SHAPE_SHADER: &str = "engine/shaders/debug/shape";
+const TEXT_SHADER: &str = "engine/shaders/debug/text";
+const FONT: &str = "engine/fonts/go3v2.ttf";
+
+const BACKGROUND_COLOUR: Colour = Colour::new_f(0.0, 0.0, 0.0, 0.7);
+const GRAPH_FAST_COLOUR: Colour = Colour::new_f(0.2, 0.8, 0.2, 1.0);
+const GRAPH_SLOW_COLOUR: Colour = Colour::new_f(0.9, 0.8, 0.1, 1.0);
+const GRAPH_VERY_SLOW_COLOUR: Colour = Colour::new_f(0.9, 0.2, 0.1, 1.0);
+const CONSOLE_INPUT_COLOUR: Colour = Colour::new_f(1.0, 0.85, 0.0, 1.0);
+
+/**
+Draws debug overlay and developer console on top of the frame, engine renders it with screen space camera
+after everything else. Overlay panel sits in top right corner and console drops down from the top of the window.
+ */
+pub struct DebugView {
+    debug_overlay: Rc<DebugOverlay>,
+    console: Rc<Console>,
+    text_material: Material,
+    font: Rc<SizedFont>,
+    // reused between frames, grows to the highest number of lines drawn at once
+    texts: Vec<TextGameObject>,
+    // text colour is baked into glyphs when text is created, so input line has text of its own
+    console_input: TextGameObject,
+    // unit square scaled to panel that is drawn
+    background: Rectangle<ColoredVertexDataLayout>,
+    frame_graph: ShapeDrawingComponent<ColoredVertexDataLayout>,
+    graph_material: Material,
+}
+
+impl DebugView {
+    pub fn new(debug_overlay: Rc<DebugOverlay>, console: Rc<Console>, resource_manager: Rc<dyn ResourceManager>) -> DebugView {
+        let shape_material = resource_manager.fetch_shader_material(SHAPE_SHADER);
+        let text_material = resource_manager.fetch_shader_material(TEXT_SHADER);
+        let font = resource_manager.fetch_font(FONT);
+        DebugView {
+            debug_overlay,
+            console,
+            console_input: DebugView::create_text(&font, &text_material, CONSOLE_INPUT_COLOUR),
+            text_material,
+            font,
+            texts: vec!(),
+            background: Rectangle::new_colored(&glam::vec3(0.0, 0.0, 0.0), &glam::vec2(1.0, 1.0), shape_material.clone(), BACKGROUND_COLOUR),
+            frame_graph: ShapeDrawingComponent::new(
+                &DebugView::create_graph_vertices(&[]),
+                &DebugView::create_graph_indices(),
+                None,
+            ),
+            graph_material: shape_material,
+        }
+    }
+
+    fn create_text(font: &Rc<SizedFont>, material: &Material, colour: Colour) -> TextGameObject {
+        let mut text = TextGameObject::new(Rc::clone(font), "", glam::vec3(0.0, 0.0, 0.0), material.clone(), colour);
+        text.get_transform_mut().set_scale(glam::Vec3::splat(DEBUG_TEXT_SCALE));
+        text
+    }
+
+    // bars go from left to right, bars of frames not recorded yet are empty
+    fn create_graph_vertices(frame_times_millis: &[f32]) -> Vec<ColoredVertexDataLayout> {
+        let mut vertices = Vec::with_capacity(FRAME_TIME_HISTORY * 4);
+        for bar_idx in 0..FRAME_TIME_HISTORY {
+            let millis = frame_times_millis.get(bar_idx).cloned().unwrap_or(0.0);
+            let colour = match millis {
+                millis if millis > GRAPH_VERY_SLOW_MILLIS => GRAPH_VERY_SLOW_COLOUR,
+                millis if millis > GRAPH_SLOW_MILLIS => GRAPH_SLOW_COLOUR,
+                _ => GRAPH_FAST_COLOUR
+            };
+            let height = (millis / GRAPH_MAX_MILLIS).min(1.0) * GRAPH_HEIGHT;
+            let left = bar_idx as f32 * GRAPH_BAR_WIDTH;
+            // bars are a pixel apart
+            let right = left + (GRAPH_BAR_WIDTH - 1.0).max(1.0);
+            vertices.push(ColoredVertexDataLayout { pos: (right, height, 0.0).into(), clr: colour.clone().into() });
+            vertices.push(ColoredVertexDataLayout { pos: (right, 0.0, 0.0).into(), clr: colour.clone().into() });
+            vertices.push(ColoredVertexDataLayout { pos: (left, 0.0, 0.0).into(), clr: colour.clone().into() });
+            vertices.push(ColoredVertexDataLayout { pos: (left, height, 0.0).into(), clr: colour.into() });
+        }
+        vertices
+    }
+
+    fn create_graph_indices() -> Vec<i32> {
+        (0..FRAME_TIME_HISTORY as i32)
+            .flat_map(|bar_idx| {
+                let first = bar_idx * 4;
+                vec![first, first + 1, first + 3, first + 1, first + 2, first + 3]
+            })
+            .collect()
+    }
+
+    fn render_background(&mut self, min: glam::Vec2, size: glam::Vec2, render_util: &RenderUtil) {
+        let transform = self.background.get_transform_mut();
+        transform.set_translation(min.extend(0.0));
+        transform.set_scale(size.extend(1.0));
+        self.background.render(render_util);
+    }
+
+    // lines are drawn top to bottom below given point, texts of pool starting at first text idx are used for them
+    fn render_lines(&mut self, lines: Vec<String>, first_text_idx: usize, top_left: glam::Vec2, render_util: &RenderUtil) {
+        while self.texts.len() < first_text_idx + lines.len() {
+            self.texts.push(DebugView::create_text(&self.font, &self.text_material, WHITE));
+        }
+        for (line_idx, line) in lines.into_iter().enumerate() {
+            let text = &mut self.texts[first_text_idx + line_idx];
+            text.set_text(line);
+            DebugView::render_text_line(text, line_idx, top_left, render_util);
+        }
+    }
+
+    fn render_text_line(text: &mut TextGameObject, line_idx: usize, top_left: glam::Vec2, render_util: &RenderUtil) {
+        text.get_transform_mut().set_translation(glam::vec3(top_left.x, top_left.y - (line_idx + 1) as f32 * LINE_HEIGHT, 0.0));
+        text.render(render_util);
+    }
+
+    // returns number of texts of pool used
+    fn render_overlay(&mut self, render_util: &RenderUtil) -> usize {
+        let lines = self.debug_overlay.get_lines();
+        let num_lines = lines.len();
+        let panel_height = lines.len() as f32 * LINE_HEIGHT + GRAPH_HEIGHT + 3.0 * PADDING;
+        let rect = UiLayout::new(Anchor::TOP_RIGHT, UiLength::PIXELS(PANEL_WIDTH), UiLength::PIXELS(panel_height))
+            .with_offset(UiLength::PIXELS(PADDING), UiLength::PIXELS(PADDING))
+            .resolve(render_util.get_window_size());
+        self.render_background(*rect.get_min(), *rect.get_max() - *rect.get_min(), render_util);
+        self.render_lines(lines, 0, glam::vec2(rect.get_min().x + PADDING, rect.get_max().y - PADDING), render_util);
+
+        let frame_times_millis: Vec<f32> = self.debug_overlay.get_frame_times().iter()
+            .map(|frame_time| frame_time.as_secs_f32() * 1000.0)
+            .collect();
+        self.frame_graph.bind_data(&DebugView::create_graph_vertices(&frame_times_millis));
+        let graph_origin = *rect.get_min() + glam::vec2(PADDING, PADDING);
+        let world_matrix = Transform::from_translation(graph_origin.extend(0.0)).get_local_matrix();
+        self.frame_graph.render((FRAME_TIME_HISTORY * 6) as i32, gl::TRIANGLES, world_matrix, render_util, &mut self.graph_material);
+        num_lines
+    }
+
+    fn render_console(&mut self, first_text_idx: usize, render_util: &RenderUtil) {
+        let output = self.console.get_output(CONSOLE_OUTPUT_LINES);
+        // output is aligned to the bottom, right above input line
+        let first_output_line = CONSOLE_OUTPUT_LINES - output.len();
+
+        let console_height = (CONSOLE_OUTPUT_LINES + 1) as f32 * LINE_HEIGHT + 2.0 * PADDING;
+        let rect = UiLayout::new(Anchor::TOP_LEFT, UiLength::PERCENT(100.0), UiLength::PIXELS(console_height))
+            .resolve(render_util.get_window_size());
+        self.render_background(*rect.get_min(), *rect.get_max() - *rect.get_min(), render_util);
+        let top_left = glam::vec2(rect.get_min().x + PADDING, rect.get_max().y - PADDING);
+        self.render_lines(output, first_text_idx, top_left - glam::vec2(0.0, first_output_line as f32 * LINE_HEIGHT), render_util);
+        self.console_input.set_text(format!("> {}_", self.console.get_input_line()));
+        DebugView::render_text_line(&mut self.console_input, CONSOLE_OUTPUT_LINES, top_left, render_util);
+    }
+}
+
+impl Drawable for DebugView {
+    // overlay is drawn first and console over it
+    fn render(&mut self, render_util: &RenderUtil) {
+        let num_overlay_texts = match self.debug_overlay.is_visible() {
+            true => self.render_overlay(render_util),
+            false => 0
+        };
+        if self.console.is_open() {
+            self.render_console(num_overlay_texts, &render_util.with_render_order(render_util.get_render_order().offset_by(1)));
+        }
+    }
+}
@@ -1,49 +1,135 @@
 use std::borrow::BorrowMut;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use sdl2::EventPump;
-use sdl2::mouse::MouseButton;
+use sdl2::keyboard::Keycode;
 
 use crate::engine::api::audio::AudioManager;
 use crate::engine::api::colour::WHITE;
+use crate::engine::api::console::Console;
 use crate::engine::api::coordinate_system::CoordinateSystem;
+use crate::engine::api::debug_overlay::DebugOverlay;
 use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::ecs::systems::{System, SystemSchedule, SystemStage};
+use crate::engine::api::ecs::world::World;
 use crate::engine::api::engine_utilities::EngineUtilities;
-use crate::engine::api::game_api::GameController;
+use crate::engine::api::game_api::{CameraConfig, GameController};
+use crate::engine::api::input::InputManager;
+use crate::engine::api::post_processing::PostProcessing;
+use crate::engine::api::random::RandomGenerator;
+use crate::engine::api::resource_manager::ResourceManager;
 use crate::engine::api::maths::point::Point;
 use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
+use crate::engine::debug_view::DebugView;
 use crate::engine::opengl_context::OpenglContext;
 use crate::engine::renderer;
 use crate::engine::renderer::Renderer;
+use crate::engine::replay::{InputRecorder, InputRecording, ReplayError};
 use crate::engine::resources::catching_resource_manager::CachingResourceManager;
+use crate::engine::timestep::{FixedTimestepAccumulator, Timestep};
 use crate::games_root::GamesRoot;
 
-const ENGINE_FEATURES_ON: bool = true;
+const SCREENSHOTS_DIR: &str = "screenshots";
+const INPUT_BINDINGS_CONFIG: &str = "engine/input/bindings.cfg";
+// config as shipped, used when the copy next to executable was edited into something that does not load
+const DEFAULT_INPUT_BINDINGS: &str = include_str!("../assets/engine/input/bindings.cfg");
 
 pub struct Engine {
     // todo: this should not be concrete implementation
     // todo: but Box<dyn Drawable + GameController> does not work
     game: Option<GamesRoot>,
     coordinate_system: CoordinateSystem,
-    // one day generalize to engine overlay
+    // drawn over the game, shows debug overlay and console
+    debug_view: DebugView,
     event_pump: EventPump,
     renderer: Renderer,
     opengl_context: OpenglContext,
     engine_utilities: Rc<EngineUtilities>,
-
+    // engine stops on its own after rendering that many frames, used when running headless
+    frame_limit: Option<u32>,
+    timestep: Timestep,
+    // entities of the world are updated after games and drawn on top of them
+    systems: SystemSchedule,
+    // none if sdl could not initialize game controllers, gamepad bindings just do nothing then
+    controller_subsystem: Option<sdl2::GameControllerSubsystem>,
+    // controllers must be kept open for sdl to send their events
+    open_controllers: Vec<sdl2::controller::GameController>,
+    recorder: Option<InputRecorder>,
+    // when set frames, their delta times and events come from recording instead of real time and window
+    replay: Option<InputRecording>,
 }
 
 impl Engine {
     pub fn new() -> Engine {
-        let opengl_context = OpenglContext::init();
+        Engine::with_context(OpenglContext::init(), None, RandomGenerator::from_current_time())
+    }
+
+    // engine that renders offscreen with given resolution and exits after num_frames frames
+    pub fn new_headless(width: u32, height: u32, num_frames: u32) -> Engine {
+        Engine::with_context(OpenglContext::init_headless(width, height), Some(num_frames), RandomGenerator::from_current_time())
+    }
+
+    // headless engine that plays back recorded input and exits when recording ends
+    // game must be created after this so that it gets the same random numbers as during recording
+    // replay renders at window size of recording and runs with its timestep, resolution or timestep other than that is rejected
+    pub fn new_replay(recording_path: &Path, resolution: Option<(u32, u32)>, timestep: Option<Timestep>) -> Result<Engine, ReplayError> {
+        let recording = InputRecording::load(recording_path)?;
+        let (width, height) = recording.get_window_size();
+        if let Some(requested) = resolution {
+            if requested != (width, height) {
+                return Err(ReplayError::WindowSizeMismatch { recorded: (width, height), requested });
+            }
+        }
+        if let Some(requested) = timestep {
+            if requested != *recording.get_timestep() {
+                return Err(ReplayError::TimestepMismatch { recorded: recording.get_timestep().clone(), requested });
+            }
+        }
+        let mut engine = Engine::with_context(
+            OpenglContext::init_headless(width, height),
+            Some(recording.get_num_frames() as u32),
+            RandomGenerator::new(recording.get_seed()),
+        );
+        engine.timestep = recording.get_timestep().clone();
+        engine.replay = Some(recording);
+        Ok(engine)
+    }
+
+    fn with_context(opengl_context: OpenglContext, frame_limit: Option<u32>, random: RandomGenerator) -> Engine {
 
         let mut resource_manager = Rc::new(CachingResourceManager::new());
         let audio_manager = Rc::new(AudioManager::new());
-        let engine_utilities = Rc::new(EngineUtilities::new(resource_manager, audio_manager));
+        let post_processing = Rc::new(PostProcessing::new());
+        post_processing.register_built_in_passes(&*resource_manager);
+        let input_manager = Rc::new(InputManager::new());
+        let world = Rc::new(World::new());
+        if let Err(err) = input_manager.load_bindings(resource_manager.read_file_lines(INPUT_BINDINGS_CONFIG).map(|line| line.unwrap())) {
+            println!("Could not load input bindings, using default ones: {:?}", err);
+            input_manager.load_bindings(DEFAULT_INPUT_BINDINGS.lines().map(String::from)).expect("Could not load default input bindings");
+        }
+        let console = Rc::new(Console::new());
+        console.register_command("wireframe", "wireframe - toggles drawing polygon outlines");
+        console.register_command("reload_assets", "reload_assets - drops cached resources and reloads input bindings");
+        console.register_command("overlay", "overlay - toggles debug overlay, same as F3");
+        let debug_overlay = Rc::new(DebugOverlay::new());
+        let debug_view = DebugView::new(Rc::clone(&debug_overlay), Rc::clone(&console), resource_manager.clone());
+        let engine_utilities = Rc::new(EngineUtilities::new(
+            resource_manager,
+            audio_manager,
+            Rc::clone(&post_processing),
+            input_manager,
+            Rc::new(random),
+            Rc::clone(&world),
+            console,
+            debug_overlay,
+        ));
+        let controller_subsystem = opengl_context.sdl.game_controller().ok();
 
         let mut event_pump = opengl_context.sdl.event_pump().unwrap();
-        let mut renderer = renderer::Renderer::new(&opengl_context);
+        let mut renderer = renderer::Renderer::new(&opengl_context, post_processing);
 
         let shader_material = engine_utilities.get_resource_manager().fetch_shader_material("chess/shaders/triangle");
 
@@ -52,17 +138,52 @@ impl Engine {
         Engine {
             game: None,
             coordinate_system,
+            debug_view,
             opengl_context,
             event_pump,
             renderer,
             engine_utilities,
+            frame_limit,
+            timestep: Timestep::VARIABLE,
+            systems: SystemSchedule::new(world),
+            controller_subsystem,
+            open_controllers: vec!(),
+            recorder: None,
+            replay: None,
         }
     }
 
+    // records input of every frame until engine stops, recording can be played back with `new_replay`
+    pub fn start_recording(&mut self, path: &Path) -> Result<(), ReplayError> {
+        self.recorder = Some(InputRecorder::create(
+            path,
+            self.engine_utilities.get_random().get_seed(),
+            self.opengl_context.get_window_size(),
+            &self.timestep,
+        )?);
+        Ok(())
+    }
+
     pub fn set_game(&mut self, games_root: GamesRoot) {
         self.game = Some(games_root)
     }
 
+    // must be set before engine is started and before recording starts, recording stores it
+    // replay takes timestep from recording
+    pub fn set_timestep(&mut self, timestep: Timestep) {
+        if self.recorder.is_some() {
+            panic!("Timestep must be set before recording starts");
+        }
+        if self.replay.is_some() {
+            panic!("Replay runs with timestep of its recording");
+        }
+        self.timestep = timestep
+    }
+
+    pub fn add_system(&mut self, stage: SystemStage, system: Box<dyn System>) {
+        self.systems.add_system(stage, system)
+    }
+
     pub fn get_engine_utilities(&self) -> Rc<EngineUtilities> {
         Rc::clone(&self.engine_utilities)
     }
@@ -72,56 +193,213 @@ impl Engine {
             None => { panic!("Attempting to start game in engine, but no game was provided") }
             Some(game) => {
                 let mut time = Instant::now();
+                let mut frames_rendered: u32 = 0;
+                let mut fixed_timestep = match &self.timestep {
+                    Timestep::VARIABLE => None,
+                    Timestep::FIXED { config } => Some(FixedTimestepAccumulator::new(config.clone()))
+                };
                 'main: loop {
-                    let mouse_state = self.event_pump.mouse_state();
-                    let sdl_pos = glam::vec2(mouse_state.x().clone() as f32, mouse_state.y().clone() as f32);
+                    if let Some(frame_limit) = self.frame_limit {
+                        if frames_rendered >= frame_limit {
+                            break 'main;
+                        }
+                    }
+
+                    // frame limit of replay is number of recorded frames so there is always next one
+                    let mut replayed_frame = self.replay.as_mut().map(|replay| replay.next_frame().unwrap());
+
+                    let sdl_pos = match &replayed_frame {
+                        Some(frame) => frame.get_mouse_position().clone(),
+                        None => {
+                            let mouse_state = self.event_pump.mouse_state();
+                            glam::vec2(mouse_state.x().clone() as f32, mouse_state.y().clone() as f32)
+                        }
+                    };
 
-                    let camera_config = game.get_camera_config();
                     let now = Instant::now();
+                    let frame_time = match (&replayed_frame, &fixed_timestep) {
+                        (Some(frame), _) => frame.get_delta_time().clone(),
+                        // headless frames are not bound to real time so that runs are reproducible
+                        (None, Some(accumulator)) if self.opengl_context.is_headless() => accumulator.get_config().get_tick_duration().clone(),
+                        _ => now.duration_since(time)
+                    };
+                    time = now;
+
+                    if let Some(recorder) = &mut self.recorder {
+                        recorder.record_frame(frame_time, sdl_pos).expect("Could not write input recording");
+                    }
+                    let debug_overlay = self.engine_utilities.get_debug_overlay();
+                    debug_overlay.record_frame_time(frame_time);
+
                     let update_context = UpdateContext::new(
                         sdl_pos,
-                        camera_config,
+                        game.get_camera_config(),
                         Rc::clone(&self.engine_utilities),
-                        now.duration_since(time)
+                        frame_time,
                     );
-                    time = now;
-
-                    game.update(&update_context);
-
-                    for event in self.event_pump.poll_iter() {
-                        if ENGINE_FEATURES_ON {
-                            match event {
-                                sdl2::event::Event::Quit { .. } => break 'main,
-                                sdl2::event::Event::Window {
-                                    win_event: sdl2::event::WindowEvent::Resized(w, h),
-                                    ..
-                                } => {
-                                    self.renderer.resize_viewport(w, h);
-                                }
-                                sdl2::event::Event::MouseButtonDown {
-                                    mouse_btn, ..
-                                } => {
-                                    if mouse_btn == MouseButton::Left {
-                                        match self.opengl_context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), update_context.get_camera_config()) {
-                                            None => {
-                                                println!("Could not find mouse and z0 crosspoint")
-                                            }
-                                            Some(pos) => {
-                                                println!("Mouse position is {:?}", pos)
-                                            }
-                                        }
+                    let input_manager = self.engine_utilities.get_input_manager();
+                    let console = self.engine_utilities.get_console();
+                    // events come first so that actions they press are seen by update of this frame
+                    let events: Vec<sdl2::event::Event> = match &mut replayed_frame {
+                        Some(frame) => {
+                            // window of replay is hidden, whatever it produces is not part of recording
+                            self.event_pump.poll_iter().for_each(drop);
+                            frame.take_events()
+                        }
+                        None => self.event_pump.poll_iter().collect()
+                    };
+                    for event in events {
+                        if let Some(recorder) = &mut self.recorder {
+                            recorder.record_event(&event).expect("Could not write input recording");
+                        }
+                        match event {
+                            sdl2::event::Event::Quit { .. } => break 'main,
+                            sdl2::event::Event::Window {
+                                win_event: sdl2::event::WindowEvent::Resized(w, h),
+                                ..
+                            } => {
+                                self.opengl_context.resize_offscreen_framebuffer(w as u32, h as u32);
+                                self.renderer.resize_viewport(w, h);
+                            }
+                            sdl2::event::Event::KeyDown {
+                                keycode: Some(Keycode::F12), ..
+                            } => {
+                                self.renderer.request_screenshot(&Engine::next_screenshot_path());
+                            }
+                            sdl2::event::Event::KeyDown {
+                                keycode: Some(Keycode::F3), ..
+                            } => {
+                                debug_overlay.toggle();
+                            }
+                            sdl2::event::Event::KeyDown {
+                                keycode: Some(Keycode::Backquote), repeat: false, ..
+                            } => {
+                                console.toggle();
+                                continue;
+                            }
+                            sdl2::event::Event::ControllerDeviceAdded { which, .. } => {
+                                match self.controller_subsystem.as_ref().map(|subsystem| subsystem.open(which)) {
+                                    Some(Ok(controller)) => {
+                                        println!("Opened game controller {:?}", controller.name());
+                                        self.open_controllers.push(controller);
                                     }
+                                    Some(Err(err)) => println!("Could not open game controller {}: {}", which, err),
+                                    None => {}
                                 }
-                                _ => {}
                             }
+                            _ => {}
+                        }
+
+                        // open console takes keyboard from game
+                        if console.handle_event(&event) {
+                            continue;
+                        }
+
+                        input_manager.handle_event(&event);
+                        game.handle_event(&event, &self.opengl_context, &update_context);
+                    }
+
+                    match &mut fixed_timestep {
+                        None => {
+                            debug_overlay.clear_lines();
+                            game.update(&update_context);
+                            self.systems.update(&update_context);
+                            input_manager.finish_update();
+                            Engine::handle_console_commands(&self.engine_utilities, &mut self.renderer);
+                            console.finish_update();
+                        }
+                        Some(accumulator) => {
+                            let tick_duration = accumulator.get_config().get_tick_duration().clone();
+                            for _ in 0..accumulator.advance(frame_time) {
+                                let tick_context = UpdateContext::new(
+                                    sdl_pos,
+                                    game.get_camera_config(),
+                                    Rc::clone(&self.engine_utilities),
+                                    tick_duration,
+                                );
+                                debug_overlay.clear_lines();
+                                game.update(&tick_context);
+                                self.systems.update(&tick_context);
+                                input_manager.finish_update();
+                                Engine::handle_console_commands(&self.engine_utilities, &mut self.renderer);
+                                console.finish_update();
+                            }
+                            self.renderer.set_interpolation_alpha(accumulator.get_interpolation_alpha());
                         }
+                    }
 
-                        game.handle_event(&event, &self.opengl_context, &update_context)
+                    if debug_overlay.is_visible() {
+                        debug_overlay.set_engine_lines(Engine::get_debug_overlay_lines(&self.engine_utilities, &self.renderer, &self.opengl_context, &sdl_pos, &game.get_camera_config()));
                     }
-                    let camera_config = game.get_camera_config();
-                    self.renderer.render(&mut [game, /*&mut self.coordinate_system, &mut point*/], &camera_config, &self.opengl_context)
+                    let cameras = game.get_cameras();
+                    self.renderer.render(
+                        &mut [game, &mut self.systems, /*&mut self.coordinate_system, &mut point*/],
+                        &cameras,
+                        &mut [&mut self.debug_view],
+                        &self.opengl_context,
+                    );
+                    frames_rendered += 1;
+                }
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.flush().expect("Could not write input recording");
                 }
             }
         }
     }
+
+    // commands of games are taken by them during update, these are handled by engine
+    fn handle_console_commands(engine_utilities: &EngineUtilities, renderer: &mut Renderer) {
+        let console = engine_utilities.get_console();
+        for _ in console.take_commands("wireframe") {
+            renderer.set_wireframe(!renderer.is_wireframe());
+            console.print(format!("Wireframe {}", if renderer.is_wireframe() { "on" } else { "off" }));
+        }
+        for _ in console.take_commands("overlay") {
+            engine_utilities.get_debug_overlay().toggle();
+        }
+        for _ in console.take_commands("reload_assets") {
+            // objects keep resources they already fetched, only newly fetched ones are loaded again
+            let resource_manager = engine_utilities.get_resource_manager();
+            let num_dropped = resource_manager.clear_caches();
+            let input_manager = engine_utilities.get_input_manager();
+            match input_manager.reload_bindings(resource_manager.read_file_lines(INPUT_BINDINGS_CONFIG).map(|line| line.unwrap())) {
+                Ok(_) => console.print(format!("Dropped {} cached resources and reloaded input bindings", num_dropped)),
+                Err(err) => console.print(format!("Dropped {} cached resources, kept input bindings that could not be reloaded: {:?}", num_dropped, err))
+            }
+        }
+    }
+
+    // shown above lines of game, computed once per rendered frame
+    fn get_debug_overlay_lines(engine_utilities: &EngineUtilities, renderer: &Renderer, context: &OpenglContext, sdl_pos: &glam::Vec2, camera_config: &CameraConfig) -> Vec<String> {
+        let debug_overlay = engine_utilities.get_debug_overlay();
+        let mut lines = vec!();
+        if let Some(frame_time) = debug_overlay.get_average_frame_time() {
+            lines.push(format!("FPS {:.0}  frame time {:.2} ms", 1.0 / frame_time.as_secs_f32(), frame_time.as_secs_f32() * 1000.0));
+        }
+        let render_stats = renderer.get_last_frame_stats();
+        lines.push(format!("Draw calls {}  batched {}  batched vertices {}",
+                                       render_stats.draw_calls, render_stats.batched_draw_calls, render_stats.batched_vertices));
+        let cache_stats = engine_utilities.get_resource_manager().get_cache_stats();
+        lines.push(format!("Cached textures {}  atlases {}  shaders {}  audio {}  fonts {}",
+                                       cache_stats.textures, cache_stats.atlases, cache_stats.shaders, cache_stats.audio, cache_stats.fonts));
+        lines.push(match context.sdl_space_to_world_space_at_z0(sdl_pos, camera_config) {
+            None => String::from("Mouse is not over z == 0 plane"),
+            Some(pos) => format!("Mouse world position ({:.2}, {:.2})", pos.x, pos.y)
+        });
+        let eye = camera_config.get_eye_position();
+        let look_at = camera_config.get_look_at();
+        lines.push(format!("Camera eye ({:.2}, {:.2}, {:.2})  look at ({:.2}, {:.2}, {:.2})", eye.x, eye.y, eye.z, look_at.x, look_at.y, look_at.z));
+        lines.push(format!("Projection {:?}", camera_config.get_projection()));
+        lines.push(format!("Viewport {:?}", camera_config.get_viewport()));
+        if renderer.is_wireframe() {
+            lines.push(String::from("Wireframe on"));
+        }
+        lines
+    }
+
+    fn next_screenshot_path() -> PathBuf {
+        fs::create_dir_all(SCREENSHOTS_DIR).expect("Could not create screenshots directory");
+        let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+        Path::new(SCREENSHOTS_DIR).join(format!("screenshot_{}.png", millis))
+    }
 }
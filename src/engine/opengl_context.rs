@@ -1,17 +1,35 @@
 use std::ops::{Add, Mul};
 
 use crate::engine::api::game_api::CameraConfig;
+use crate::engine::rendering::framebuffer::OffscreenFramebuffer;
+
+const WINDOW_WIDTH: u32 = 1920;
+const WINDOW_HEIGHT: u32 = 1080;
 
 pub struct OpenglContext {
     pub sdl: sdl2::Sdl,
     pub window: sdl2::video::Window,
     // todo: make those private
     gl_context: sdl2::video::GLContext,
+    // when running headless everything is rendered here instead of window
+    offscreen_framebuffer: Option<OffscreenFramebuffer>,
 }
 
 
 impl OpenglContext {
     pub fn init() -> OpenglContext {
+        OpenglContext::create(WINDOW_WIDTH, WINDOW_HEIGHT, false)
+    }
+
+    // creates context without visible window, rendering goes to offscreen framebuffer of given resolution
+    // sdl "offscreen" video driver is used so that no display is needed,
+    // it can be overridden with SDL_VIDEODRIVER env variable
+    pub fn init_headless(width: u32, height: u32) -> OpenglContext {
+        sdl2::hint::set("SDL_VIDEODRIVER", "offscreen");
+        OpenglContext::create(width, height, true)
+    }
+
+    fn create(width: u32, height: u32, headless: bool) -> OpenglContext {
         let sdl = sdl2::init().unwrap();
         let video_subsystem = sdl.video().unwrap();
 
@@ -20,10 +38,14 @@ impl OpenglContext {
         gl_attr.set_context_version(4, 5);
 
         // todo: can window be moved to renderer?
-        let window = video_subsystem
-            .window("Game", 1920, 1080)
-            .opengl()
-            .resizable()
+        let mut window_builder = video_subsystem.window("Game", width, height);
+        window_builder.opengl();
+        if headless {
+            window_builder.hidden();
+        } else {
+            window_builder.resizable();
+        }
+        let window = window_builder
             .build()
             .unwrap();
 
@@ -34,36 +56,97 @@ impl OpenglContext {
             // todo is this needed?
             gl::Enable(gl::TEXTURE_2D);
 
-            // todo: to enable this I would have to alternate z offset of pieces and chessboard
-            // todo: to do so I would need to transfrom sdl point via camera transform to world space
-            // gl::Enable(gl::DEPTH_TEST);
+            // draws at equal depth pass so flat scenes keep draw order, only opaque geometry writes depth
+            gl::Enable(gl::DEPTH_TEST);
+            gl::DepthFunc(gl::LEQUAL);
 
             // todo: this should be moved to texture rendering code and disabled afterwards
             gl::Enable(gl::BLEND);
             gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
         }
 
+        let offscreen_framebuffer = if headless {
+            let framebuffer = OffscreenFramebuffer::new(width, height).unwrap();
+            framebuffer.bind();
+            Some(framebuffer)
+        } else {
+            None
+        };
+
         OpenglContext {
             sdl,
             window,
             gl_context,
+            offscreen_framebuffer,
+        }
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.offscreen_framebuffer.is_some()
+    }
+
+    // size of surface that is rendered to, in headless mode this is offscreen framebuffer resolution
+    pub fn get_window_size(&self) -> (u32, u32) {
+        match &self.offscreen_framebuffer {
+            None => self.window.size(),
+            Some(framebuffer) => framebuffer.get_size()
+        }
+    }
+
+    // window resizes itself, headless surface follows replayed resizes so that it matches recorded session
+    pub fn resize_offscreen_framebuffer(&mut self, width: u32, height: u32) {
+        if self.offscreen_framebuffer.is_some() {
+            // old framebuffer is deleted after new one is created so that bound framebuffer is never deleted one
+            let framebuffer = OffscreenFramebuffer::new(width, height).unwrap();
+            framebuffer.bind();
+            self.offscreen_framebuffer = Some(framebuffer);
+        }
+    }
+
+    pub fn present(&self) {
+        match &self.offscreen_framebuffer {
+            None => self.window.gl_swap_window(),
+            Some(_) => unsafe { gl::Finish() }
         }
     }
 
     pub fn sdl_window_to_opengl_space(&self, pos: &glam::Vec2) -> glam::Vec2 {
-        let win_size = self.window.size();
+        let win_size = self.get_window_size();
         return glam::vec2(
             2.0 * pos.x as f32 / win_size.0 as f32 - 1.0,
             -(2.0 * pos.y as f32 / win_size.1 as f32 - 1.0),
         );
     }
 
+    // like sdl_window_to_opengl_space but relative to viewport rect of camera, none when position is outside of it
+    pub fn sdl_window_to_viewport_space(&self, pos: &glam::Vec2, camera_config: &CameraConfig) -> Option<glam::Vec2> {
+        let win_size = self.get_window_size();
+        let (x, y, w, h) = camera_config.get_viewport().to_pixels(glam::vec2(win_size.0 as f32, win_size.1 as f32));
+        // sdl y axis goes down from top of window, opengl one goes up from bottom
+        let opengl_pos = glam::vec2(pos.x, win_size.1 as f32 - pos.y);
+        if opengl_pos.x < x as f32 || opengl_pos.x > (x + w) as f32 || opengl_pos.y < y as f32 || opengl_pos.y > (y + h) as f32 {
+            return None;
+        }
+        Some(glam::vec2(
+            2.0 * (opengl_pos.x - x as f32) / w as f32 - 1.0,
+            2.0 * (opengl_pos.y - y as f32) / h as f32 - 1.0,
+        ))
+    }
+
+    // pixels from bottom left corner of camera viewport, matches coordinates of screen space camera
+    pub fn sdl_window_to_viewport_pixels(&self, pos: &glam::Vec2, camera_config: &CameraConfig) -> Option<glam::Vec2> {
+        let win_size = self.get_window_size();
+        let (_, _, w, h) = camera_config.get_viewport().to_pixels(glam::vec2(win_size.0 as f32, win_size.1 as f32));
+        self.sdl_window_to_viewport_space(pos, camera_config)
+            .map(|viewport_pos| (viewport_pos + glam::vec2(1.0, 1.0)) / 2.0 * glam::vec2(w as f32, h as f32))
+    }
+
     pub fn engine_to_opengl_space(&self, pos: &(i32, i32, i32)) -> (f32, f32, f32) {
         self.engine_to_opengl_space_f(&(pos.0 as f32, pos.1 as f32, pos.2 as f32))
     }
 
     pub fn engine_to_opengl_space_f(&self, pos: &(f32, f32, f32)) -> (f32, f32, f32) {
-        let win_size = self.window.size();
+        let win_size = self.get_window_size();
         return (
             2.0 * pos.0 / win_size.0 as f32 - 1.0,
             2.0 * pos.1 / win_size.1 as f32 - 1.0,
@@ -71,54 +154,43 @@ impl OpenglContext {
         );
     }
 
-    // based on https://stackoverflow.com/questions/7692988/opengl-math-projecting-screen-space-to-world-space-coords
-    // and https://antongerdelan.net/opengl/raycasting.html
+    // mouse ray goes from near to far plane, for perspective it starts near the eye and for orthographic
+    // projection all rays are parallel to view direction
+    // mouse outside of camera viewport rect does not point at anything this camera shows
     pub fn sdl_space_to_world_space_at_z0(&self, pos: &glam::Vec2, camera_config: &CameraConfig) -> Option<glam::Vec3> {
-        // 1. screen mouse coords to normalized space
-        let opengl_mouse_pos = self.sdl_window_to_opengl_space(pos);
-        let clip_coords = glam::vec4(opengl_mouse_pos.x as f32, opengl_mouse_pos.y as f32, -1.0, 1.0);
-
-        // 2. Calculate ray
-        let eye_coords = OpenglContext::get_eye_coords(clip_coords, camera_config, self.get_aspect_ratio());
-        let world_coords = OpenglContext::get_world_coords(eye_coords, camera_config);
-        let ray_direction = world_coords.normalize();
+        // 1. screen mouse coords to normalized device coordinates of camera viewport
+        let viewport_mouse_pos = match self.sdl_window_to_viewport_space(pos, camera_config) {
+            None => return None,
+            Some(viewport_mouse_pos) => viewport_mouse_pos
+        };
+        let window_size = self.get_window_size();
+        let (_, _, viewport_width, viewport_height) = camera_config.get_viewport()
+            .to_pixels(glam::vec2(window_size.0 as f32, window_size.1 as f32));
+
+        // 2. unproject mouse position at near and far plane to get ray
+        let view_projection = camera_config.get_projection_matrix(glam::vec2(viewport_width as f32, viewport_height as f32))
+            * camera_config.get_view_matrix();
+        let inverted_view_projection = view_projection.inverse();
+        let near_point = inverted_view_projection.project_point3(glam::vec3(viewport_mouse_pos.x, viewport_mouse_pos.y, -1.0));
+        let far_point = inverted_view_projection.project_point3(glam::vec3(viewport_mouse_pos.x, viewport_mouse_pos.y, 1.0));
+        let ray_direction = (far_point - near_point).normalize();
 
         // 3. Ray plane intersection - for simplicity we only consider plane located at z == 0
-        let plane_normal = glam::Vec3::new(0., 0., -1.0).normalize();
-        let bot = ray_direction.clone().dot(plane_normal.clone());
-        if bot.abs() < 0.01 {
-            println!("bot too small: {:?}, does not cross!", bot);
+        // it is called every frame, so ray that does not cross the plane is not reported
+        if ray_direction.z.abs() < 0.01 {
             return None;
         }
-
-        let top = -(camera_config.get_eye_position().clone().dot(plane_normal.clone()));
-        let t = top / bot;
-
+        let t = -near_point.z / ray_direction.z;
+        // plane is behind ray start
         if t < 0.0 {
-            println!("t too small: {:?}, does not cross!", t);
             return None;
         }
-        let z_zero_plane_intersection = camera_config.get_eye_position().clone().add(ray_direction.mul(t));
-
-        return Some(z_zero_plane_intersection);
+        return Some(near_point.add(ray_direction.mul(t)));
     }
 
     pub fn get_aspect_ratio(&self) -> f32 {
-        self.window.size().0 as f32 / self.window.size().1 as f32
-    }
-
-    fn get_eye_coords(clip_coords: glam::Vec4, camera_config: &CameraConfig, aspect_ratio: f32) -> glam::Vec4 {
-        let projection = camera_config.get_projection_matrix(aspect_ratio);
-        let inverted_projection = projection.inverse();
-        let eye_coords = inverted_projection * clip_coords;
-        return glam::vec4(eye_coords.x, eye_coords.y,-1.0, 0.0);
-    }
-
-    fn get_world_coords(eye_coords: glam::Vec4, camera_config: &CameraConfig) -> glam::Vec3 {
-        let view_matrix = camera_config.get_view_matrix();
-        let inverted_view = view_matrix.inverse();
-        let ray_world = inverted_view * eye_coords;
-        return glam::vec3(ray_world.x, ray_world.y, ray_world.z);
+        let win_size = self.get_window_size();
+        win_size.0 as f32 / win_size.1 as f32
     }
 
 }
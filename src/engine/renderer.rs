@@ -1,16 +1,36 @@
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use image::RgbaImage;
+
+use crate::engine::api::colour::Colour;
 use crate::engine::api::drawable::Drawable;
-use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::game_api::{CameraConfig, LayerMask};
+use crate::engine::api::post_processing::PostProcessing;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::opengl_context::OpenglContext;
 use crate::engine::rendering;
+use crate::engine::rendering::camera_views::render_camera_views;
+use crate::engine::rendering::sprite_batch::{RenderStats, SpriteBatch};
 
 pub struct Renderer {
     viewport: rendering::Viewport,
+    // screenshot is taken at the end of next rendered frame, right before it is presented
+    pending_screenshot: Option<PathBuf>,
+    post_processing: Rc<PostProcessing>,
+    sprite_batch: RefCell<SpriteBatch>,
+    last_frame_stats: RenderStats,
+    // passed to drawables through render util, set by engine when running with fixed timestep
+    interpolation_alpha: f32,
+    wireframe: bool,
 }
 
 impl Renderer {
-    pub fn new(context: &OpenglContext) -> Renderer {
-        let viewport = rendering::Viewport::for_window(context.window.size().0 as i32, context.window.size().1 as i32);
+    pub fn new(context: &OpenglContext, post_processing: Rc<PostProcessing>) -> Renderer {
+        let window_size = context.get_window_size();
+        let viewport = rendering::Viewport::for_window(window_size.0 as i32, window_size.1 as i32);
         viewport.set_used();
         unsafe {
             gl::ClearColor(0.0, 0.0, 0.0, 1.0);
@@ -18,22 +38,112 @@ impl Renderer {
 
         Renderer {
             viewport,
+            pending_screenshot: None,
+            post_processing,
+            sprite_batch: RefCell::new(SpriteBatch::new()),
+            last_frame_stats: RenderStats::new(),
+            interpolation_alpha: 1.0,
+            wireframe: false,
+        }
+    }
+
+    // overlay is drawn over finished frame in window pixels, screenshots do not show it
+    pub fn render(&mut self, objects: &mut [&mut dyn Drawable], cameras: &[CameraConfig], overlay: &mut [&mut dyn Drawable], context: &OpenglContext) {
+        self.draw(objects, cameras, context);
+
+        if let Some(path) = self.pending_screenshot.take() {
+            match self.capture_frame().save(&path) {
+                Ok(_) => println!("Saved screenshot to {:?}", path),
+                Err(err) => println!("Could not save screenshot to {:?}: {}", path, err)
+            }
+        }
+
+        if !overlay.is_empty() {
+            self.draw_overlay(overlay, context);
         }
+
+        context.present();
     }
 
-    pub fn render(&mut self, objects: &mut [&mut dyn Drawable], active_camera_config: &CameraConfig, context: &OpenglContext) {
+    fn draw_overlay(&mut self, overlay: &mut [&mut dyn Drawable], context: &OpenglContext) {
+        unsafe {
+            gl::Clear(gl::DEPTH_BUFFER_BIT);
+        }
+        let camera_config = CameraConfig::new_screen_space().with_layer_mask(LayerMask::all());
+        let render_util = RenderUtil::new(camera_config, context, &self.sprite_batch);
+        overlay.iter_mut().for_each(|obj| obj.render(&render_util));
+        // stats describe the frame, not the overlay showing them
+        self.sprite_batch.borrow_mut().finish_frame();
+    }
+
+    // draws objects to currently bound framebuffer without presenting it, once for every camera
+    pub fn draw(&mut self, objects: &mut [&mut dyn Drawable], cameras: &[CameraConfig], context: &OpenglContext) {
+        let first_camera_config = match cameras.first() {
+            None => panic!("At least one camera is needed to draw a frame"),
+            Some(camera_config) => camera_config
+        };
+        let render_util = RenderUtil::new(first_camera_config.clone(), context, &self.sprite_batch)
+            .with_interpolation_alpha(self.interpolation_alpha)
+            .with_wireframe(self.wireframe);
+        let surface_size = (self.viewport.w as u32, self.viewport.h as u32);
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         }
-        objects.iter_mut()
-            .for_each(
-                |obj| {
-                    obj.render(
-                        &RenderUtil::new(active_camera_config.clone(), context))
-                }
+        if self.post_processing.has_enabled_passes() {
+            self.post_processing.render(
+                objects,
+                cameras,
+                &render_util,
+                (0, 0, surface_size.0 as i32, surface_size.1 as i32),
+                Colour::new_f(0.0, 0.0, 0.0, 1.0),
+            );
+        } else {
+            render_camera_views(objects, cameras, &render_util, surface_size);
+        }
+        self.last_frame_stats = self.sprite_batch.borrow_mut().finish_frame();
+    }
+
+    pub fn set_interpolation_alpha(&mut self, interpolation_alpha: f32) {
+        self.interpolation_alpha = interpolation_alpha
+    }
+
+    pub fn set_wireframe(&mut self, wireframe: bool) {
+        self.wireframe = wireframe
+    }
+
+    pub fn is_wireframe(&self) -> bool {
+        self.wireframe
+    }
+
+    pub fn get_last_frame_stats(&self) -> &RenderStats {
+        &self.last_frame_stats
+    }
+
+    // reads pixels of current viewport from currently bound framebuffer
+    // must be called after draw but before frame is presented, after swap back buffer content is undefined
+    pub fn capture_frame(&self) -> RgbaImage {
+        let width = self.viewport.w as u32;
+        let height = self.viewport.h as u32;
+        let mut pixels: Vec<u8> = vec![0; (width * height * 4) as usize];
+        unsafe {
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::ReadPixels(
+                self.viewport.x,
+                self.viewport.y,
+                self.viewport.w,
+                self.viewport.h,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut c_void,
             );
+        }
+        let image = RgbaImage::from_raw(width, height, pixels).unwrap();
+        // opengl rows go from bottom to top, image rows from top to bottom
+        image::imageops::flip_vertical(&image)
+    }
 
-        context.window.gl_swap_window();
+    pub fn request_screenshot(&mut self, path: &Path) {
+        self.pending_screenshot = Some(path.to_path_buf());
     }
 
     pub fn resize_viewport(&mut self, w: i32, h: i32) {
@@ -7,4 +7,9 @@ pub mod buffer;
 mod viewport;
 pub mod shape_drawing_component;
 pub mod material;
+pub mod framebuffer;
+pub mod render_target;
+pub mod post_processing;
+pub mod sprite_batch;
+pub mod camera_views;
 
@@ -29,12 +29,21 @@ impl<B> Buffer<B> where B: BufferType {
     }
 
     pub fn bind_buffer_data<T>(&self, data: &[T]) {
+        self.bind_buffer_data_with_usage(data, gl::STATIC_DRAW);
+    }
+
+    // for buffers that are refilled every frame
+    pub fn bind_dynamic_buffer_data<T>(&self, data: &[T]) {
+        self.bind_buffer_data_with_usage(data, gl::DYNAMIC_DRAW);
+    }
+
+    fn bind_buffer_data_with_usage<T>(&self, data: &[T], usage: gl::types::GLenum) {
         unsafe {
             gl::BufferData(
                 B::BUFFER_TYPE, // target
                 (data.len() * std::mem::size_of::<T>()) as gl::types::GLsizeiptr, // size of data in bytes
                 data.as_ptr() as *const gl::types::GLvoid, // pointer to data
-                gl::STATIC_DRAW, // usage
+                usage,
             );
         }
     }
@@ -0,0 +1,66 @@
+use crate::engine::api::colour::Colour;
+use crate::engine::api::drawable::Drawable;
+use crate::engine::api::game_api::{CameraConfig, ViewportRect};
+use crate::engine::api::render_util::RenderUtil;
+
+// renders objects once per camera into currently bound framebuffer, each camera into its own part of it
+// surface should already be cleared, afterwards viewport covers whole surface again
+pub fn render_camera_views(objects: &mut [&mut dyn Drawable], cameras: &[CameraConfig], render_util: &RenderUtil, surface_size: (u32, u32)) {
+    let mut previous_clear_colour: [gl::types::GLfloat; 4] = [0.0; 4];
+    unsafe {
+        gl::GetFloatv(gl::COLOR_CLEAR_VALUE, previous_clear_colour.as_mut_ptr());
+        if render_util.is_wireframe() {
+            gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+        }
+    }
+
+    for camera_config in cameras {
+        let (x, y, w, h) = camera_config.get_viewport().to_pixels(glam::vec2(surface_size.0 as f32, surface_size.1 as f32));
+        if w <= 0 || h <= 0 {
+            continue;
+        }
+        // quads batched by previous camera must be drawn with its viewport
+        render_util.flush_sprite_batch();
+        unsafe {
+            gl::Viewport(x, y, w, h);
+            // cameras do not see depth of what earlier cameras drew
+            let mut clear_mask = gl::DEPTH_BUFFER_BIT;
+            if let Some(clear_colour) = camera_config.get_clear_colour() {
+                let clear_colour: glam::Vec4 = (*clear_colour).into();
+                gl::ClearColor(clear_colour.x, clear_colour.y, clear_colour.z, clear_colour.w);
+                clear_mask |= gl::COLOR_BUFFER_BIT;
+            }
+            gl::Enable(gl::SCISSOR_TEST);
+            gl::Scissor(x, y, w, h);
+            gl::Clear(clear_mask);
+            gl::Disable(gl::SCISSOR_TEST);
+        }
+
+        match camera_config.get_post_processing() {
+            Some(post_processing) if post_processing.has_enabled_passes() => {
+                // passes see only this camera, it is rendered into target of its viewport size and the result is blended in
+                let camera_in_target = camera_config.clone().without_post_processing().with_viewport(ViewportRect::full());
+                let background = camera_config.get_clear_colour().unwrap_or(Colour::new_f(0.0, 0.0, 0.0, 0.0));
+                post_processing.render(objects, std::slice::from_ref(&camera_in_target), render_util, (x, y, w, h), background);
+                unsafe {
+                    // rendering into target switched back to filled polygons
+                    if render_util.is_wireframe() {
+                        gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+                    }
+                }
+            }
+            _ => {
+                let camera_render_util = render_util.with_camera(camera_config.clone(), glam::vec2(w as f32, h as f32));
+                objects.iter_mut().for_each(|obj| obj.render(&camera_render_util));
+            }
+        }
+    }
+    render_util.flush_sprite_batch();
+
+    unsafe {
+        // post processing quads drawn afterwards must stay filled
+        gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+        gl::Viewport(0, 0, surface_size.0 as i32, surface_size.1 as i32);
+        gl::ClearColor(previous_clear_colour[0], previous_clear_colour[1], previous_clear_colour[2], previous_clear_colour[3]);
+    }
+}
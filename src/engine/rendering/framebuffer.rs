@@ -0,0 +1,82 @@
+// framebuffer that is not backed by any window
+// used when engine runs headless and there is no default framebuffer we could present to
+pub struct OffscreenFramebuffer {
+    fbo: gl::types::GLuint,
+    colour_rbo: gl::types::GLuint,
+    depth_rbo: gl::types::GLuint,
+    width: u32,
+    height: u32,
+}
+
+impl OffscreenFramebuffer {
+    pub fn new(width: u32, height: u32) -> Result<OffscreenFramebuffer, FramebufferError> {
+        let mut fbo: gl::types::GLuint = 0;
+        let mut colour_rbo: gl::types::GLuint = 0;
+        let mut depth_rbo: gl::types::GLuint = 0;
+        unsafe {
+            gl::GenFramebuffers(1, &mut fbo);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+
+            gl::GenRenderbuffers(1, &mut colour_rbo);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, colour_rbo);
+            gl::RenderbufferStorage(gl::RENDERBUFFER, gl::RGBA8, width as i32, height as i32);
+            gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::RENDERBUFFER, colour_rbo);
+
+            gl::GenRenderbuffers(1, &mut depth_rbo);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, depth_rbo);
+            gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH24_STENCIL8, width as i32, height as i32);
+            gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::RENDERBUFFER, depth_rbo);
+
+            gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+
+            let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            if status != gl::FRAMEBUFFER_COMPLETE {
+                // there is no framebuffer whose drop would release these
+                gl::DeleteRenderbuffers(1, &mut colour_rbo);
+                gl::DeleteRenderbuffers(1, &mut depth_rbo);
+                gl::DeleteFramebuffers(1, &mut fbo);
+                return Err(FramebufferError::Incomplete { status });
+            }
+        }
+
+        Ok(OffscreenFramebuffer {
+            fbo,
+            colour_rbo,
+            depth_rbo,
+            width,
+            height,
+        })
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
+        }
+    }
+
+    pub fn unbind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl Drop for OffscreenFramebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteRenderbuffers(1, &mut self.colour_rbo);
+            gl::DeleteRenderbuffers(1, &mut self.depth_rbo);
+            gl::DeleteFramebuffers(1, &mut self.fbo);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FramebufferError {
+    Incomplete { status: gl::types::GLenum },
+}
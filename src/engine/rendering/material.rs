@@ -7,6 +7,9 @@ use crate::engine::rendering::ShaderProgram;
 
 type UniformName = String;
 
+// uniforms engine sets before every draw call
+const PER_DRAW_UNIFORMS: [&str; 3] = ["mvp", "resolution", "timeMillis"];
+
 #[derive(Clone)]
 pub struct Material {
     shader_program: Rc<ShaderProgram>,
@@ -30,11 +33,34 @@ impl Material {
     }
 
     pub fn activate(&mut self) {
+        self.mark_activated();
+        self.shader_program.set_used();
+        self.uniforms.values().for_each(|uniform| { uniform.activate() })
+    }
+
+    // materials can be drawn in single batch if they use the same program with the same uniform values
+    // uniforms that are set separately for each draw are skipped
+    pub fn is_batch_compatible(&self, other: &Material) -> bool {
+        if !Rc::ptr_eq(&self.shader_program, &other.shader_program) {
+            return false;
+        }
+        let is_per_draw = |name: &String| PER_DRAW_UNIFORMS.contains(&name.as_str());
+        let own_uniforms: Vec<(&UniformName, &Uniform)> = self.uniforms.iter().filter(|(name, _)| !is_per_draw(name)).collect();
+        let other_uniforms_count = other.uniforms.keys().filter(|name| !is_per_draw(name)).count();
+
+        own_uniforms.len() == other_uniforms_count && own_uniforms.iter().all(|(name, uniform)| {
+            match other.uniforms.get(*name) {
+                None => false,
+                Some(other_uniform) => other_uniform.kind == uniform.kind
+            }
+        })
+    }
+
+    // starts active duration without using program, for materials whose copy is drawn later
+    pub fn mark_activated(&mut self) {
         if let None = self.activation_time {
             self.activation_time = Some(Instant::now());
         }
-        self.shader_program.set_used();
-        self.uniforms.values().for_each(|uniform| { uniform.activate() })
     }
 
     pub fn get_active_duration(&self) -> Duration {
@@ -74,7 +100,7 @@ impl Uniform {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum UniformKind {
     MAT_4 { value: glam::Mat4 },
     VEC_4 { value: glam::Vec4 },
@@ -0,0 +1,148 @@
+use crate::engine::api::colour::{Colour, WHITE};
+use crate::engine::api::drawable::Drawable;
+use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::maths::vertex::{TexturedVertexDataLayout, VertexShaderDataLayout};
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::rendering::buffer::{ArrayBuffer, ElementArrayBuffer, VertexArray};
+use crate::engine::rendering::material::{Material, UniformKind};
+use crate::engine::rendering::render_target::RenderTarget;
+
+const QUAD_INDICES: [i32; 6] = [0, 1, 3, 1, 2, 3];
+
+// scene is rendered to one target, then each pass reads from one target and writes to the other
+// last pass is blended into framebuffer that was bound when rendering started
+pub struct PostProcessingChain {
+    ping_pong_targets: Option<[RenderTarget; 2]>,
+    quad: FullscreenQuad,
+}
+
+impl PostProcessingChain {
+    pub fn new() -> PostProcessingChain {
+        PostProcessingChain {
+            ping_pong_targets: None,
+            quad: FullscreenQuad::new(),
+        }
+    }
+
+    // viewport is x, y, width and height in pixels of output framebuffer, targets have its size
+    // scene target is cleared with background, where it stays transparent output keeps what it had
+    pub fn render(&mut self,
+                  objects: &mut [&mut dyn Drawable],
+                  cameras: &[CameraConfig],
+                  render_util: &RenderUtil,
+                  passes: &mut [Material],
+                  viewport: (i32, i32, i32, i32),
+                  background: Colour,
+    ) {
+        let (x, y, width, height) = viewport;
+        self.ensure_targets_size((width as u32, height as u32));
+        let targets = self.ping_pong_targets.as_mut().unwrap();
+        targets[0].set_clear_colour(background);
+        let targets = self.ping_pong_targets.as_ref().unwrap();
+
+        let mut output_fbo: gl::types::GLint = 0;
+        let blend_enabled = unsafe {
+            gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut output_fbo);
+            gl::IsEnabled(gl::BLEND) == gl::TRUE
+        };
+
+        targets[0].render_cameras(objects, cameras, render_util);
+
+        let resolution = glam::vec2(width as f32, height as f32);
+        let num_passes = passes.len();
+        for (idx, material) in passes.iter_mut().enumerate() {
+            let input = &targets[idx % 2];
+            unsafe {
+                if idx == num_passes - 1 {
+                    gl::BindFramebuffer(gl::FRAMEBUFFER, output_fbo as gl::types::GLuint);
+                    gl::Viewport(x, y, width, height);
+                    if blend_enabled {
+                        gl::Enable(gl::BLEND);
+                    }
+                } else {
+                    targets[(idx + 1) % 2].bind();
+                    gl::Viewport(0, 0, width, height);
+                    // blending intermediate pictures into cleared target would multiply their alpha into colour
+                    gl::Disable(gl::BLEND);
+                    gl::Clear(gl::COLOR_BUFFER_BIT);
+                }
+            }
+            self.quad.render(input, material, resolution);
+            render_util.get_sprite_batch().borrow_mut().record_immediate_draw();
+        }
+    }
+
+    fn ensure_targets_size(&mut self, size: (u32, u32)) {
+        let up_to_date = match &self.ping_pong_targets {
+            None => false,
+            Some(targets) => targets[0].get_size() == size
+        };
+        if !up_to_date {
+            self.ping_pong_targets = Some([
+                RenderTarget::new(size.0, size.1, true).unwrap(),
+                RenderTarget::new(size.0, size.1, false).unwrap(),
+            ]);
+        }
+    }
+}
+
+struct FullscreenQuad {
+    vbo: ArrayBuffer,
+    vao: VertexArray,
+    ebo: ElementArrayBuffer,
+}
+
+impl FullscreenQuad {
+    fn new() -> FullscreenQuad {
+        let vertices = [
+            TexturedVertexDataLayout { pos: (1.0, 1.0, 0.0).into(), clr: WHITE.into(), tx_coords: (1.0, 1.0).into() },
+            TexturedVertexDataLayout { pos: (1.0, -1.0, 0.0).into(), clr: WHITE.into(), tx_coords: (1.0, 0.0).into() },
+            TexturedVertexDataLayout { pos: (-1.0, -1.0, 0.0).into(), clr: WHITE.into(), tx_coords: (0.0, 0.0).into() },
+            TexturedVertexDataLayout { pos: (-1.0, 1.0, 0.0).into(), clr: WHITE.into(), tx_coords: (0.0, 1.0).into() },
+        ];
+
+        let vbo = ArrayBuffer::new();
+        let vao = VertexArray::new();
+        let ebo = ElementArrayBuffer::new();
+
+        vao.bind();
+        vbo.bind();
+        vbo.bind_buffer_data(&vertices);
+        ebo.bind();
+        ebo.bind_buffer_data(&QUAD_INDICES);
+        TexturedVertexDataLayout::configure_vertex_shader_data();
+
+        vbo.unbind(); // vao must be unbind before ebo else ebo does not get saved!
+        vao.unbind();
+        ebo.unbind();
+
+        FullscreenQuad {
+            vbo,
+            vao,
+            ebo,
+        }
+    }
+
+    fn render(&self, input: &RenderTarget, material: &mut Material, resolution: glam::Vec2) {
+        material.set_variable("resolution", UniformKind::VEC_2 { value: resolution });
+        material.set_variable("timeMillis", UniformKind::FLOAT { value: material.get_active_duration().as_millis() as f32 });
+        material.activate();
+
+        let sprite = input.get_sprite();
+        self.vao.bind();
+        self.ebo.bind();
+        sprite.start_drawing();
+        unsafe {
+            gl::DrawElements(
+                gl::TRIANGLES,
+                QUAD_INDICES.len() as i32,
+                gl::UNSIGNED_INT,
+                0 as *const gl::types::GLvoid,
+            );
+            gl::UseProgram(0);
+        }
+        sprite.stop_drawing();
+        self.vao.unbind();
+        self.ebo.unbind();
+    }
+}
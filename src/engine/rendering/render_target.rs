@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use crate::engine::api::colour::Colour;
+use crate::engine::api::drawable::Drawable;
+use crate::engine::api::game_api::CameraConfig;
+use crate::engine::api::render_util::RenderUtil;
+use crate::engine::api::texture::{InternalFormat, Sprite, Texture, TextureFilterType, TextureParams, TextureWrapType};
+use crate::engine::rendering::camera_views::render_camera_views;
+use crate::engine::rendering::framebuffer::FramebufferError;
+
+/**
+Framebuffer with texture colour attachment, everything rendered to it can be later drawn as a sprite.
+```
+let minimap = RenderTarget::new(512, 512, true).unwrap();
+minimap.render(&mut [&mut chessboard], &minimap_camera, render_util);
+let minimap_rect = Rectangle::new_textured(&position, &size, material, minimap.get_sprite());
+```
+ */
+pub struct RenderTarget {
+    fbo: gl::types::GLuint,
+    colour_texture: Rc<Texture>,
+    depth_rbo: Option<gl::types::GLuint>,
+    width: u32,
+    height: u32,
+    clear_colour: Colour,
+}
+
+impl RenderTarget {
+    pub fn new(width: u32, height: u32, with_depth: bool) -> Result<RenderTarget, FramebufferError> {
+        let colour_texture = Rc::new(Texture::new_empty(
+            width as i32,
+            height as i32,
+            TextureParams::new()
+                .with_mag_filter(TextureFilterType::LINEAR)
+                .with_min_filter(TextureFilterType::LINEAR)
+                .with_x_wrap(TextureWrapType::CLAMP_TO_EDGE)
+                .with_y_wrap(TextureWrapType::CLAMP_TO_EDGE),
+            InternalFormat::RGBA,
+        ));
+
+        let mut fbo: gl::types::GLuint = 0;
+        let mut depth_rbo = None;
+        unsafe {
+            let previous_fbo = RenderTarget::get_bound_framebuffer();
+            gl::GenFramebuffers(1, &mut fbo);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+            gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, colour_texture.get_id(), 0);
+
+            if with_depth {
+                let mut rbo: gl::types::GLuint = 0;
+                gl::GenRenderbuffers(1, &mut rbo);
+                gl::BindRenderbuffer(gl::RENDERBUFFER, rbo);
+                gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH24_STENCIL8, width as i32, height as i32);
+                gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::RENDERBUFFER, rbo);
+                gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+                depth_rbo = Some(rbo);
+            }
+
+            let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, previous_fbo);
+            if status != gl::FRAMEBUFFER_COMPLETE {
+                // there is no target whose drop would release these, colour texture releases itself
+                if let Some(mut rbo) = depth_rbo {
+                    gl::DeleteRenderbuffers(1, &mut rbo);
+                }
+                gl::DeleteFramebuffers(1, &mut fbo);
+                return Err(FramebufferError::Incomplete { status });
+            }
+        }
+
+        Ok(RenderTarget {
+            fbo,
+            colour_texture,
+            depth_rbo,
+            width,
+            height,
+            clear_colour: Colour::new_f(0.0, 0.0, 0.0, 0.0),
+        })
+    }
+
+    pub fn set_clear_colour(&mut self, clear_colour: Colour) {
+        self.clear_colour = clear_colour
+    }
+
+    // sprite sharing colour texture of this target, content changes every time target is rendered to
+    pub fn get_sprite(&self) -> Sprite {
+        Sprite::new(Rc::clone(&self.colour_texture))
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    // renders objects with given camera into this target
+    // framebuffer and viewport that were in use before are restored afterwards so this can be called in the middle of a frame
+    pub fn render(&self, objects: &mut [&mut dyn Drawable], camera_config: &CameraConfig, render_util: &RenderUtil) {
+        self.render_cameras(objects, std::slice::from_ref(camera_config), render_util)
+    }
+
+    // like render but with several cameras, viewport rect of each camera is relative to this target
+    pub fn render_cameras(&self, objects: &mut [&mut dyn Drawable], cameras: &[CameraConfig], render_util: &RenderUtil) {
+        // anything pending belongs to previously bound framebuffer
+        render_util.flush_sprite_batch();
+        let previous_fbo = RenderTarget::get_bound_framebuffer();
+        let mut previous_viewport: [gl::types::GLint; 4] = [0; 4];
+        let clear_colour: glam::Vec4 = self.clear_colour.into();
+        let mut previous_clear_colour: [gl::types::GLfloat; 4] = [0.0; 4];
+        unsafe {
+            gl::GetIntegerv(gl::VIEWPORT, previous_viewport.as_mut_ptr());
+            gl::GetFloatv(gl::COLOR_CLEAR_VALUE, previous_clear_colour.as_mut_ptr());
+
+            self.bind();
+            gl::Viewport(0, 0, self.width as i32, self.height as i32);
+            gl::ClearColor(clear_colour.x, clear_colour.y, clear_colour.z, clear_colour.w);
+            let mut clear_mask = gl::COLOR_BUFFER_BIT;
+            if self.depth_rbo.is_some() {
+                clear_mask |= gl::DEPTH_BUFFER_BIT;
+            }
+            gl::Clear(clear_mask);
+        }
+
+        render_camera_views(objects, cameras, render_util, (self.width, self.height));
+
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, previous_fbo);
+            gl::Viewport(previous_viewport[0], previous_viewport[1], previous_viewport[2], previous_viewport[3]);
+            gl::ClearColor(previous_clear_colour[0], previous_clear_colour[1], previous_clear_colour[2], previous_clear_colour[3]);
+        }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
+        }
+    }
+
+    fn get_bound_framebuffer() -> gl::types::GLuint {
+        let mut bound_fbo: gl::types::GLint = 0;
+        unsafe {
+            gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut bound_fbo);
+        }
+        bound_fbo as gl::types::GLuint
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(mut rbo) = self.depth_rbo {
+                gl::DeleteRenderbuffers(1, &mut rbo);
+            }
+            gl::DeleteFramebuffers(1, &mut self.fbo);
+        }
+    }
+}
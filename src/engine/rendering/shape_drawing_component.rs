@@ -1,6 +1,5 @@
 use std::marker::PhantomData;
 use std::rc::Rc;
-use glam::Vec3;
 
 use crate::engine::api::maths::vertex::VertexShaderDataLayout;
 use crate::engine::api::render_util::RenderUtil;
@@ -9,13 +8,17 @@ use crate::engine::rendering;
 use crate::engine::rendering::buffer::{ArrayBuffer, ElementArrayBuffer, VertexArray};
 use crate::engine::rendering::buffer;
 use crate::engine::rendering::material::{Material, UniformKind};
+use crate::engine::rendering::sprite_batch::DeferredDraw;
 
 // todo: this class should be probably on engine side
+// geometry is drawn when sprite batch is flushed, in its place of draw order
 pub struct ShapeDrawingComponent<T> where T: VertexShaderDataLayout {
     vbo: ArrayBuffer,
-    vao: VertexArray,
-    ebo: ElementArrayBuffer,
+    vao: Rc<VertexArray>,
+    ebo: Rc<ElementArrayBuffer>,
     sprite: Option<Sprite>,
+    // in local space, used to sort geometry by depth
+    centre: glam::Vec3,
     _marker: PhantomData<T>,
 }
 
@@ -42,14 +45,21 @@ impl<'a, T: VertexShaderDataLayout> ShapeDrawingComponent<T> {
         ebo.unbind();
         ShapeDrawingComponent {
             vbo,
-            vao,
-            ebo,
+            vao: Rc::new(vao),
+            ebo: Rc::new(ebo),
             sprite,
+            centre: get_centre(vertices),
             _marker: ::std::marker::PhantomData,
         }
     }
 
-    pub fn bind_data(&self, vertices: &[T]) {
+    pub fn get_sprite(&self) -> &Option<Sprite> {
+        &self.sprite
+    }
+
+    // geometry that is already queued but not drawn yet is drawn with new data too
+    pub fn bind_data(&mut self, vertices: &[T]) {
+        self.centre = get_centre(vertices);
         self.vbo.bind();
         self.vbo.bind_buffer_data(vertices);
         self.vbo.unbind();
@@ -59,40 +69,38 @@ impl<'a, T: VertexShaderDataLayout> ShapeDrawingComponent<T> {
         &mut self,
         num_indices: i32,
         mode: gl::types::GLenum,
-        world_coords_position: glam::Vec3,
+        world_matrix: glam::Mat4,
         render_util: &RenderUtil,
         material: &mut Material,
-        scale: glam::Vec3,
-        scale_point_offset: Vec3
     ) {
-        let mvp = render_util.calculate_camera_MVP(world_coords_position, scale, scale_point_offset);
+        let render_order = render_util.get_render_order();
+        if !render_util.is_layer_visible(render_order.get_layer()) {
+            return;
+        }
+        let mvp = render_util.calculate_camera_MVP(world_matrix);
 
         // set shader uniforms
+        material.mark_activated();
         material.set_variable("mvp", UniformKind::MAT_4 { value: mvp });
         material.set_variable("resolution", UniformKind::VEC_2 { value: render_util.get_window_size() });
         // care - u64 to f32
         material.set_variable("timeMillis", UniformKind::FLOAT {value: material.get_active_duration().as_millis() as f32});
 
-        material.activate();
+        render_util.submit_deferred_draw(DeferredDraw {
+            vao: Rc::clone(&self.vao),
+            ebo: Rc::clone(&self.ebo),
+            material: material.clone(),
+            sprite: self.sprite.clone(),
+            mode,
+            num_indices,
+            depth: mvp.project_point3(self.centre).z,
+        });
+    }
+}
 
-        self.vao.bind();
-        self.ebo.bind();
-        unsafe {
-            if self.sprite.is_some() {
-                self.sprite.as_ref().unwrap().start_drawing();
-            }
-            gl::DrawElements(
-                mode,
-                num_indices,
-                gl::UNSIGNED_INT,
-                0 as *const gl::types::GLvoid,
-            );
-            if self.sprite.is_some() {
-                self.sprite.as_ref().unwrap().stop_drawing();
-            }
-            gl::UseProgram(0);
-        }
-        self.vao.unbind();
-        self.ebo.unbind();
+fn get_centre<T: VertexShaderDataLayout>(vertices: &[T]) -> glam::Vec3 {
+    if vertices.is_empty() {
+        return glam::Vec3::ZERO;
     }
-}
\ No newline at end of file
+    vertices.iter().fold(glam::Vec3::ZERO, |sum, vertex| sum + vertex.get_pos()) / vertices.len() as f32
+}
@@ -0,0 +1,329 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::engine::api::maths::vertex::{TexturedVertexDataLayout, VertexShaderDataLayout};
+use crate::engine::api::render_util::RenderOrder;
+use crate::engine::api::texture::Sprite;
+use crate::engine::rendering::buffer::{ArrayBuffer, ElementArrayBuffer, VertexArray};
+use crate::engine::rendering::material::{Material, UniformKind};
+
+// draw call is split when it grows above this, keeps single upload reasonably small
+const MAX_BATCH_VERTICES: usize = 4 * 1024;
+
+/**
+Queues textured geometry already transformed to world space and draws it sorted by render order when flushed.
+Neighbouring geometry in sorted queue that uses the same texture, batch compatible material and camera is drawn
+with a single draw call.
+Geometry with buffers and material of its own, like coloured shapes, is queued as deferred draw and drawn
+separately in its place of sorted queue.
+Anything drawn outside of the batch must flush it before drawing so that draw order is kept, so only geometry
+submitted since last flush is sorted together.
+ */
+pub struct SpriteBatch {
+    vbo: ArrayBuffer,
+    vao: VertexArray,
+    ebo: ElementArrayBuffer,
+    // geometry waiting for flush, indices are relative to first vertex of their geometry
+    queue: Vec<QueuedGeometry>,
+    queued_vertices: Vec<TexturedVertexDataLayout>,
+    queued_indices: Vec<i32>,
+    // states used by queued geometry, consecutive submits with compatible state share one
+    states: Vec<BatchState>,
+    deferred_draws: Vec<DeferredDraw>,
+    // content of current draw call
+    vertices: Vec<TexturedVertexDataLayout>,
+    indices: Vec<i32>,
+    stats: RenderStats,
+}
+
+struct BatchState {
+    material: Material,
+    sprite: Sprite,
+    view_projection: glam::Mat4,
+    resolution: glam::Vec2,
+}
+
+impl BatchState {
+    fn is_compatible(&self, material: &Material, sprite: &Sprite, view_projection: &glam::Mat4) -> bool {
+        self.sprite.get_texture_id() == sprite.get_texture_id()
+            && self.view_projection == *view_projection
+            && self.material.is_batch_compatible(material)
+    }
+}
+
+/**
+Draw of geometry that is not merged with anything else, it is drawn with its own buffers and material when
+sorted queue reaches it. Vertex array is shared so that it lives until then, buffers attached to it are kept alive by it.
+ */
+pub struct DeferredDraw {
+    pub vao: Rc<VertexArray>,
+    pub ebo: Rc<ElementArrayBuffer>,
+    // uniforms are already set
+    pub material: Material,
+    pub sprite: Option<Sprite>,
+    pub mode: gl::types::GLenum,
+    pub num_indices: i32,
+    // normalized device depth of geometry centre
+    pub depth: f32,
+}
+
+struct QueuedGeometry {
+    // batched geometry is drawn with state, deferred draw with itself
+    deferred_draw_idx: Option<usize>,
+    state_idx: usize,
+    render_order: RenderOrder,
+    // normalized device depth of geometry centre, bigger is further from camera
+    depth: f32,
+    first_vertex: usize,
+    num_vertices: usize,
+    first_index: usize,
+    num_indices: usize,
+}
+
+impl SpriteBatch {
+    pub fn new() -> SpriteBatch {
+        let vbo = ArrayBuffer::new();
+        let vao = VertexArray::new();
+        let ebo = ElementArrayBuffer::new();
+
+        vao.bind();
+        vbo.bind();
+        vbo.bind_dynamic_buffer_data::<TexturedVertexDataLayout>(&[]);
+        ebo.bind();
+        TexturedVertexDataLayout::configure_vertex_shader_data();
+
+        vbo.unbind(); // vao must be unbind before ebo else ebo does not get saved!
+        vao.unbind();
+        ebo.unbind();
+
+        SpriteBatch {
+            vbo,
+            vao,
+            ebo,
+            queue: vec!(),
+            queued_vertices: vec!(),
+            queued_indices: vec!(),
+            states: vec!(),
+            deferred_draws: vec!(),
+            vertices: vec!(),
+            indices: vec!(),
+            stats: RenderStats::new(),
+        }
+    }
+
+    // vertices positions must be in world space, indices are relative to submitted vertices
+    pub fn submit(&mut self,
+                  vertices: &[TexturedVertexDataLayout],
+                  indices: &[i32],
+                  material: &Material,
+                  sprite: &Sprite,
+                  view_projection: glam::Mat4,
+                  resolution: glam::Vec2,
+                  render_order: RenderOrder,
+    ) {
+        if vertices.is_empty() {
+            return;
+        }
+        let compatible = match self.states.last() {
+            None => false,
+            Some(state) => state.is_compatible(material, sprite, &view_projection)
+        };
+        if !compatible {
+            self.states.push(BatchState {
+                material: material.clone(),
+                sprite: sprite.clone(),
+                view_projection,
+                resolution,
+            });
+        }
+
+        let centre = vertices.iter().fold(glam::Vec3::ZERO, |sum, vertex| sum + vertex.get_pos()) / vertices.len() as f32;
+        self.queue.push(QueuedGeometry {
+            deferred_draw_idx: None,
+            state_idx: self.states.len() - 1,
+            render_order,
+            depth: view_projection.project_point3(centre).z,
+            first_vertex: self.queued_vertices.len(),
+            num_vertices: vertices.len(),
+            first_index: self.queued_indices.len(),
+            num_indices: indices.len(),
+        });
+        self.queued_vertices.extend_from_slice(vertices);
+        self.queued_indices.extend_from_slice(indices);
+        self.stats.batched_vertices += vertices.len() as u32;
+    }
+
+    pub fn submit_deferred(&mut self, deferred_draw: DeferredDraw, render_order: RenderOrder) {
+        self.queue.push(QueuedGeometry {
+            deferred_draw_idx: Some(self.deferred_draws.len()),
+            state_idx: 0,
+            render_order,
+            depth: deferred_draw.depth,
+            first_vertex: 0,
+            num_vertices: 0,
+            first_index: 0,
+            num_indices: 0,
+        });
+        self.deferred_draws.push(deferred_draw);
+    }
+
+    // sorts queued geometry and draws it
+    pub fn flush(&mut self) {
+        let mut queue = std::mem::take(&mut self.queue);
+        // stable sort, geometry with equal order stays in submission order
+        queue.sort_by(compare_draw_order);
+
+        // state and opacity of current draw call
+        let mut current: Option<(usize, bool)> = None;
+        for geometry in queue.iter() {
+            if let Some(deferred_draw_idx) = geometry.deferred_draw_idx {
+                self.draw(current);
+                current = None;
+                self.draw_deferred(deferred_draw_idx, geometry.render_order.is_opaque());
+                continue;
+            }
+            let opaque = geometry.render_order.is_opaque();
+            let compatible = match current {
+                None => false,
+                Some((state_idx, current_opaque)) => {
+                    let state = &self.states[geometry.state_idx];
+                    current_opaque == opaque
+                        && (state_idx == geometry.state_idx
+                        || self.states[state_idx].is_compatible(&state.material, &state.sprite, &state.view_projection))
+                }
+            };
+            if !compatible || self.vertices.len() + geometry.num_vertices > MAX_BATCH_VERTICES {
+                self.draw(current);
+                current = Some((geometry.state_idx, opaque));
+            }
+
+            let base_index = self.vertices.len() as i32;
+            self.vertices.extend_from_slice(&self.queued_vertices[geometry.first_vertex..geometry.first_vertex + geometry.num_vertices]);
+            self.indices.extend(self.queued_indices[geometry.first_index..geometry.first_index + geometry.num_indices].iter().map(|idx| idx + base_index));
+        }
+        self.draw(current);
+
+        self.queued_vertices.clear();
+        self.queued_indices.clear();
+        self.states.clear();
+        self.deferred_draws.clear();
+    }
+
+    fn draw_deferred(&mut self, deferred_draw_idx: usize, opaque: bool) {
+        let deferred_draw = &mut self.deferred_draws[deferred_draw_idx];
+        deferred_draw.material.activate();
+        deferred_draw.vao.bind();
+        deferred_draw.ebo.bind();
+        if let Some(sprite) = &deferred_draw.sprite {
+            sprite.start_drawing();
+        }
+        unsafe {
+            gl::DepthMask(if opaque { gl::TRUE } else { gl::FALSE });
+            gl::DrawElements(
+                deferred_draw.mode,
+                deferred_draw.num_indices,
+                gl::UNSIGNED_INT,
+                0 as *const gl::types::GLvoid,
+            );
+            gl::DepthMask(gl::TRUE);
+        }
+        if let Some(sprite) = &deferred_draw.sprite {
+            sprite.stop_drawing();
+        }
+        unsafe {
+            gl::UseProgram(0);
+        }
+        deferred_draw.vao.unbind();
+        deferred_draw.ebo.unbind();
+        self.stats.draw_calls += 1;
+    }
+
+    fn draw(&mut self, batch: Option<(usize, bool)>) {
+        let (state_idx, opaque) = match batch {
+            None => return,
+            Some(batch) => batch
+        };
+        if self.indices.is_empty() {
+            return;
+        }
+
+        let state = &mut self.states[state_idx];
+        state.material.set_variable("mvp", UniformKind::MAT_4 { value: state.view_projection });
+        state.material.set_variable("resolution", UniformKind::VEC_2 { value: state.resolution });
+        state.material.set_variable("timeMillis", UniformKind::FLOAT { value: state.material.get_active_duration().as_millis() as f32 });
+        state.material.activate();
+
+        self.vao.bind();
+        self.vbo.bind();
+        self.vbo.bind_dynamic_buffer_data(&self.vertices);
+        self.ebo.bind();
+        self.ebo.bind_dynamic_buffer_data(&self.indices);
+        state.sprite.start_drawing();
+        unsafe {
+            // transparent geometry must not hide anything drawn behind it later
+            gl::DepthMask(if opaque { gl::TRUE } else { gl::FALSE });
+            gl::DrawElements(
+                gl::TRIANGLES,
+                self.indices.len() as i32,
+                gl::UNSIGNED_INT,
+                0 as *const gl::types::GLvoid,
+            );
+            gl::DepthMask(gl::TRUE);
+            gl::UseProgram(0);
+        }
+        state.sprite.stop_drawing();
+        self.vbo.unbind();
+        self.vao.unbind();
+        self.ebo.unbind();
+
+        self.stats.draw_calls += 1;
+        self.stats.batched_draw_calls += 1;
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    // for draws that do not go through the batch
+    pub fn record_immediate_draw(&mut self) {
+        self.stats.draw_calls += 1;
+    }
+
+    // flushes whatever is pending and returns stats gathered since last call
+    pub fn finish_frame(&mut self) -> RenderStats {
+        self.flush();
+        std::mem::replace(&mut self.stats, RenderStats::new())
+    }
+}
+
+// by layer and order in layer, then opaque front to back before transparent back to front
+fn compare_draw_order(first: &QueuedGeometry, second: &QueuedGeometry) -> Ordering {
+    let first_order = &first.render_order;
+    let second_order = &second.render_order;
+    first_order.get_layer().cmp(&second_order.get_layer())
+        .then(first_order.get_order_in_layer().cmp(&second_order.get_order_in_layer()))
+        .then(second_order.is_opaque().cmp(&first_order.is_opaque()))
+        .then_with(|| {
+            let by_depth = match first_order.is_opaque() {
+                true => first.depth.partial_cmp(&second.depth),
+                false => second.depth.partial_cmp(&first.depth)
+            };
+            by_depth.unwrap_or(Ordering::Equal)
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RenderStats {
+    // all draw calls issued, batched or not
+    pub draw_calls: u32,
+    pub batched_draw_calls: u32,
+    pub batched_vertices: u32,
+}
+
+impl RenderStats {
+    pub fn new() -> RenderStats {
+        RenderStats {
+            draw_calls: 0,
+            batched_draw_calls: 0,
+            batched_vertices: 0,
+        }
+    }
+}
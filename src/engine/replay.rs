@@ -0,0 +1,466 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use sdl2::controller::{Axis, Button};
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use sdl2::mouse::{MouseButton, MouseState, MouseWheelDirection};
+
+use crate::engine::timestep::{FixedTimestepConfig, Timestep};
+
+/**
+Text file with everything that engine feeds into a game: random seed, window size when recording started, timestep,
+and for every frame its delta time, mouse position and events polled in it. One record per line:
+```
+seed 1634567890
+window 1920 1080
+timestep fixed 16666666 5
+frame 16666666 512 384
+mouse_down 1 1 512 384
+key_down 13 40 0 0
+text_input ls%20-a
+resized 1280 720
+```
+Input events, typed text and window resizes are recorded, other window events are left out because replay runs without window.
+Mouse positions only map to the same world positions at the same window size, so replay renders at recorded size and follows resizes.
+Timestep is `timestep variable` or `timestep fixed <tick nanoseconds> <max catch up steps>`, replay runs with the recorded one.
+ */
+pub struct InputRecorder {
+    writer: BufWriter<File>,
+}
+
+impl InputRecorder {
+    pub fn create(path: &Path, seed: u64, window_size: (u32, u32), timestep: &Timestep) -> Result<InputRecorder, ReplayError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "seed {}", seed)?;
+        writeln!(writer, "window {} {}", window_size.0, window_size.1)?;
+        writeln!(writer, "{}", timestep_to_line(timestep))?;
+        Ok(InputRecorder {
+            writer
+        })
+    }
+
+    // events recorded afterwards belong to this frame
+    pub fn record_frame(&mut self, delta_time: Duration, mouse_position: glam::Vec2) -> Result<(), ReplayError> {
+        writeln!(self.writer, "frame {} {} {}", delta_time.as_nanos(), mouse_position.x as i32, mouse_position.y as i32)?;
+        Ok(())
+    }
+
+    pub fn record_event(&mut self, event: &Event) -> Result<(), ReplayError> {
+        if let Some(line) = event_to_line(event) {
+            writeln!(self.writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), ReplayError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct InputRecording {
+    seed: u64,
+    window_size: (u32, u32),
+    timestep: Timestep,
+    frames: VecDeque<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn load(path: &Path) -> Result<InputRecording, ReplayError> {
+        let mut seed = None;
+        let mut window_size = None;
+        let mut timestep = None;
+        let mut frames: VecDeque<RecordedFrame> = VecDeque::new();
+        for (line_idx, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            let malformed = || ReplayError::MalformedLine { line_number: line_idx + 1, line: line.clone() };
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.first() {
+                None => {}
+                Some(&"seed") => {
+                    seed = Some(parse_token::<u64>(&tokens, 1).ok_or_else(malformed)?);
+                }
+                Some(&"window") => {
+                    window_size = Some((
+                        parse_token::<u32>(&tokens, 1).filter(|width| *width > 0).ok_or_else(malformed)?,
+                        parse_token::<u32>(&tokens, 2).filter(|height| *height > 0).ok_or_else(malformed)?,
+                    ));
+                }
+                Some(&"timestep") => {
+                    timestep = Some(line_to_timestep(&tokens).ok_or_else(malformed)?);
+                }
+                Some(&"frame") => {
+                    frames.push_back(RecordedFrame {
+                        delta_time: Duration::from_nanos(parse_token::<u64>(&tokens, 1).ok_or_else(malformed)?),
+                        mouse_position: glam::vec2(
+                            parse_token::<i32>(&tokens, 2).ok_or_else(malformed)? as f32,
+                            parse_token::<i32>(&tokens, 3).ok_or_else(malformed)? as f32,
+                        ),
+                        events: vec!(),
+                    });
+                }
+                Some(_) => {
+                    let event = line_to_event(&tokens).ok_or_else(malformed)?;
+                    frames.back_mut().ok_or_else(malformed)?.events.push(event);
+                }
+            }
+        }
+        Ok(InputRecording {
+            seed: seed.ok_or(ReplayError::MissingSeed)?,
+            window_size: window_size.ok_or(ReplayError::MissingWindowSize)?,
+            timestep: timestep.ok_or(ReplayError::MissingTimestep)?,
+            frames,
+        })
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    // size of window when recording started, later resizes are recorded as events
+    pub fn get_window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+
+    // updates must be run the same way as during recording, otherwise game state diverges
+    pub fn get_timestep(&self) -> &Timestep {
+        &self.timestep
+    }
+
+    pub fn get_num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn next_frame(&mut self) -> Option<RecordedFrame> {
+        self.frames.pop_front()
+    }
+}
+
+pub struct RecordedFrame {
+    delta_time: Duration,
+    mouse_position: glam::Vec2,
+    events: Vec<Event>,
+}
+
+impl RecordedFrame {
+    pub fn get_delta_time(&self) -> &Duration {
+        &self.delta_time
+    }
+
+    pub fn get_mouse_position(&self) -> &glam::Vec2 {
+        &self.mouse_position
+    }
+
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::replace(&mut self.events, vec!())
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    MalformedLine { line_number: usize, line: String },
+    MissingSeed,
+    MissingWindowSize,
+    MissingTimestep,
+    // replay must run at recorded window size so that mouse positions mean the same
+    WindowSizeMismatch { recorded: (u32, u32), requested: (u32, u32) },
+    TimestepMismatch { recorded: Timestep, requested: Timestep },
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(other: io::Error) -> Self {
+        ReplayError::Io(other)
+    }
+}
+
+fn timestep_to_line(timestep: &Timestep) -> String {
+    match timestep {
+        Timestep::VARIABLE => String::from("timestep variable"),
+        Timestep::FIXED { config } => {
+            format!("timestep fixed {} {}", config.get_tick_duration().as_nanos(), config.get_max_catch_up_steps())
+        }
+    }
+}
+
+fn line_to_timestep(tokens: &[&str]) -> Option<Timestep> {
+    match tokens.get(1) {
+        Some(&"variable") if tokens.len() == 2 => Some(Timestep::VARIABLE),
+        Some(&"fixed") if tokens.len() == 4 => {
+            let tick_duration = parse_token::<u64>(tokens, 2).filter(|nanos| *nanos > 0)?;
+            let max_catch_up_steps = parse_token::<u32>(tokens, 3).filter(|steps| *steps > 0)?;
+            Some(Timestep::FIXED { config: FixedTimestepConfig::from_tick_duration(Duration::from_nanos(tick_duration), max_catch_up_steps) })
+        }
+        _ => None
+    }
+}
+
+fn event_to_line(event: &Event) -> Option<String> {
+    let line = match event {
+        Event::Quit { .. } => String::from("quit"),
+        Event::KeyDown { keycode, scancode, keymod, repeat, .. } => {
+            format!("key_down {} {} {} {}", keycode_to_token(keycode), scancode_to_token(scancode), keymod.bits(), *repeat as u8)
+        }
+        Event::KeyUp { keycode, scancode, keymod, repeat, .. } => {
+            format!("key_up {} {} {} {}", keycode_to_token(keycode), scancode_to_token(scancode), keymod.bits(), *repeat as u8)
+        }
+        Event::MouseMotion { mousestate, x, y, xrel, yrel, .. } => {
+            format!("mouse_motion {} {} {} {} {}", mousestate.to_sdl_state(), x, y, xrel, yrel)
+        }
+        Event::MouseButtonDown { mouse_btn, clicks, x, y, .. } => {
+            format!("mouse_down {} {} {} {}", *mouse_btn as u8, clicks, x, y)
+        }
+        Event::MouseButtonUp { mouse_btn, clicks, x, y, .. } => {
+            format!("mouse_up {} {} {} {}", *mouse_btn as u8, clicks, x, y)
+        }
+        Event::MouseWheel { x, y, direction, .. } => {
+            format!("wheel {} {} {}", x, y, direction.to_ll())
+        }
+        Event::ControllerButtonDown { which, button, .. } => {
+            format!("pad_down {} {}", which, button.string())
+        }
+        Event::ControllerButtonUp { which, button, .. } => {
+            format!("pad_up {} {}", which, button.string())
+        }
+        Event::ControllerAxisMotion { which, axis, value, .. } => {
+            format!("pad_axis {} {} {}", which, axis.string(), value)
+        }
+        // typed text drives console, sdl does not send empty text
+        Event::TextInput { text, .. } if !text.is_empty() => format!("text_input {}", escape_text(text)),
+        Event::Window { win_event: WindowEvent::Resized(width, height), .. } => format!("resized {} {}", width, height),
+        _ => return None
+    };
+    Some(line)
+}
+
+// timestamps and window ids are not recorded, they are zeroed
+fn line_to_event(tokens: &[&str]) -> Option<Event> {
+    let event = match *tokens.first()? {
+        "quit" => Event::Quit { timestamp: 0 },
+        "key_down" => Event::KeyDown {
+            timestamp: 0,
+            window_id: 0,
+            keycode: token_to_keycode(tokens.get(1)?)?,
+            scancode: token_to_scancode(tokens.get(2)?)?,
+            keymod: Mod::from_bits_truncate(parse_token(tokens, 3)?),
+            repeat: parse_token::<u8>(tokens, 4)? != 0,
+        },
+        "key_up" => Event::KeyUp {
+            timestamp: 0,
+            window_id: 0,
+            keycode: token_to_keycode(tokens.get(1)?)?,
+            scancode: token_to_scancode(tokens.get(2)?)?,
+            keymod: Mod::from_bits_truncate(parse_token(tokens, 3)?),
+            repeat: parse_token::<u8>(tokens, 4)? != 0,
+        },
+        "mouse_motion" => Event::MouseMotion {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mousestate: MouseState::from_sdl_state(parse_token(tokens, 1)?),
+            x: parse_token(tokens, 2)?,
+            y: parse_token(tokens, 3)?,
+            xrel: parse_token(tokens, 4)?,
+            yrel: parse_token(tokens, 5)?,
+        },
+        "mouse_down" => Event::MouseButtonDown {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mouse_btn: MouseButton::from_ll(parse_token(tokens, 1)?),
+            clicks: parse_token(tokens, 2)?,
+            x: parse_token(tokens, 3)?,
+            y: parse_token(tokens, 4)?,
+        },
+        "mouse_up" => Event::MouseButtonUp {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mouse_btn: MouseButton::from_ll(parse_token(tokens, 1)?),
+            clicks: parse_token(tokens, 2)?,
+            x: parse_token(tokens, 3)?,
+            y: parse_token(tokens, 4)?,
+        },
+        "wheel" => Event::MouseWheel {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            x: parse_token(tokens, 1)?,
+            y: parse_token(tokens, 2)?,
+            direction: MouseWheelDirection::from_ll(parse_token(tokens, 3)?),
+        },
+        "pad_down" => Event::ControllerButtonDown {
+            timestamp: 0,
+            which: parse_token(tokens, 1)?,
+            button: Button::from_string(tokens.get(2)?)?,
+        },
+        "pad_up" => Event::ControllerButtonUp {
+            timestamp: 0,
+            which: parse_token(tokens, 1)?,
+            button: Button::from_string(tokens.get(2)?)?,
+        },
+        "pad_axis" => Event::ControllerAxisMotion {
+            timestamp: 0,
+            which: parse_token(tokens, 1)?,
+            axis: Axis::from_string(tokens.get(2)?)?,
+            value: parse_token(tokens, 3)?,
+        },
+        "text_input" => Event::TextInput {
+            timestamp: 0,
+            window_id: 0,
+            text: unescape_text(tokens.get(1)?)?,
+        },
+        "resized" => Event::Window {
+            timestamp: 0,
+            window_id: 0,
+            win_event: WindowEvent::Resized(parse_token(tokens, 1)?, parse_token(tokens, 2)?),
+        },
+        _ => return None
+    };
+    Some(event)
+}
+
+fn parse_token<T: std::str::FromStr>(tokens: &[&str], idx: usize) -> Option<T> {
+    tokens.get(idx)?.parse::<T>().ok()
+}
+
+// text becomes single token, whitespace, control characters and percent sign are written as %XX bytes of their utf-8
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for ch in text.chars() {
+        if ch.is_whitespace() || ch.is_control() || ch == '%' {
+            let mut buffer = [0; 4];
+            ch.encode_utf8(&mut buffer).bytes().for_each(|byte| escaped.push_str(&format!("%{:02X}", byte)));
+        } else {
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+fn unescape_text(token: &str) -> Option<String> {
+    let mut bytes: Vec<u8> = vec!();
+    let mut rest = token;
+    while let Some(percent_idx) = rest.find('%') {
+        bytes.extend_from_slice(rest[..percent_idx].as_bytes());
+        let hex = rest.get(percent_idx + 1..percent_idx + 3)?;
+        bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        rest = &rest[percent_idx + 3..];
+    }
+    bytes.extend_from_slice(rest.as_bytes());
+    String::from_utf8(bytes).ok()
+}
+
+// keycode and scancode are optional in sdl events, `none` stands for missing one
+fn keycode_to_token(keycode: &Option<Keycode>) -> String {
+    keycode.map(|keycode| (keycode as i32).to_string()).unwrap_or_else(|| String::from("none"))
+}
+
+fn scancode_to_token(scancode: &Option<Scancode>) -> String {
+    scancode.map(|scancode| (scancode as i32).to_string()).unwrap_or_else(|| String::from("none"))
+}
+
+// outer option tells if token could be parsed at all
+fn token_to_keycode(token: &str) -> Option<Option<Keycode>> {
+    if token == "none" {
+        return Some(None);
+    }
+    token.parse::<i32>().ok().map(Keycode::from_i32)
+}
+
+fn token_to_scancode(token: &str) -> Option<Option<Scancode>> {
+    if token == "none" {
+        return Some(None);
+    }
+    token.parse::<i32>().ok().map(Scancode::from_i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sdl2::event::{Event, WindowEvent};
+
+    use crate::engine::timestep::{FixedTimestepConfig, Timestep};
+
+    use super::{event_to_line, InputRecorder, InputRecording, line_to_event, line_to_timestep, ReplayError, timestep_to_line};
+
+    fn round_trip(event: &Event) -> Event {
+        let line = event_to_line(event).unwrap();
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        line_to_event(&tokens).unwrap()
+    }
+
+    #[test]
+    fn resize_is_recorded() {
+        let event = Event::Window { timestamp: 0, window_id: 0, win_event: WindowEvent::Resized(1280, 720) };
+        assert_eq!(round_trip(&event), event);
+    }
+
+    #[test]
+    fn typed_text_is_recorded_as_single_token() {
+        let event = Event::TextInput { timestamp: 0, window_id: 0, text: String::from("camera 1.5 -2 %ż\t") };
+        let line = event_to_line(&event).unwrap();
+        assert_eq!(line.split_whitespace().count(), 2);
+        assert_eq!(round_trip(&event), event);
+    }
+
+    #[test]
+    fn malformed_escape_is_rejected() {
+        assert!(line_to_event(&["text_input", "ls%2"]).is_none());
+        assert!(line_to_event(&["text_input", "ls%zz"]).is_none());
+    }
+
+    #[test]
+    fn other_window_events_are_left_out() {
+        assert!(event_to_line(&Event::Window { timestamp: 0, window_id: 0, win_event: WindowEvent::FocusGained }).is_none());
+    }
+
+    #[test]
+    fn timestep_is_recorded() {
+        let fixed = Timestep::FIXED { config: FixedTimestepConfig::new(60, 5) };
+        for timestep in [Timestep::VARIABLE, fixed] {
+            let line = timestep_to_line(&timestep);
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(line_to_timestep(&tokens), Some(timestep));
+        }
+    }
+
+    #[test]
+    fn malformed_timestep_is_rejected() {
+        assert!(line_to_timestep(&["timestep"]).is_none());
+        assert!(line_to_timestep(&["timestep", "variable", "60"]).is_none());
+        assert!(line_to_timestep(&["timestep", "fixed", "0", "5"]).is_none());
+        assert!(line_to_timestep(&["timestep", "fixed", "16666666"]).is_none());
+    }
+
+    #[test]
+    fn recording_keeps_header() {
+        let path = std::env::temp_dir().join(format!("replay_header_{}.rec", std::process::id()));
+        let timestep = Timestep::FIXED { config: FixedTimestepConfig::from_tick_duration(Duration::from_nanos(16666666), 3) };
+        let mut recorder = InputRecorder::create(&path, 42, (1280, 720), &timestep).unwrap();
+        recorder.record_frame(Duration::from_millis(16), glam::vec2(10.0, 20.0)).unwrap();
+        recorder.flush().unwrap();
+
+        let recording = InputRecording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(recording.get_seed(), 42);
+        assert_eq!(recording.get_window_size(), (1280, 720));
+        assert_eq!(*recording.get_timestep(), timestep);
+        assert_eq!(recording.get_num_frames(), 1);
+    }
+
+    #[test]
+    fn recording_without_timestep_is_rejected() {
+        let path = std::env::temp_dir().join(format!("replay_no_timestep_{}.rec", std::process::id()));
+        std::fs::write(&path, "seed 1\nwindow 640 480\nframe 16000000 0 0\n").unwrap();
+        let loaded = InputRecording::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(loaded, Err(ReplayError::MissingTimestep)));
+    }
+}
@@ -2,3 +2,4 @@ mod resource_loader;
 mod rgba_image_data;
 pub mod catching_resource_manager;
 pub mod fonts;
+pub mod texture_atlas;
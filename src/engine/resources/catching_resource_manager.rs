@@ -7,16 +7,21 @@ use std::path::Path;
 use std::rc::Rc;
 
 use crate::engine::api::audio::AudioResource;
-use crate::engine::api::resource_manager::{ResourceManager};
-use crate::engine::api::texture::{Sprite, Texture, TextureFilterType, TextureParams};
+use crate::engine::api::resource_manager::{ResourceCacheStats, ResourceManager};
+use crate::engine::api::texture::{InternalFormat, Sprite, Texture, TextureFilterType, TextureParams};
 use crate::engine::rendering::{ShaderProgram, ShaderType};
 use crate::engine::rendering::material::Material;
 use crate::engine::resources::fonts::SizedFont;
 use crate::engine::resources::resource_loader::ResourceLoader;
+use crate::engine::resources::texture_atlas::{TextureAtlas, TextureAtlasBuilder};
+
+// maximum, atlas pages are trimmed to what is used
+const ATLAS_PAGE_SIZE: u32 = 4096;
 
 // todo: this probably should not be here but be more generic class in engine
 pub struct CachingResourceManager {
     textures_cache: ResourceCache<Texture>,
+    atlases_cache: ResourceCache<TextureAtlas>,
     shaders_cache: ResourceCache<ShaderProgram>,
     audio_cache: ResourceCache<AudioResource>,
     font_faces_cache: ResourceCache<Vec<u8>>,
@@ -28,6 +33,7 @@ impl CachingResourceManager {
     pub fn new() -> CachingResourceManager {
         CachingResourceManager {
             textures_cache: ResourceCache::new(),
+            atlases_cache: ResourceCache::new(),
             shaders_cache: ResourceCache::new(),
             audio_cache: ResourceCache::new(),
             font_faces_cache: ResourceCache::new(),
@@ -93,6 +99,28 @@ impl ResourceManager for CachingResourceManager {
         )
     }
 
+    fn fetch_atlas(&self, id: &str, image_ids: &[&str]) -> Rc<TextureAtlas> {
+        let atlas = self.atlases_cache.fetch(id,
+                                 || {
+                                     let mut builder = TextureAtlasBuilder::new(ATLAS_PAGE_SIZE, ATLAS_PAGE_SIZE, InternalFormat::RGBA);
+                                     for image_id in image_ids {
+                                         let texture_data = self.resource_loader.load_image(image_id);
+                                         builder.add(image_id, texture_data.width, texture_data.height, texture_data.image.into_raw());
+                                     }
+                                     builder.build(
+                                         TextureParams::new()
+                                             .with_mag_filter(TextureFilterType::NEAREST)
+                                             .with_min_filter(TextureFilterType::NEAREST),
+                                     )
+                                 },
+        );
+        // cache is keyed by id only, atlas built for other images would be missing some of these
+        if atlas.get_stats().n_entries as usize != image_ids.len() || !image_ids.iter().all(|image_id| atlas.has_entry(image_id)) {
+            panic!("Atlas {:?} was already built from different images than {:?}", id, image_ids);
+        }
+        atlas
+    }
+
     fn fetch_audio(&self, id: &str) -> Rc<AudioResource> {
         self.audio_cache.fetch(id, || self.resource_loader.load_audio(id))
     }
@@ -107,6 +135,24 @@ impl ResourceManager for CachingResourceManager {
         // todo: implement caching somehow
         self.resource_loader.load_file_lines(id)
     }
+
+    fn get_cache_stats(&self) -> ResourceCacheStats {
+        ResourceCacheStats {
+            textures: self.textures_cache.len(),
+            atlases: self.atlases_cache.len(),
+            shaders: self.shaders_cache.len(),
+            audio: self.audio_cache.len(),
+            fonts: self.font_faces_cache.len(),
+        }
+    }
+
+    fn clear_caches(&self) -> usize {
+        self.textures_cache.clear()
+            + self.atlases_cache.clear()
+            + self.shaders_cache.clear()
+            + self.audio_cache.clear()
+            + self.font_faces_cache.clear()
+    }
 }
 
 struct ResourceCache<T> {
@@ -130,4 +176,13 @@ impl<T> ResourceCache<T> {
             }
         }
     }
-}
\ No newline at end of file
+
+    fn len(&self) -> usize {
+        self.data.borrow().len()
+    }
+
+    // returns number of dropped entries
+    fn clear(&self) -> usize {
+        self.data.borrow_mut().drain().count()
+    }
+}
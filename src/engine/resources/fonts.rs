@@ -1,55 +1,70 @@
-use std::rc::Rc;
-
 use freetype::face::LoadFlag;
 
-use crate::engine::api::texture::{InternalFormat, Sprite, Texture, TextureFilterType, TextureParams, TextureWrapType};
+use crate::engine::api::texture::{InternalFormat, Sprite, TextureFilterType, TextureParams, TextureWrapType};
+use crate::engine::resources::texture_atlas::{TextureAtlas, TextureAtlasBuilder};
+
+// glyphs are packed into atlas with single channel pages, 48pt glyphs of latin-1 fit into one page of this size
+const GLYPH_ATLAS_PAGE_SIZE: u32 = 1024;
+const N_CHARACTERS: usize = 255;
 
 pub struct SizedFont {
     characters: Vec<Character>,
+    atlas: TextureAtlas,
 }
 
 impl SizedFont {
     pub fn new(face: &freetype::Face) -> SizedFont {
         face.set_char_size(0, 48 * 64, 96, 96).unwrap();
-        let mut characters = vec!();
-        unsafe {
-            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
-        }
-        for i in 0..255 {
+        let mut atlas_builder = TextureAtlasBuilder::new(GLYPH_ATLAS_PAGE_SIZE, GLYPH_ATLAS_PAGE_SIZE, InternalFormat::RED);
+        let mut glyph_metrics = vec!();
+        for i in 0..N_CHARACTERS {
             face.load_char(i, LoadFlag::RENDER);
-            characters.push(Character::new(face));
-        }
-        unsafe {
-            gl::PixelStoref(gl::UNPACK_ALIGNMENT, 4 as gl::types::GLfloat);
+            let glyph = face.glyph();
+            let bitmap = glyph.bitmap();
+            atlas_builder.add(
+                &SizedFont::glyph_id(i),
+                bitmap.width() as u32,
+                bitmap.rows() as u32,
+                SizedFont::read_glyph_bitmap(&bitmap),
+            );
+            glyph_metrics.push((
+                glam::vec2(bitmap.width() as f32, bitmap.rows() as f32),
+                glam::vec2(glyph.bitmap_left() as f32, glyph.bitmap_top() as f32),
+                glyph.advance().x,
+            ));
         }
+
+        let atlas = atlas_builder.build(
+            TextureParams::new()
+                .with_mag_filter(TextureFilterType::LINEAR)
+                .with_min_filter(TextureFilterType::LINEAR)
+                .with_x_wrap(TextureWrapType::CLAMP_TO_EDGE)
+                .with_y_wrap(TextureWrapType::CLAMP_TO_EDGE)
+        );
+        let characters = glyph_metrics.into_iter()
+            .enumerate()
+            .map(|(i, (size, bearing, advance))| Character::new(atlas.get_sprite(&SizedFont::glyph_id(i)), size, bearing, advance))
+            .collect();
+
         SizedFont {
-            characters
+            characters,
+            atlas,
         }
     }
 
     pub fn get_char(&self, ch: char) -> &Character {
         &self.characters.get(ch as usize).unwrap()
     }
-}
-
-pub struct Character {
-    sprite: Sprite,
-    size: glam::Vec2,
-    bearing: glam::Vec2,
-    advance: i64,
-}
 
-impl Character {
-    pub fn new(configured_face: &freetype::Face) -> Character {
-        let texture_params = TextureParams::new()
-            .with_mag_filter(TextureFilterType::LINEAR)
-            .with_min_filter(TextureFilterType::LINEAR)
-            .with_x_wrap(TextureWrapType::CLAMP_TO_EDGE)
-            .with_y_wrap(TextureWrapType::CLAMP_TO_EDGE);
+    pub fn get_atlas(&self) -> &TextureAtlas {
+        &self.atlas
+    }
 
-        let glyph = configured_face.glyph();
-        let bitmap = glyph.bitmap();
+    fn glyph_id(char_code: usize) -> String {
+        format!("glyph_{}", char_code)
+    }
 
+    fn read_glyph_bitmap(bitmap: &freetype::Bitmap) -> Vec<u8> {
         let mut buffer = vec!();
         for y in 0..bitmap.rows() {
             for x in 0..bitmap.width() {
@@ -59,14 +74,24 @@ impl Character {
                 buffer.push(bitmap.buffer()[(bitmap.width() * (bitmap.rows() - 1 - y) + x) as usize]);
             }
         }
+        buffer
+    }
+}
 
-        let tx = Texture::from_raw_data(buffer, bitmap.width(), bitmap.rows(), texture_params, InternalFormat::RED);
-        let sprite = Sprite::new(Rc::new(tx));
+pub struct Character {
+    sprite: Sprite,
+    size: glam::Vec2,
+    bearing: glam::Vec2,
+    advance: i64,
+}
+
+impl Character {
+    pub fn new(sprite: Sprite, size: glam::Vec2, bearing: glam::Vec2, advance: i64) -> Character {
         Character {
             sprite,
-            size: glam::vec2(bitmap.width() as f32, bitmap.rows() as f32),
-            bearing: glam::vec2(glyph.bitmap_left() as f32, glyph.bitmap_top() as f32),
-            advance: glyph.advance().x,
+            size,
+            bearing,
+            advance,
         }
     }
 
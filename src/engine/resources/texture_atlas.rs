@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::engine::api::texture::{InternalFormat, Sprite, Texture, TextureParams};
+
+// empty pixels left around every entry so that linear filtering does not bleed neighbours into each other
+const ENTRY_PADDING: u32 = 1;
+
+/**
+Packs many images into as few large textures (pages) as possible.
+Every entry can be later fetched as a sprite pointing at its part of the page, so everything packed
+into the same page can be drawn with single texture bind and batched together.
+Packing uses shelves: entries are sorted by height and placed left to right on horizontal shelves,
+entry goes to the shelf that fits it with least height wasted, new shelf is opened when entry does not fit
+on any of existing ones and new page is opened when there is no space left for new shelf.
+Page size given to builder is maximum, pages are trimmed to the area that is actually used.
+```
+let mut builder = TextureAtlasBuilder::new(1024, 1024, InternalFormat::RGBA);
+builder.add("ring", ring_image.width, ring_image.height, ring_image.image.into_raw());
+let atlas = builder.build(TextureParams::new());
+let ring_sprite = atlas.get_sprite("ring");
+```
+ */
+pub struct TextureAtlasBuilder {
+    page_width: u32,
+    page_height: u32,
+    format: InternalFormat,
+    entries: Vec<PendingEntry>,
+}
+
+struct PendingEntry {
+    id: String,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl TextureAtlasBuilder {
+    pub fn new(page_width: u32, page_height: u32, format: InternalFormat) -> TextureAtlasBuilder {
+        TextureAtlasBuilder {
+            page_width,
+            page_height,
+            format,
+            entries: vec!(),
+        }
+    }
+
+    // data must be tightly packed rows of pixels in format of the atlas, first row is bottom row of the image
+    pub fn add(&mut self, id: &str, width: u32, height: u32, data: Vec<u8>) {
+        if width + 2 * ENTRY_PADDING > self.page_width || height + 2 * ENTRY_PADDING > self.page_height {
+            panic!("Atlas entry {:?} of size {}x{} does not fit into atlas page of size {}x{}", id, width, height, self.page_width, self.page_height);
+        }
+        if data.len() != (width * height) as usize * self.format.get_bytes_per_pixel() {
+            panic!("Atlas entry {:?} has {} bytes of data, which does not match its size {}x{}", id, data.len(), width, height);
+        }
+        if self.entries.iter().any(|entry| entry.id == id) {
+            panic!("Atlas entry {:?} is already added", id);
+        }
+        self.entries.push(PendingEntry {
+            id: String::from(id),
+            width,
+            height,
+            data,
+        });
+    }
+
+    pub fn build(self, texture_params: TextureParams) -> TextureAtlas {
+        let sizes: Vec<(u32, u32)> = self.entries.iter().map(|entry| (entry.width, entry.height)).collect();
+        let (placements, pages_sizes) = pack_rectangles(&sizes, self.page_width, self.page_height);
+
+        let bytes_per_pixel = self.format.get_bytes_per_pixel();
+        let mut pages_data: Vec<Vec<u8>> = pages_sizes.iter()
+            .map(|(width, height)| vec![0; (width * height) as usize * bytes_per_pixel])
+            .collect();
+        for (entry, placement) in self.entries.iter().zip(placements.iter()) {
+            let entry_row_len = entry.width as usize * bytes_per_pixel;
+            let page_row_len = pages_sizes[placement.page].0 as usize * bytes_per_pixel;
+            let page_data = &mut pages_data[placement.page];
+            for row in 0..entry.height as usize {
+                let dst_start = (placement.y as usize + row) * page_row_len + placement.x as usize * bytes_per_pixel;
+                let src_start = row * entry_row_len;
+                page_data[dst_start..dst_start + entry_row_len].copy_from_slice(&entry.data[src_start..src_start + entry_row_len]);
+            }
+        }
+
+        unsafe {
+            // rows of single channel pages are not 4 byte aligned in general
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+        }
+        let pages: Vec<Rc<Texture>> = pages_data.into_iter().zip(pages_sizes.iter()).map(|(page_data, (width, height))| {
+            Rc::new(Texture::from_raw_data(
+                page_data,
+                *width as i32,
+                *height as i32,
+                texture_params.clone(),
+                self.format.clone(),
+            ))
+        }).collect();
+        unsafe {
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4);
+        }
+
+        let mut sprites = HashMap::new();
+        let mut used_pixels: u64 = 0;
+        for (entry, placement) in self.entries.iter().zip(placements.iter()) {
+            let page_size = glam::vec2(pages_sizes[placement.page].0 as f32, pages_sizes[placement.page].1 as f32);
+            let region_min = glam::vec2(placement.x as f32, placement.y as f32) / page_size;
+            let region_max = glam::vec2((placement.x + entry.width) as f32, (placement.y + entry.height) as f32) / page_size;
+            sprites.insert(entry.id.clone(), Sprite::new_region(Rc::clone(&pages[placement.page]), region_min, region_max));
+            used_pixels += entry.width as u64 * entry.height as u64;
+        }
+
+        // packing quality is reported by get_stats instead of being printed on every build
+        let stats = AtlasStats {
+            n_entries: self.entries.len() as u32,
+            n_pages: pages_sizes.len() as u32,
+            used_pixels,
+            total_pixels: pages_sizes.iter().map(|(width, height)| *width as u64 * *height as u64).sum(),
+        };
+
+        TextureAtlas {
+            sprites,
+            stats,
+        }
+    }
+}
+
+// sprites keep their pages alive
+pub struct TextureAtlas {
+    sprites: HashMap<String, Sprite>,
+    stats: AtlasStats,
+}
+
+impl TextureAtlas {
+    pub fn get_sprite(&self, id: &str) -> Sprite {
+        match self.sprites.get(id) {
+            None => { panic!("Atlas does not contain entry {:?}", id) }
+            Some(sprite) => sprite.clone()
+        }
+    }
+
+    pub fn has_entry(&self, id: &str) -> bool {
+        self.sprites.contains_key(id)
+    }
+
+    pub fn get_stats(&self) -> &AtlasStats {
+        &self.stats
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AtlasStats {
+    pub n_entries: u32,
+    pub n_pages: u32,
+    // pixels actually covered by entries, padding counts as wasted
+    pub used_pixels: u64,
+    pub total_pixels: u64,
+}
+
+impl AtlasStats {
+    // fraction of atlas pages that is not covered by any entry
+    pub fn get_wasted_space(&self) -> f32 {
+        if self.total_pixels == 0 {
+            return 0.0;
+        }
+        1.0 - self.used_pixels as f32 / self.total_pixels as f32
+    }
+}
+
+// bottom left corner of packed entry, excluding padding
+#[derive(Clone, Copy, Debug)]
+struct Placement {
+    page: usize,
+    x: u32,
+    y: u32,
+}
+
+struct Shelf {
+    page: usize,
+    y: u32,
+    height: u32,
+    used_width: u32,
+}
+
+// returns placement for every rectangle, in order of input, and size of every page used
+// pages are trimmed to the area actually taken by shelves, every rectangle must fit into page together with padding
+fn pack_rectangles(sizes: &[(u32, u32)], page_width: u32, page_height: u32) -> (Vec<Placement>, Vec<(u32, u32)>) {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    // tallest first so that shelves get filled with entries of similar height
+    order.sort_by(|a, b| sizes[*b].1.cmp(&sizes[*a].1).then(sizes[*b].0.cmp(&sizes[*a].0)));
+
+    let mut shelves: Vec<Shelf> = vec!();
+    // height taken by shelves on every page
+    let mut pages_used_height: Vec<u32> = vec!();
+    let mut placements: Vec<Option<Placement>> = vec![None; sizes.len()];
+
+    for idx in order {
+        let width = sizes[idx].0 + ENTRY_PADDING;
+        let height = sizes[idx].1 + ENTRY_PADDING;
+
+        let best_shelf = shelves.iter()
+            .enumerate()
+            .filter(|(_, shelf)| shelf.height >= height && shelf.used_width + width + ENTRY_PADDING <= page_width)
+            .min_by_key(|(_, shelf)| shelf.height - height)
+            .map(|(shelf_idx, _)| shelf_idx);
+
+        let shelf_idx = match best_shelf {
+            Some(shelf_idx) => shelf_idx,
+            None => {
+                let free_page = pages_used_height.iter().position(|used_height| used_height + height + ENTRY_PADDING <= page_height);
+                let page = match free_page {
+                    Some(page) => page,
+                    None => {
+                        pages_used_height.push(0);
+                        pages_used_height.len() - 1
+                    }
+                };
+                shelves.push(Shelf {
+                    page,
+                    y: pages_used_height[page],
+                    height,
+                    used_width: 0,
+                });
+                pages_used_height[page] += height;
+                shelves.len() - 1
+            }
+        };
+
+        let shelf = &mut shelves[shelf_idx];
+        placements[idx] = Some(Placement {
+            page: shelf.page,
+            x: shelf.used_width + ENTRY_PADDING,
+            y: shelf.y + ENTRY_PADDING,
+        });
+        shelf.used_width += width;
+    }
+
+    let pages_sizes = pages_used_height.iter()
+        .enumerate()
+        .map(|(page, used_height)| {
+            let used_width = shelves.iter()
+                .filter(|shelf| shelf.page == page)
+                .map(|shelf| shelf.used_width)
+                .max()
+                .unwrap_or(0);
+            (used_width + ENTRY_PADDING, used_height + ENTRY_PADDING)
+        })
+        .collect();
+
+    (placements.into_iter().map(|placement| placement.unwrap()).collect(), pages_sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ENTRY_PADDING, pack_rectangles};
+
+    // pseudo random sizes without pulling random generator into tests
+    fn sizes(count: u32) -> Vec<(u32, u32)> {
+        (0..count).map(|idx| (1 + (idx * 37) % 61, 1 + (idx * 53) % 47)).collect()
+    }
+
+    fn assert_packed(sizes: &[(u32, u32)], page_width: u32, page_height: u32) -> usize {
+        let (placements, pages_sizes) = pack_rectangles(sizes, page_width, page_height);
+        assert_eq!(placements.len(), sizes.len());
+        for (idx, (placement, (width, height))) in placements.iter().zip(sizes.iter()).enumerate() {
+            let (page_w, page_h) = pages_sizes[placement.page];
+            assert!(page_w <= page_width && page_h <= page_height, "page {} of size {}x{} is too big", placement.page, page_w, page_h);
+            assert!(placement.x >= ENTRY_PADDING && placement.y >= ENTRY_PADDING, "entry {} touches page edge", idx);
+            assert!(placement.x + width + ENTRY_PADDING <= page_w && placement.y + height + ENTRY_PADDING <= page_h,
+                    "entry {} at {:?} leaves page of size {}x{}", idx, placement, page_w, page_h);
+            for (other_idx, (other, (other_width, other_height))) in placements.iter().zip(sizes.iter()).enumerate().skip(idx + 1) {
+                if other.page != placement.page {
+                    continue;
+                }
+                // padding must separate entries too
+                let separated = placement.x + width + ENTRY_PADDING <= other.x
+                    || other.x + other_width + ENTRY_PADDING <= placement.x
+                    || placement.y + height + ENTRY_PADDING <= other.y
+                    || other.y + other_height + ENTRY_PADDING <= placement.y;
+                assert!(separated, "entries {} {:?} and {} {:?} overlap", idx, placement, other_idx, other);
+            }
+        }
+        pages_sizes.len()
+    }
+
+    #[test]
+    fn packed_rectangles_do_not_overlap_and_stay_within_page() {
+        assert_eq!(assert_packed(&sizes(40), 1024, 1024), 1);
+    }
+
+    #[test]
+    fn rectangles_that_do_not_fit_go_to_next_pages() {
+        assert!(assert_packed(&sizes(200), 128, 128) > 1);
+    }
+
+    #[test]
+    fn rectangle_as_big_as_page_fits() {
+        let page_size = 16 + 2 * ENTRY_PADDING;
+        assert_eq!(assert_packed(&[(16, 16), (16, 16)], page_size, page_size), 2);
+    }
+}
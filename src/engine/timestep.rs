@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+// how engine advances simulation between rendered frames
+#[derive(Clone, Debug, PartialEq)]
+pub enum Timestep {
+    // update is called once per rendered frame with time elapsed since previous frame
+    VARIABLE,
+    // update is called with constant delta time, as many times as needed to catch up with real time
+    FIXED { config: FixedTimestepConfig },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixedTimestepConfig {
+    tick_duration: Duration,
+    // frames that would need more updates than this drop the rest of accumulated time
+    // so that slow update does not make every next frame even slower
+    max_catch_up_steps: u32,
+}
+
+impl FixedTimestepConfig {
+    pub fn new(ticks_per_second: u32, max_catch_up_steps: u32) -> FixedTimestepConfig {
+        if ticks_per_second == 0 || max_catch_up_steps == 0 {
+            panic!("Fixed timestep needs positive tick rate and number of catch up steps");
+        }
+        FixedTimestepConfig {
+            tick_duration: Duration::from_secs(1) / ticks_per_second,
+            max_catch_up_steps,
+        }
+    }
+
+    // exact tick length, e.g. as stored in input recording
+    pub fn from_tick_duration(tick_duration: Duration, max_catch_up_steps: u32) -> FixedTimestepConfig {
+        if tick_duration.is_zero() || max_catch_up_steps == 0 {
+            panic!("Fixed timestep needs positive tick duration and number of catch up steps");
+        }
+        FixedTimestepConfig {
+            tick_duration,
+            max_catch_up_steps,
+        }
+    }
+
+    pub fn get_tick_duration(&self) -> &Duration {
+        &self.tick_duration
+    }
+
+    pub fn get_max_catch_up_steps(&self) -> &u32 {
+        &self.max_catch_up_steps
+    }
+}
+
+// accumulates real time passed between frames and hands it out in ticks of constant length
+pub struct FixedTimestepAccumulator {
+    config: FixedTimestepConfig,
+    accumulated: Duration,
+}
+
+impl FixedTimestepAccumulator {
+    pub fn new(config: FixedTimestepConfig) -> FixedTimestepAccumulator {
+        FixedTimestepAccumulator {
+            config,
+            accumulated: Duration::from_secs(0),
+        }
+    }
+
+    // returns how many updates must be run to catch up with time passed
+    pub fn advance(&mut self, frame_time: Duration) -> u32 {
+        self.accumulated += frame_time;
+        let mut steps = 0;
+        while self.accumulated >= self.config.tick_duration {
+            if steps == self.config.max_catch_up_steps {
+                // keep only the part of a tick so that interpolation still works
+                self.accumulated = Duration::from_nanos((self.accumulated.as_nanos() % self.config.tick_duration.as_nanos()) as u64);
+                break;
+            }
+            self.accumulated -= self.config.tick_duration;
+            steps += 1;
+        }
+        steps
+    }
+
+    // how far between last and next update rendered frame is, in range [0, 1)
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        (self.accumulated.as_secs_f64() / self.config.tick_duration.as_secs_f64()) as f32
+    }
+
+    pub fn get_config(&self) -> &FixedTimestepConfig {
+        &self.config
+    }
+}
@@ -1,88 +1,324 @@
+use std::rc::Rc;
+
 use sdl2::event::Event;
-use sdl2::keyboard::Keycode;
-use sdl2::mouse::{MouseButton, MouseWheelDirection};
 
 use crate::engine::api::camera::CameraGameObject;
-use crate::engine::api::drawable::{Drawable, UpdateContext};
-use crate::engine::api::game_api::{CameraConfig, GameController};
+use crate::engine::api::camera_controller::CameraController;
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
+use crate::engine::api::colour::Colour;
+use crate::engine::api::console::Console;
+use crate::engine::api::engine_utilities::EngineUtilities;
+use crate::engine::api::game_api::{CameraConfig, GameController, HUD_LAYER, LayerMask, Projection, ViewportRect};
 use crate::engine::api::maths::quadrangle::Quadrangle;
 use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
+use crate::engine::api::post_processing::PostProcessing;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::opengl_context::OpenglContext;
 
-const CAMERA_SPEED: f32 = 0.3;
+// world units per second
+const CAMERA_SPEED: f32 = 9.0;
+// how fast camera catches up with panning, per second
+const CAMERA_FOLLOW_SPEED: f32 = 8.0;
+const MIN_WORLD_UNITS_PER_PIXEL: f32 = 0.004;
+const MAX_WORLD_UNITS_PER_PIXEL: f32 = 0.05;
+// all games are flat, at 1080 pixels high window this shows about as much as the old perspective camera did
+const WORLD_UNITS_PER_PIXEL: f32 = 0.016;
+// each game is drawn on its own layer so that split screen pane shows only its game, lower layers are reserved by engine
+const FIRST_GAME_LAYER: u32 = 2;
+// panes are told apart by slightly different backgrounds
+const PANE_CLEAR_COLOURS: [Colour; 3] = [
+    Colour::new_f(0.05, 0.05, 0.1, 1.0),
+    Colour::new_f(0.1, 0.05, 0.05, 1.0),
+    Colour::new_f(0.05, 0.1, 0.05, 1.0),
+];
 
+// what split screen pane of a game looks at
+pub struct PaneFocus {
+    center: glam::Vec2,
+    world_units_per_pixel: f32,
+}
+
+impl PaneFocus {
+    pub fn new(center: glam::Vec2, world_units_per_pixel: f32) -> PaneFocus {
+        PaneFocus {
+            center,
+            world_units_per_pixel,
+        }
+    }
+}
 
 pub struct GamesRoot {
     games: Vec<Box<dyn Drawable>>,
-    camera: CameraGameObject, //todo: camera should be game object
+    // used by console to pick a game
+    game_names: Vec<String>,
+    pane_focuses: Vec<PaneFocus>,
+    // passes of a game are applied to its split screen pane only, main camera shows all games so it gets none
+    game_post_processing: Vec<Option<Rc<PostProcessing>>>,
+    camera: CameraController,
+    // one per game, used instead of main camera while split screen is on
+    pane_cameras: Vec<CameraController>,
+    split_screen: bool,
+    // pane under mouse, input moves and zooms its camera
+    hovered_pane: usize,
+    // mouse position at z == 0 plane, zoom keeps this point in place
+    mouse_world_position: Option<glam::Vec3>,
 }
 
 impl GamesRoot {
-    pub fn new(games: Vec<Box<dyn Drawable>>) -> GamesRoot {
+    pub fn new(engine_utilities: Rc<EngineUtilities>, games: Vec<(&str, Box<dyn Drawable>, PaneFocus)>) -> GamesRoot {
+        let mut game_names = vec!();
+        let mut game_objects = vec!();
+        let mut pane_focuses = vec!();
+        for (name, game, focus) in games {
+            game_names.push(String::from(name));
+            game_objects.push(game);
+            pane_focuses.push(focus);
+        }
+        let console = engine_utilities.get_console();
+        console.register_command("camera", "camera <x> <y> [zoom] - moves controlled camera to point, zoom is world units per pixel");
+        console.register_command("game", &format!("game <{}> - leaves split screen and shows given game", game_names.join("|")));
         GamesRoot {
-            games,
-            camera: CameraGameObject::new(
-                glam::vec3(-10.0, 10.0, 20.0),
-                glam::vec3(0.0, 1.0, 0.0),
-                glam::vec3(-10.0, 10.0, -1.0),
-            ),
+            games: game_objects,
+            game_post_processing: game_names.iter().map(|_| None).collect(),
+            game_names,
+            camera: GamesRoot::create_camera(glam::vec2(-10.0, 10.0), WORLD_UNITS_PER_PIXEL),
+            pane_cameras: pane_focuses.iter()
+                .map(|focus| GamesRoot::create_camera(focus.center, focus.world_units_per_pixel))
+                .collect(),
+            pane_focuses,
+            split_screen: false,
+            hovered_pane: 0,
+            mouse_world_position: None,
+        }
+    }
+
+    pub fn with_post_processing(mut self, game_name: &str, post_processing: Rc<PostProcessing>) -> GamesRoot {
+        match self.game_names.iter().position(|name| name == game_name) {
+            None => panic!("There is no game named {:?}", game_name),
+            Some(game_idx) => self.game_post_processing[game_idx] = Some(post_processing)
+        }
+        self
+    }
+
+    fn create_camera(center: glam::Vec2, world_units_per_pixel: f32) -> CameraController {
+        let mut camera = CameraGameObject::new(
+            center.extend(20.0),
+            glam::vec3(0.0, 1.0, 0.0),
+            center.extend(-1.0),
+        );
+        camera.set_projection(Projection::new_orthographic(world_units_per_pixel));
+        let mut camera = CameraController::new(camera);
+        camera.set_follow_speed(CAMERA_FOLLOW_SPEED);
+        camera.set_zoom_limits(MIN_WORLD_UNITS_PER_PIXEL, MAX_WORLD_UNITS_PER_PIXEL);
+        camera
+    }
+
+    fn get_game_layer(game_idx: usize) -> u32 {
+        FIRST_GAME_LAYER + game_idx as u32
+    }
+
+    // panes split window into equally wide columns, pane shows only its own game
+    fn get_pane_camera_config(&self, pane_idx: usize) -> CameraConfig {
+        let layer_mask = (0..self.games.len())
+            .filter(|game_idx| *game_idx != pane_idx)
+            .fold(LayerMask::all().without(HUD_LAYER), |mask, game_idx| mask.without(GamesRoot::get_game_layer(game_idx)));
+        let camera_config = self.pane_cameras[pane_idx].get_current_config()
+            .with_viewport(self.get_pane_viewport(pane_idx))
+            .with_clear_colour(PANE_CLEAR_COLOURS[pane_idx % PANE_CLEAR_COLOURS.len()])
+            .with_layer_mask(layer_mask);
+        match &self.game_post_processing[pane_idx] {
+            None => camera_config,
+            Some(post_processing) => camera_config.with_post_processing(Rc::clone(post_processing))
+        }
+    }
+
+    fn get_pane_viewport(&self, pane_idx: usize) -> ViewportRect {
+        let pane_width = 1.0 / self.pane_cameras.len() as f32;
+        ViewportRect::new(pane_idx as f32 * pane_width, 0.0, pane_width, 1.0)
+    }
+
+    fn get_main_camera_config(&self) -> CameraConfig {
+        self.camera.get_current_config().with_layer_mask(LayerMask::all().without(HUD_LAYER))
+    }
+
+    // games must be visible to hud camera too, otherwise they would not be asked to render their hud
+    fn get_hud_camera_config(&self, game_indices: impl Iterator<Item=usize>, viewport: ViewportRect) -> CameraConfig {
+        let layer_mask = game_indices.fold(LayerMask::only(HUD_LAYER), |mask, game_idx| mask.with(GamesRoot::get_game_layer(game_idx)));
+        CameraConfig::new_screen_space()
+            .with_viewport(viewport)
+            .with_layer_mask(layer_mask)
+    }
+
+    // camera that is moved and zoomed by input
+    fn get_controlled_camera_config(&self) -> CameraConfig {
+        match self.split_screen {
+            true => self.get_pane_camera_config(self.hovered_pane),
+            false => self.get_main_camera_config()
+        }
+    }
+
+    fn get_controlled_camera_mut(&mut self) -> &mut CameraController {
+        match self.split_screen {
+            true => &mut self.pane_cameras[self.hovered_pane],
+            false => &mut self.camera
+        }
+    }
+
+    fn handle_console_commands(&mut self, console: &Console) {
+        for command in console.take_commands("camera") {
+            match (command.parse_arg::<f32>(0), command.parse_arg::<f32>(1)) {
+                (Ok(x), Ok(y)) => {
+                    let camera = self.get_controlled_camera_mut();
+                    camera.set_target(glam::vec2(x, y));
+                    camera.snap_to_target();
+                    if command.get_args().len() > 2 {
+                        match command.parse_arg::<f32>(2) {
+                            Ok(zoom_level) => camera.set_zoom_level(zoom_level),
+                            Err(err) => console.print(err)
+                        }
+                    }
+                }
+                (Err(err), _) | (_, Err(err)) => console.print(err)
+            }
+        }
+        for command in console.take_commands("game") {
+            let name: String = match command.parse_arg(0) {
+                Ok(name) => name,
+                Err(err) => {
+                    console.print(err);
+                    continue;
+                }
+            };
+            match self.game_names.iter().position(|game_name| *game_name == name) {
+                None => console.print(format!("game: unknown game {}, games are {}", name, self.game_names.join(", "))),
+                Some(game_idx) => {
+                    // main camera looks at game the way its pane does
+                    self.split_screen = false;
+                    self.mouse_world_position = None;
+                    let focus = &self.pane_focuses[game_idx];
+                    self.camera.set_target(focus.center);
+                    self.camera.snap_to_target();
+                    self.camera.set_zoom_level(focus.world_units_per_pixel);
+                }
+            }
+        }
+    }
+
+    // in split screen game sees mouse through camera of its pane
+    // games rendered later are drawn on top so they get events first
+    // mouse events go to games under the mouse before the others, in split screen that is game of hovered pane
+    fn get_event_order(&self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> Vec<usize> {
+        let topmost_first = (0..self.games.len()).rev();
+        match event {
+            Event::MouseButtonDown { .. } | Event::MouseButtonUp { .. } | Event::MouseMotion { .. } | Event::MouseWheel { .. } => {
+                let (under_mouse, rest): (Vec<usize>, Vec<usize>) = topmost_first.partition(|game_idx| match self.split_screen {
+                    true => *game_idx == self.hovered_pane,
+                    false => self.games[*game_idx].is_under_mouse(context, &self.get_game_update_context(*game_idx, update_context))
+                });
+                under_mouse.into_iter().chain(rest).collect()
+            }
+            _ => topmost_first.collect()
+        }
+    }
+
+    fn get_game_update_context(&self, game_idx: usize, update_context: &UpdateContext) -> UpdateContext {
+        match self.split_screen {
+            true => update_context.with_camera_config(self.get_pane_camera_config(game_idx)),
+            false => update_context.with_camera_config(self.get_main_camera_config())
         }
     }
 }
 
 impl Drawable for GamesRoot {
+    // called once per camera, every camera renders games on layers it shows
     fn render(&mut self, render_util: &RenderUtil) {
-        self.games.iter_mut().for_each(|game| {game.render(render_util)});
+        for (game_idx, game) in self.games.iter_mut().enumerate() {
+            if !render_util.is_layer_visible(GamesRoot::get_game_layer(game_idx)) {
+                continue;
+            }
+            match self.split_screen {
+                true => self.pane_cameras[game_idx].set_viewport_size(render_util.get_window_size()),
+                false => self.camera.set_viewport_size(render_util.get_window_size())
+            }
+            game.render(render_util);
+        }
     }
 
     fn update(&mut self, update_context: &UpdateContext) {
-        for game in self.games.iter_mut() {
-            game.update(update_context);
+        let input = update_context.get_input();
+        if input.just_pressed("toggle_split_screen") {
+            self.split_screen = !self.split_screen;
+            self.mouse_world_position = None;
+        }
+        self.handle_console_commands(&update_context.get_engine_utilities().get_console());
+        let horizontal = input.axis_value("camera_right") - input.axis_value("camera_left") + input.axis_value("camera_horizontal");
+        let vertical = input.axis_value("camera_up") - input.axis_value("camera_down") + input.axis_value("camera_vertical");
+        let direction = glam::vec2(horizontal, vertical).clamp_length_max(1.0);
+        if direction != glam::Vec2::ZERO {
+            let distance = CAMERA_SPEED * update_context.get_delta_time().as_secs_f32();
+            self.get_controlled_camera_mut().move_target_by(direction * distance);
+        }
+
+        let zoom = input.axis_value("zoom");
+        if zoom != 0.0 {
+            let mouse_world_position = self.mouse_world_position;
+            let camera = self.get_controlled_camera_mut();
+            match &mouse_world_position {
+                None => camera.zoom_by(zoom),
+                Some(mouse_world_position) => camera.zoom_to_cursor(zoom, mouse_world_position)
+            }
+        }
+        self.camera.update(*update_context.get_delta_time());
+        self.pane_cameras.iter_mut().for_each(|camera| camera.update(*update_context.get_delta_time()));
+
+        for game_idx in 0..self.games.len() {
+            let game_update_context = self.get_game_update_context(game_idx, update_context);
+            self.games[game_idx].update(&game_update_context);
         }
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
-        match event {
-            sdl2::event::Event::KeyDown { keycode, .. } => {
-                match keycode.unwrap() {
-                    Keycode::Left => {
-                        self.camera.move_by(glam::Vec3::new(-CAMERA_SPEED, 0.0, 0.0))
-                    }
-                    Keycode::Right => {
-                        self.camera.move_by(glam::Vec3::new(CAMERA_SPEED, 0.0, 0.0))
-                    }
-                    Keycode::Down => {
-                        self.camera.move_by(glam::Vec3::new(0.0, -CAMERA_SPEED, 0.0))
-                    }
-                    Keycode::Up => {
-                        self.camera.move_by(glam::Vec3::new(0.0, CAMERA_SPEED, 0.0))
-                    }
-                    _ => {}
+    // camera is driven by input actions in update
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
+        // wheel events refresh it too since camera could have moved under still mouse
+        if let Event::MouseMotion { .. } | Event::MouseWheel { .. } = event {
+            if self.split_screen {
+                let mouse_position = update_context.get_sdl_mouse_position();
+                if let Some(pane_idx) = (0..self.pane_cameras.len())
+                    .find(|pane_idx| context.sdl_window_to_viewport_space(mouse_position, &self.get_pane_camera_config(*pane_idx)).is_some()) {
+                    self.hovered_pane = pane_idx;
                 }
             }
-            sdl2::event::Event::MouseWheel {
-                y, direction, ..
-            } => {
-                if y != &0 {
-                    let amount = *y as f32 * {
-                        if direction == &MouseWheelDirection::Normal { 1.0 } else { -1.0 }
-                    };
-                    self.camera.zoom_by(amount);
-                }
-            },
-            _ => {
-                for game in self.games.iter_mut() {
-                    game.handle_event(event, context, update_context);
-                }
+            self.mouse_world_position = context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &self.get_controlled_camera_config());
+        }
+        for game_idx in self.get_event_order(event, context, update_context) {
+            let game_update_context = self.get_game_update_context(game_idx, update_context);
+            if self.games[game_idx].handle_event(event, context, &game_update_context).is_consumed() {
+                return EventResult::CONSUMED;
             }
         }
+        EventResult::IGNORED
     }
 }
 
 impl GameController for GamesRoot {
     fn get_camera_config(&self) -> CameraConfig {
-        self.camera.get_current_config()
+        self.get_controlled_camera_config()
+    }
+
+    // hud of each game is drawn after its world by screen space camera covering the same part of window
+    fn get_cameras(&self) -> Vec<CameraConfig> {
+        match self.split_screen {
+            true => (0..self.pane_cameras.len())
+                .flat_map(|pane_idx| vec![
+                    self.get_pane_camera_config(pane_idx),
+                    self.get_hud_camera_config(std::iter::once(pane_idx), self.get_pane_viewport(pane_idx)),
+                ])
+                .collect(),
+            false => vec![
+                self.get_main_camera_config(),
+                self.get_hud_camera_config(0..self.games.len(), ViewportRect::full()),
+            ]
+        }
     }
 }
 
@@ -1,12 +1,16 @@
 #![feature(drain_filter)]
 #![feature(const_fn_floating_point_arithmetic)]
 
+use std::env;
+use std::path::Path;
+
 use engine::api::maths::vertex;
 use osu::osu_game::OsuGame;
 
 use crate::chess::chess_game::ChessGame;
 use crate::engine::engine::Engine;
-use crate::games_root::GamesRoot;
+use crate::engine::timestep::{FixedTimestepConfig, Timestep};
+use crate::games_root::{GamesRoot, PaneFocus};
 use crate::submarine::submarine_game::SubmarineGame;
 
 mod chess;
@@ -15,18 +19,98 @@ mod games_root;
 mod osu;
 mod submarine;
 
+const HEADLESS_FLAG: &str = "--headless";
+const FIXED_TIMESTEP_FLAG: &str = "--fixed-timestep";
+const RECORD_FLAG: &str = "--record";
+const REPLAY_FLAG: &str = "--replay";
+const DEFAULT_HEADLESS_FRAMES: u32 = 120;
+const DEFAULT_HEADLESS_RESOLUTION: (u32, u32) = (1920, 1080);
+const DEFAULT_TICKS_PER_SECOND: u32 = 60;
+const MAX_CATCH_UP_STEPS: u32 = 5;
+
 fn main() {
-    let mut engine = Engine::new();
+    let args: Vec<String> = env::args().collect();
+    let mut engine = create_engine(&args);
+    if !args.iter().any(|arg| arg == REPLAY_FLAG) {
+        engine.set_timestep(parse_timestep(&args));
+    }
+    if let Some(recording_path) = get_flag_value(&args, RECORD_FLAG) {
+        engine.start_recording(Path::new(recording_path)).expect("Could not create input recording");
+    }
     let osu_game = OsuGame::new(engine.get_engine_utilities());
     let chess_game = ChessGame::new(engine.get_engine_utilities());
     let submarine_game = SubmarineGame::new(engine.get_engine_utilities());
+    let osu_post_processing = osu_game.get_post_processing();
+    let chess_post_processing = chess_game.get_post_processing();
     let games_root = GamesRoot::new(
+        engine.get_engine_utilities(),
         vec![
-            Box::new(osu_game),
-            Box::new(chess_game),
-            Box::new(submarine_game),
+            ("osu", Box::new(osu_game), PaneFocus::new(glam::vec2(-10.3, -10.0), 0.05)),
+            ("chess", Box::new(chess_game), PaneFocus::new(glam::vec2(4.0, 4.0), 0.016)),
+            ("submarine", Box::new(submarine_game), PaneFocus::new(glam::vec2(-6.0, 9.5), 0.02)),
         ]
-    );
+    )
+        .with_post_processing("osu", osu_post_processing)
+        .with_post_processing("chess", chess_post_processing);
     engine.set_game(games_root);
     engine.start();
-}
\ No newline at end of file
+}
+
+// usage: `TestRust --headless [num_frames] [WIDTHxHEIGHT]` or `TestRust --replay <recording> [WIDTHxHEIGHT]`
+fn create_engine(args: &[String]) -> Engine {
+    if let Some(flag_idx) = args.iter().position(|arg| arg == REPLAY_FLAG) {
+        let recording_path = args.get(flag_idx + 1).expect("Path to recording must follow replay flag");
+        // recording knows its window size, resolution given here must match it
+        let resolution = args.get(flag_idx + 2).filter(|arg| !arg.starts_with("--")).map(|arg| parse_resolution(arg));
+        // recording knows its timestep too, flag given here must match it
+        let timestep = args.iter().any(|arg| arg == FIXED_TIMESTEP_FLAG).then(|| parse_timestep(args));
+        return Engine::new_replay(Path::new(recording_path), resolution, timestep).expect("Could not load input recording");
+    }
+    match args.iter().position(|arg| arg == HEADLESS_FLAG) {
+        None => Engine::new(),
+        Some(flag_idx) => {
+            let num_frames = args.get(flag_idx + 1)
+                .map(|arg| arg.parse::<u32>().expect("Number of headless frames must be positive integer"))
+                .unwrap_or(DEFAULT_HEADLESS_FRAMES);
+            let resolution = args.get(flag_idx + 2)
+                .map(|arg| parse_resolution(arg))
+                .unwrap_or(DEFAULT_HEADLESS_RESOLUTION);
+            Engine::new_headless(resolution.0, resolution.1, num_frames)
+        }
+    }
+}
+
+// usage: `TestRust --fixed-timestep [ticks_per_second]`
+fn parse_timestep(args: &[String]) -> Timestep {
+    match args.iter().position(|arg| arg == FIXED_TIMESTEP_FLAG) {
+        None => Timestep::VARIABLE,
+        Some(flag_idx) => {
+            // tick rate is optional, next flag is not mistaken for it
+            let ticks_per_second = args.get(flag_idx + 1)
+                .filter(|arg| !arg.starts_with("--"))
+                .map(|arg| match arg.parse::<u32>() {
+                    Ok(ticks_per_second) if ticks_per_second > 0 => ticks_per_second,
+                    _ => panic!("Ticks per second must be positive integer, got {}", arg)
+                })
+                .unwrap_or(DEFAULT_TICKS_PER_SECOND);
+            Timestep::FIXED { config: FixedTimestepConfig::new(ticks_per_second, MAX_CATCH_UP_STEPS) }
+        }
+    }
+}
+
+fn get_flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a String> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .map(|flag_idx| args.get(flag_idx + 1).expect(&format!("Value must follow {} flag", flag)))
+}
+
+fn parse_resolution(arg: &str) -> (u32, u32) {
+    let parts: Vec<&str> = arg.split('x').collect();
+    if parts.len() != 2 {
+        panic!("Cannot parse resolution {}, expected format is WIDTHxHEIGHT", arg);
+    }
+    (
+        parts[0].parse::<u32>().expect("Resolution width must be positive integer"),
+        parts[1].parse::<u32>().expect("Resolution height must be positive integer"),
+    )
+}
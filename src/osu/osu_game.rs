@@ -9,37 +9,39 @@ use soloud::*;
 
 use crate::engine::api::audio::AudioResource;
 use crate::engine::api::colour::{Colour, GREEN, WHITE};
-use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
 use crate::engine::api::engine_utilities::EngineUtilities;
 use crate::engine::api::maths::circle::Circle;
 use crate::engine::api::maths::quadrangle::Quadrangle;
 use crate::engine::api::maths::shapes_common::Area;
 use crate::engine::api::maths::vertex::ColoredVertexDataLayout;
+use crate::engine::api::post_processing::{BuiltInPass, PostProcessing};
 use crate::engine::api::render_util::RenderUtil;
-use crate::engine::api::text_game_object::TextGameObject;
+use crate::engine::api::text_game_object::{TEXT_SCALE, TextGameObject};
+use crate::engine::api::ui::{Anchor, UiLayout, UiLength, UiNode};
 use crate::engine::engine::Engine;
 use crate::engine::opengl_context::OpenglContext;
 use crate::osu::playing_field::PlayingField;
 
 pub struct OsuGame {
     playing_field: PlayingField,
-    score_label: TextGameObject,
-    score_text: TextGameObject,
-    game_time_text: TimerGameObject,
-    start_game_button: TextGameObject,
-    stop_game_button: TextGameObject,
+    hud: Hud,
     paused: bool,
     senungoku: Rc<AudioResource>,
+    // applied only to camera showing this game
+    post_processing: Rc<PostProcessing>,
 }
 
 const SCOREBOARD_PADDING: f32 = 4.0;
-const TEXT_TOP_PADDING: f32 = 1.0;
-const UI_LEFT_PADDING: f32 = 1.40;
-const TIMER_LEFT_PADDING: f32 = UI_LEFT_PADDING / 2.0 - 0.25;
-const TEXT_Y_OFFSET: f32 = 1.0;
-const TIMER_Y_OFFSET: f32 = 10.0;
-const BUTTONS_Y_OFFSET: f32 = 15.0;
-const BUTTONS_PADDING: f32 = 1.0;
+// hud is laid out in pixels of the window
+const HUD_MARGIN: UiLength = UiLength::PERCENT(2.0);
+const HUD_TEXT_PADDING: f32 = 10.0;
+const HUD_LINE_HEIGHT: f32 = 40.0;
+// font glyphs are drawn at half of their pixel size
+const HUD_TEXT_SCALE: f32 = 0.5 / TEXT_SCALE;
+const BUTTON_SIZE: glam::Vec2 = glam::const_vec2!([140.0, 50.0]);
+const BUTTONS_MARGIN: f32 = 24.0;
+const BUTTONS_SPACING: f32 = 10.0;
 
 impl OsuGame {
     pub fn new(engine_utilities: Rc<EngineUtilities>) -> OsuGame {
@@ -52,116 +54,155 @@ impl OsuGame {
             &playing_field_size,
             engine_utilities.get_resource_manager());
 
+        let post_processing = Rc::new(PostProcessing::new());
+        post_processing.register_built_in_passes(&*engine_utilities.get_resource_manager());
+
         let senungoku = engine_utilities.get_resource_manager().fetch_audio("osu/audio/a_cruel_angel_thesis.ogg");
         engine_utilities.get_audio_manager().load_paused(Rc::clone(&senungoku));
 
         let text_material = engine_utilities.get_resource_manager().fetch_shader_material("osu/shaders/character");
         let sized_font = engine_utilities.get_resource_manager().fetch_font("osu/fonts/go3v2.ttf");
-        let score_label = TextGameObject::new(Rc::clone(&sized_font), "SCORE",
-                                              glam::vec3(playing_field_position.x + playing_field_size.x + UI_LEFT_PADDING,
-                                                         playing_field_top_edge - TEXT_TOP_PADDING,
-                                                         0.0,
-                                              ),
-                                              text_material.clone(),
-                                              WHITE,
+        let create_text = |text: &str, line: f32, colour: Colour| {
+            let mut text = TextGameObject::new(Rc::clone(&sized_font), text,
+                                               glam::vec3(HUD_TEXT_PADDING, HUD_TEXT_PADDING + line * HUD_LINE_HEIGHT, 0.0),
+                                               text_material.clone(),
+                                               colour,
+            );
+            text.get_transform_mut().set_scale(glam::Vec3::splat(HUD_TEXT_SCALE));
+            text
+        };
+
+        let score_panel = ScorePanel {
+            score_label: create_text("SCORE", 1.0, WHITE),
+            score_text: create_text("0", 0.0, WHITE),
+        };
+        let score_panel_layout = UiLayout::new(Anchor::TOP_RIGHT, UiLength::PIXELS(200.0), UiLength::PIXELS(2.0 * HUD_LINE_HEIGHT + HUD_TEXT_PADDING))
+            .with_offset(HUD_MARGIN, HUD_MARGIN);
+
+        let timer_layout = UiLayout::new(Anchor::TOP_CENTRE, UiLength::PIXELS(260.0), UiLength::PIXELS(HUD_LINE_HEIGHT + HUD_TEXT_PADDING))
+            .with_offset(UiLength::PIXELS(0.0), HUD_MARGIN);
+        let timer = TimerGameObject::new(create_text("", 0.0, GREEN), senungoku.get_duration());
+
+        // stop button sits in the corner and start button right above it
+        let button_layout = UiLayout::new(Anchor::BOTTOM_RIGHT, UiLength::PIXELS(BUTTON_SIZE.x), UiLength::PIXELS(BUTTON_SIZE.y));
+        let stop_game_button = UiNode::new(
+            button_layout.with_offset(UiLength::PIXELS(BUTTONS_MARGIN), UiLength::PIXELS(BUTTONS_MARGIN)),
+            create_text("STOP", 0.0, WHITE),
         );
-
-        let score_text = TextGameObject::new(Rc::clone(&sized_font), "0",
-                                             glam::vec3(playing_field_position.x + playing_field_size.x + UI_LEFT_PADDING,
-                                                        playing_field_top_edge - TEXT_TOP_PADDING - TEXT_Y_OFFSET,
-                                                        0.0,
-                                             ),
-                                             text_material.clone(),
-                                             WHITE,
-        );
-
-        let start_game_button = TextGameObject::new(Rc::clone(&sized_font), "START",
-                                                    glam::vec3(playing_field_position.x + playing_field_size.x + UI_LEFT_PADDING,
-                                                               playing_field_top_edge - BUTTONS_Y_OFFSET,
-                                                               0.0,
-                                                    ),
-                                                    text_material.clone(),
-                                                    WHITE,
-        );
-
-        let stop_game_button = TextGameObject::new(Rc::clone(&sized_font), "STOP",
-                                                   glam::vec3(playing_field_position.x + playing_field_size.x + UI_LEFT_PADDING,
-                                                              playing_field_top_edge - BUTTONS_Y_OFFSET - BUTTONS_PADDING,
-                                                              0.0,
-                                                   ),
-                                                   text_material.clone(),
-                                                   WHITE,
-        );
-
-        let timer_text = TextGameObject::new(sized_font, "",
-                                             glam::vec3(playing_field_position.x + playing_field_size.x + TIMER_LEFT_PADDING,
-                                                        playing_field_top_edge - TIMER_Y_OFFSET,
-                                                        0.0,
-                                             ),
-                                             text_material,
-                                             GREEN,
+        let start_game_button = UiNode::new(
+            button_layout.with_offset(UiLength::PIXELS(BUTTONS_MARGIN), UiLength::PIXELS(BUTTONS_MARGIN + BUTTON_SIZE.y + BUTTONS_SPACING)),
+            create_text("START", 0.0, WHITE),
         );
 
         OsuGame {
             playing_field,
-            score_label,
-            score_text,
-            game_time_text: TimerGameObject::new(timer_text, senungoku.get_duration()),
-            start_game_button,
-            stop_game_button,
+            hud: Hud {
+                score_panel: UiNode::new(score_panel_layout, score_panel),
+                game_time: UiNode::new(timer_layout, timer),
+                start_game_button,
+                stop_game_button,
+            },
             paused: true,
             senungoku,
+            post_processing,
         }
     }
+
+    pub fn get_post_processing(&self) -> Rc<PostProcessing> {
+        Rc::clone(&self.post_processing)
+    }
+
+    fn start(&mut self, engine_utilities: &EngineUtilities) {
+        engine_utilities.get_audio_manager().unpause(self.senungoku.get_id().clone());
+        // rings glow while game is running
+        self.post_processing.set_enabled(BuiltInPass::BLOOM.get_name(), true);
+        self.paused = false;
+    }
+
+    fn stop(&mut self, engine_utilities: &EngineUtilities) {
+        engine_utilities.get_audio_manager().pause(self.senungoku.get_id().clone());
+        self.post_processing.set_enabled(BuiltInPass::BLOOM.get_name(), false);
+        self.paused = true;
+    }
 }
 
 impl<'a> Drawable for OsuGame {
     fn render(&mut self, render_util: &RenderUtil) {
         self.playing_field.render(render_util);
-        self.score_label.render(render_util);
-        self.score_text.render(render_util);
-        self.start_game_button.render(render_util);
-        self.stop_game_button.render(render_util);
-        self.game_time_text.render(render_util);
+        self.hud.render(render_util);
     }
 
     fn update(&mut self, update_context: &UpdateContext) {
+        let input = update_context.get_input();
+        if self.paused && input.just_pressed("start_game") {
+            self.start(update_context.get_engine_utilities());
+        } else if !self.paused && input.just_pressed("stop_game") {
+            self.stop(update_context.get_engine_utilities());
+        }
+
         if !self.paused {
             self.playing_field.update(update_context);
-            self.score_text.set_text(self.playing_field.get_total_score().to_string());
-            self.game_time_text.update_with_duration(update_context.get_engine_utilities().get_audio_manager().get_audio_play_time(self.senungoku.get_id().clone()))
+            self.hud.score_panel.get_content_mut().score_text.set_text(self.playing_field.get_total_score().to_string());
+            self.hud.game_time.get_content_mut().update_with_duration(update_context.get_engine_utilities().get_audio_manager().get_audio_play_time(self.senungoku.get_id().clone()))
         }
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
-        match context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &update_context.get_camera_config()) {
-            None => {}
-            Some(world_mouse_position) => {
-                match event {
-                    sdl2::event::Event::MouseButtonDown { mouse_btn, .. } => {
-                        match mouse_btn {
-                            MouseButton::Left => {
-                                if self.start_game_button.contains_point(&(world_mouse_position.x, world_mouse_position.y)) && self.paused {
-                                    update_context.get_engine_utilities().get_audio_manager().unpause(self.senungoku.get_id().clone());
-                                    self.paused = false
-                                }
-
-                                if self.stop_game_button.contains_point(&(world_mouse_position.x, world_mouse_position.y)) && !self.paused {
-                                    update_context.get_engine_utilities().get_audio_manager().pause(self.senungoku.get_id().clone());
-                                    self.paused = true
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
+        // buttons are drawn over playing field so they get clicks first
+        if let sdl2::event::Event::MouseButtonDown { mouse_btn: MouseButton::Left, .. } = event {
+            let mouse_position = update_context.get_sdl_mouse_position();
+            let camera_config = update_context.get_camera_config();
+            if self.paused && self.hud.start_game_button.contains_sdl_point(mouse_position, context, camera_config) {
+                self.start(update_context.get_engine_utilities());
+                return EventResult::CONSUMED;
+            }
+            if !self.paused && self.hud.stop_game_button.contains_sdl_point(mouse_position, context, camera_config) {
+                self.stop(update_context.get_engine_utilities());
+                return EventResult::CONSUMED;
             }
         }
 
         if !self.paused {
-            self.playing_field.handle_event(event, context, update_context)
+            return self.playing_field.handle_event(event, context, update_context);
         }
+        EventResult::IGNORED
+    }
+
+    fn is_under_mouse(&self, context: &OpenglContext, update_context: &UpdateContext) -> bool {
+        let mouse_position = update_context.get_sdl_mouse_position();
+        let camera_config = update_context.get_camera_config();
+        self.hud.start_game_button.contains_sdl_point(mouse_position, context, camera_config)
+            || self.hud.stop_game_button.contains_sdl_point(mouse_position, context, camera_config)
+            || self.playing_field.is_under_mouse(context, update_context)
+    }
+}
+
+// drawn by screen space camera, stays in place when world camera moves
+struct Hud {
+    score_panel: UiNode<ScorePanel>,
+    game_time: UiNode<TimerGameObject>,
+    start_game_button: UiNode<TextGameObject>,
+    stop_game_button: UiNode<TextGameObject>,
+}
+
+impl Drawable for Hud {
+    fn render(&mut self, render_util: &RenderUtil) {
+        self.score_panel.render(render_util);
+        self.game_time.render(render_util);
+        self.start_game_button.render(render_util);
+        self.stop_game_button.render(render_util);
+    }
+}
+
+struct ScorePanel {
+    score_label: TextGameObject,
+    score_text: TextGameObject,
+}
+
+impl Drawable for ScorePanel {
+    fn render(&mut self, render_util: &RenderUtil) {
+        self.score_label.render(render_util);
+        self.score_text.render(render_util);
     }
 }
 
@@ -208,7 +249,7 @@ impl Drawable for TimerGameObject {
         self.timer_text.update(update_context)
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
         self.timer_text.handle_event(event, context, update_context)
     }
 }
\ No newline at end of file
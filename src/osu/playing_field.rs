@@ -1,33 +1,44 @@
 use std::borrow::BorrowMut;
+use std::f32::consts::PI;
 use std::process::id;
 use std::rc::Rc;
-use std::time::SystemTime;
+use std::time::Duration;
 
-use rand::prelude::*;
 use sdl2::event::Event;
 
-use crate::engine::api::colour::Colour;
-use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::collision::narrow_phase::test_collision;
+use crate::engine::api::collision::shapes::CollisionShape;
+use crate::engine::api::colour::{Colour, TRANSPARENT, WHITE};
+use crate::engine::api::countdown_timer::CountdownTimer;
+use crate::engine::api::drawable::{Drawable, EventResult, UpdateContext};
 use crate::engine::api::maths::quadrangle::Quadrangle;
 use crate::engine::api::maths::rectangle::Rectangle;
 use crate::engine::api::maths::shapes_common::Area;
 use crate::engine::api::maths::vertex::{ColoredVertexDataLayout, TexturedVertexDataLayout};
+use crate::engine::api::particles::{Curve, ParticleEmitter, ParticleEmitterConfig};
+use crate::engine::api::random::RandomGenerator;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::api::resource_manager::ResourceManager;
 use crate::engine::opengl_context::OpenglContext;
 use crate::osu::ring::{Ring, RING_RADIUS, RingStateKind};
 
-const SPAWN_INTERVAL_MILLIS: u128 = 500;
+const SPAWN_INTERVAL: Duration = Duration::from_millis(500);
+const SPARKS_PER_POP: u32 = 40;
+// when field is crowded ring is spawned on top of others after this many tries
+const MAX_SPAWN_ATTEMPTS: u32 = 10;
 
 pub struct PlayingField {
     background: Rectangle<TexturedVertexDataLayout>,
     rings: Vec<Ring>,
     fade_offs: Vec<Ring>,
     expires: Vec<Ring>,
+    // burst at every successfully popped ring
+    sparks: ParticleEmitter,
     total_score: i32,
     size: glam::Vec2,
     // todo: this should be part of rectangle,
-    spawn_time: SystemTime,
+    // driven by update delta time so that spawning does not depend on frame rate
+    spawn_timer: CountdownTimer,
 }
 
 impl PlayingField {
@@ -42,25 +53,45 @@ impl PlayingField {
             bg_sprite,
         );
 
+        let sparks_config = ParticleEmitterConfig::new_default(resource_manager.as_ref())
+            .with_lifetime(Duration::from_millis(300), Duration::from_millis(700))
+            .with_velocity_cone(0.0, PI, 2.0, 6.0)
+            .with_gravity(glam::vec2(0.0, -9.81))
+            .with_size_over_lifetime(Curve::linear(0.25, 0.05))
+            .with_start_colours(vec![Colour::new_f(1.0, 0.85, 0.3, 1.0), Colour::new_f(1.0, 0.5, 0.1, 1.0), WHITE])
+            .with_colour_over_lifetime(Curve::new(WHITE).with_key(0.6, WHITE).with_key(1.0, TRANSPARENT));
+
         PlayingField {
             background,
             rings: vec!(),
             fade_offs: vec!(),
             expires: vec!(),
+            sparks: ParticleEmitter::new(sparks_config, glam::vec3(0.0, 0.0, 0.0)),
             total_score: 0,
             size: size.clone(),
-            spawn_time: SystemTime::now(),
+            spawn_timer: CountdownTimer::new(SPAWN_INTERVAL),
         }
     }
 
-    pub fn calc_random_ring_position(pos: &glam::Vec3, size: &glam::Vec2) -> glam::Vec3 {
-        let mut rng = thread_rng();
-
+    pub fn calc_random_ring_position(pos: &glam::Vec3, size: &glam::Vec2, rng: &RandomGenerator) -> glam::Vec3 {
         let x = rng.gen_range((pos.x + RING_RADIUS)..(pos.x + size.x - RING_RADIUS));
         let y = rng.gen_range((pos.y + RING_RADIUS)..(pos.y + size.y - RING_RADIUS));
         glam::vec3(x, y, 0.0)
     }
 
+    // tries to find position where new ring does not overlap any alive ring
+    fn find_free_ring_position(&self, pos: &glam::Vec3, rng: &RandomGenerator) -> glam::Vec3 {
+        let mut ring_position = PlayingField::calc_random_ring_position(pos, &self.size, rng);
+        for _ in 1..MAX_SPAWN_ATTEMPTS {
+            let ring_shape = CollisionShape::new_circle(ring_position.truncate(), RING_RADIUS);
+            if self.rings.iter().all(|ring| test_collision(&ring_shape, &ring.get_hit_shape()).is_none()) {
+                break;
+            }
+            ring_position = PlayingField::calc_random_ring_position(pos, &self.size, rng);
+        }
+        ring_position
+    }
+
     pub fn get_total_score(&self) -> &i32 {
         &self.total_score
     }
@@ -72,18 +103,19 @@ impl Drawable for PlayingField {
         self.rings.iter_mut().for_each(|ring| ring.render(render_util));
         self.fade_offs.iter_mut().for_each(|ring| ring.render(render_util));
         self.expires.iter_mut().for_each(|ring| ring.render(render_util));
+        self.sparks.render(render_util);
     }
 
     fn update(&mut self, update_context: &UpdateContext) {
         // spawn new rings randomly
-        let now = SystemTime::now();
-        let difference = now.duration_since(self.spawn_time);
-        if difference.unwrap().as_millis() > SPAWN_INTERVAL_MILLIS {
-            self.spawn_time = now;
+        self.spawn_timer.advance(*update_context.get_delta_time());
+        if self.spawn_timer.is_finished() {
+            self.spawn_timer.restart();
 
             let pos = glam::vec3(self.background.get_pos().x, self.background.get_pos().y, self.background.get_pos().z);
 
-            let ring = Ring::new(&PlayingField::calc_random_ring_position(&pos, &self.size), update_context.get_engine_utilities().get_resource_manager());
+            let ring_position = self.find_free_ring_position(&pos, &update_context.get_engine_utilities().get_random());
+            let ring = Ring::new(&ring_position, update_context.get_engine_utilities().get_resource_manager());
             self.rings.push(ring);
         }
         // check if fade off effects are finished
@@ -94,6 +126,7 @@ impl Drawable for PlayingField {
         self.fade_offs.iter_mut().for_each(|ring| ring.update(update_context));
         self.rings.iter_mut().for_each(|ring| ring.update(update_context));
         self.expires.iter_mut().for_each(|ring| ring.update(update_context));
+        self.sparks.update(update_context);
 
         // check what is newly expired
         let mut expired = self.rings.drain_filter(|ring| !ring.is_alive()).collect::<Vec<_>>();
@@ -103,35 +136,49 @@ impl Drawable for PlayingField {
         self.expires.append(&mut expired);
     }
 
-    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) {
+    fn handle_event(&mut self, event: &Event, context: &OpenglContext, update_context: &UpdateContext) -> EventResult {
         match context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &update_context.get_camera_config()) {
             None => {}
             Some(world_mouse_position) => {
                 match event {
                     sdl2::event::Event::MouseButtonDown { .. } => {
-                        self.rings
-                            .drain_filter(|ring| ring.contains_point(&world_mouse_position))
-                            .collect::<Vec<_>>()
-                            .drain(0..)
-                            .for_each(|mut ring|
-                                match ring.handle_click() {
-                                    RingStateKind::ALIVE => { panic!("Unexpected alive state returned when popping ring ") }
-                                    RingStateKind::FADE_OFF => {
-                                        self.total_score += ring.get_score();
-                                        self.fade_offs.push(ring);
-                                    }
-                                    RingStateKind::EXPIRE => {
-                                        self.total_score -= ring.get_score();
-                                        self.expires.push(ring);
-                                    }
-                                });
-                        println!("TOTAL SCORE IS {:?}", self.total_score);
+                        // rings spawned later are drawn on top, only the topmost one under cursor is popped
+                        let clicked_ring = self.rings.iter().rposition(|ring| ring.contains_point(&world_mouse_position));
+                        if let Some(clicked_idx) = clicked_ring {
+                            let mut ring = self.rings.remove(clicked_idx);
+                            match ring.handle_click() {
+                                RingStateKind::ALIVE => { panic!("Unexpected alive state returned when popping ring ") }
+                                RingStateKind::FADE_OFF => {
+                                    self.total_score += ring.get_score();
+                                    self.sparks.set_position(*ring.get_position());
+                                    self.sparks.burst(SPARKS_PER_POP);
+                                    self.fade_offs.push(ring);
+                                }
+                                RingStateKind::EXPIRE => {
+                                    self.total_score -= ring.get_score();
+                                    self.expires.push(ring);
+                                }
+                            }
+                            return EventResult::CONSUMED;
+                        }
                     }
                     _ => {}
                 }
             }
         }
 
-        self.rings.iter_mut().for_each(|ring| ring.handle_event(event, context, update_context));
+        for ring in self.rings.iter_mut().rev() {
+            if ring.handle_event(event, context, update_context).is_consumed() {
+                return EventResult::CONSUMED;
+            }
+        }
+        EventResult::IGNORED
+    }
+
+    fn is_under_mouse(&self, context: &OpenglContext, update_context: &UpdateContext) -> bool {
+        match context.sdl_space_to_world_space_at_z0(update_context.get_sdl_mouse_position(), &update_context.get_camera_config()) {
+            None => false,
+            Some(world_mouse_position) => self.background.contains_point(&(world_mouse_position.x, world_mouse_position.y))
+        }
     }
 }
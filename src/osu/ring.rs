@@ -5,6 +5,9 @@ use std::time::{Duration, Instant};
 use sdl2::event::Event;
 use sdl2::timer::Timer;
 
+use crate::engine::api::animation::easing::Easing;
+use crate::engine::api::animation::tween::{Animator, Parallel, Tween};
+use crate::engine::api::collision::shapes::CollisionShape;
 use crate::engine::api::colour::{BLUE, Colour, GREEN, RED, TRANSPARENT, WHITE};
 use crate::engine::api::countdown_timer::CountdownTimer;
 use crate::engine::api::drawable::{Drawable, UpdateContext};
@@ -12,6 +15,7 @@ use crate::engine::api::maths::circle::Circle;
 use crate::engine::api::maths::quadrangle::Quadrangle;
 use crate::engine::api::maths::rectangle::Rectangle;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::maths::transform::Transform;
 use crate::engine::api::maths::util::{lerp_v3, lerp_v4};
 use crate::engine::api::maths::vertex::TexturedVertexDataLayout;
 use crate::engine::api::render_util::RenderUtil;
@@ -29,7 +33,12 @@ const FADE_OFF_TIMER_DURATION: Duration = Duration::from_millis(250);
 const EXPIRE_TIMER_DURATION: Duration = Duration::from_millis(400);
 const CLICK_WINDOW_LENGTH: Duration = Duration::from_millis(500);
 
+const RING_TEXTURE: &str = "osu/textures/ring.png";
+const RING_BORDER_TEXTURE: &str = "osu/textures/rings_border.png";
+
 pub struct Ring {
+    // ring shapes are positioned relative to the middle of a ring
+    transform: Transform,
     hit_box: Circle,
     ring_rectangle: Rectangle<TexturedVertexDataLayout>,
     ring_border_rectangle: Rectangle<TexturedVertexDataLayout>,
@@ -41,10 +50,12 @@ impl Ring {
         let ring_shader_material = resource_manager.fetch_shader_material("osu/shaders/ring");
         let clr_shader_material = resource_manager.fetch_shader_material("osu/shaders/colour");
         let ring_border_shader_material = resource_manager.fetch_shader_material("osu/shaders/ring_border");
-        let ring_sprite = resource_manager.fetch_sprite("osu/textures/ring.png");
-        let ring_border_sprite = resource_manager.fetch_sprite("osu/textures/rings_border.png");
+        // both ring textures share one atlas page so that rings do not need texture switches
+        let rings_atlas = resource_manager.fetch_atlas("osu/rings_atlas", &[RING_TEXTURE, RING_BORDER_TEXTURE]);
+        let ring_sprite = rings_atlas.get_sprite(RING_TEXTURE);
+        let ring_border_sprite = rings_atlas.get_sprite(RING_BORDER_TEXTURE);
 
-        let tx_position = glam::vec3(position.x - RING_RADIUS, position.y - RING_RADIUS, position.z);
+        let tx_position = glam::vec3(-RING_RADIUS, -RING_RADIUS, 0.0);
         let mut ring_rectangle = Rectangle::new_textured(
             &tx_position,
             &glam::vec2(RING_RADIUS * 2.0, RING_RADIUS * 2.0),
@@ -52,6 +63,7 @@ impl Ring {
             ring_sprite.clone(),
         );
         ring_rectangle.set_material_variable("color", UniformKind::VEC_4 { value: BLUE.into() });
+        ring_rectangle.set_material_variable("fadeOffAlpha", UniformKind::FLOAT { value: 1.0 });
 
         let mut ring_border_rectangle = Rectangle::new_textured(
             &tx_position,
@@ -63,22 +75,32 @@ impl Ring {
         ring_border_rectangle.set_scale(glam::vec3(RING_BORDER_SCALE, RING_BORDER_SCALE, 0.0));
 
         let hit_box = Circle::new_colored(
-            position,
+            &glam::vec3(0.0, 0.0, 0.0),
             glam::vec4(0.5, 0.5, 0.5, 1.0).into(),
             RING_RADIUS,
             clr_shader_material,
         );
 
         Ring {
+            transform: Transform::from_translation(position.clone()),
             ring_rectangle,
             ring_border_rectangle,
             hit_box,
-            state: RingState::new_alive(CountdownTimer::new(ALIVE_TIMER_DURATION)),
+            state: RingState::new_alive(),
         }
     }
 
     pub fn contains_point(&self, position: &glam::Vec3) -> bool {
-        self.hit_box.contains_point(&(position.x, position.y))
+        let local_position = self.transform.to_local_point(position);
+        self.hit_box.contains_point(&(local_position.x, local_position.y))
+    }
+
+    pub fn get_position(&self) -> &glam::Vec3 {
+        self.transform.get_translation()
+    }
+
+    pub fn get_hit_shape(&self) -> CollisionShape {
+        CollisionShape::new_circle(self.transform.get_translation().truncate(), RING_RADIUS)
     }
 
     pub fn get_score(&self) -> i32 {
@@ -88,16 +110,21 @@ impl Ring {
     pub fn handle_click(&mut self) -> RingStateKind {
         return match self.state.timer.get_duration_left() <= CLICK_WINDOW_LENGTH {
             true => {
-                self.state = RingState::new_fade_off(CountdownTimer::new(FADE_OFF_TIMER_DURATION));
+                self.state = RingState::new_fade_off();
                 RingStateKind::FADE_OFF
             }
             false => {
-                self.state = RingState::new_expire(CountdownTimer::new(EXPIRE_TIMER_DURATION));
+                self.start_expire();
                 RingStateKind::EXPIRE
             }
         }
     }
 
+    fn start_expire(&mut self) {
+        self.ring_rectangle.set_material_variable("color", UniformKind::VEC_4 { value: RED.into() });
+        self.state = RingState::new_expire();
+    }
+
     // imagine implementing signals and removing this ugly pull model
     pub fn is_fully_faded(&self) -> bool {
         self.state.kind == RingStateKind::FADE_OFF && self.state.timer.is_finished()
@@ -114,82 +141,87 @@ impl Ring {
 
 impl Drawable for Ring {
     fn render(&mut self, render_util: &RenderUtil) {
+        let ring_render_util = render_util.with_parent_transform(&self.transform);
         if self.state.kind == RingStateKind::ALIVE {
-            self.ring_border_rectangle.render(render_util);
+            self.ring_border_rectangle.render(&ring_render_util);
         }
-        self.ring_rectangle.render(render_util);
+        self.ring_rectangle.render(&ring_render_util);
     }
 
     fn update(&mut self, update_context: &UpdateContext) {
         self.state.timer.advance(*update_context.get_delta_time());
+        if self.state.kind == RingStateKind::ALIVE && self.state.timer.is_finished() {
+            self.start_expire();
+        }
 
-        match self.state.kind {
-            RingStateKind::ALIVE => {
-                match self.state.timer.is_finished() {
-                    true => {
-                        self.state = RingState::new_expire(CountdownTimer::new(EXPIRE_TIMER_DURATION));
-                    }
-                    false => {
-                        self.ring_rectangle.set_material_variable("fadeOffAlpha", UniformKind::FLOAT { value: 1.0 });
-
-                        let border_scale = 1.0 + (RING_BORDER_SCALE - 1.0) * (1.0 - self.state.timer.get_percent_complete());
-                        self.ring_border_rectangle.set_scale(glam::vec3(border_scale, border_scale, 0.0));
-                    }
-                }
-            }
-            RingStateKind::FADE_OFF => {
-                match self.state.timer.is_finished() {
-                    true => {}
-                    false => {
-                        let scale_change = MAX_RING_GROWTH * self.state.timer.get_percent_complete();
-                        self.ring_rectangle.set_scale(glam::vec3(1.0 + scale_change, 1.0 + scale_change, 1.0));
-                        self.ring_rectangle.set_material_variable("fadeOffAlpha", UniformKind::FLOAT { value: 1.0 - self.state.timer.get_percent_complete() });
-                    }
-                }
-            }
-            RingStateKind::EXPIRE => {
-                match self.state.timer.is_finished() {
-                    true => {}
-                    false => {
-                        self.ring_rectangle.set_material_variable("color", UniformKind::VEC_4 { value: RED.into() });
-                        let scale_change = MAX_RING_COLLAPSE * self.state.timer.get_percent_complete();
-                        self.ring_rectangle.set_scale(glam::vec3(1.0 - scale_change, 1.0 - scale_change, 1.0));
-                        self.ring_rectangle.set_material_variable("fadeOffAlpha", UniformKind::FLOAT { value: 1.0 - self.state.timer.get_percent_complete() })
-                    }
-                }
-            }
+        let delta_time = *update_context.get_delta_time();
+        if let Some(animator) = self.state.ring_animator.as_mut() {
+            animator.update(&mut self.ring_rectangle, delta_time);
+        }
+        if let Some(animator) = self.state.border_animator.as_mut() {
+            animator.update(&mut self.ring_border_rectangle, delta_time);
         }
     }
 }
 
+type RingAnimator = Animator<Rectangle<TexturedVertexDataLayout>>;
+
 struct RingState {
     timer: CountdownTimer,
     kind: RingStateKind,
+    ring_animator: Option<RingAnimator>,
+    border_animator: Option<RingAnimator>,
 }
 
 impl RingState {
-    fn new_alive(timer: CountdownTimer) -> RingState {
+    // border shrinks onto the ring, ring should be clicked when they meet
+    fn new_alive() -> RingState {
+        let shrink_border = Tween::new(RING_BORDER_SCALE, 1.0, ALIVE_TIMER_DURATION, Easing::LINEAR, set_flat_scale);
         RingState {
             kind: RingStateKind::ALIVE,
-            timer,
+            timer: CountdownTimer::new(ALIVE_TIMER_DURATION),
+            ring_animator: None,
+            border_animator: Some(Animator::new(shrink_border)),
         }
     }
 
-    fn new_fade_off(timer: CountdownTimer) -> RingState {
+    fn new_fade_off() -> RingState {
+        let fade_off = Parallel::new()
+            .with(Tween::new(1.0, 1.0 + MAX_RING_GROWTH, FADE_OFF_TIMER_DURATION, Easing::QUAD_OUT, set_scale))
+            .with(Tween::new(1.0, 0.0, FADE_OFF_TIMER_DURATION, Easing::LINEAR, set_fade_off_alpha));
         RingState {
             kind: RingStateKind::FADE_OFF,
-            timer,
+            timer: CountdownTimer::new(FADE_OFF_TIMER_DURATION),
+            ring_animator: Some(Animator::new(fade_off)),
+            border_animator: None,
         }
     }
 
-    fn new_expire(timer: CountdownTimer) -> RingState {
+    fn new_expire() -> RingState {
+        let collapse = Parallel::new()
+            .with(Tween::new(1.0, 1.0 - MAX_RING_COLLAPSE, EXPIRE_TIMER_DURATION, Easing::BACK_IN, set_scale))
+            .with(Tween::new(1.0, 0.0, EXPIRE_TIMER_DURATION, Easing::LINEAR, set_fade_off_alpha));
         RingState {
             kind: RingStateKind::EXPIRE,
-            timer,
+            timer: CountdownTimer::new(EXPIRE_TIMER_DURATION),
+            ring_animator: Some(Animator::new(collapse)),
+            border_animator: None,
         }
     }
 }
 
+fn set_scale(rectangle: &mut Rectangle<TexturedVertexDataLayout>, scale: f32) {
+    rectangle.set_scale(glam::vec3(scale, scale, 1.0));
+}
+
+fn set_flat_scale(rectangle: &mut Rectangle<TexturedVertexDataLayout>, scale: f32) {
+    rectangle.set_scale(glam::vec3(scale, scale, 0.0));
+}
+
+fn set_fade_off_alpha(rectangle: &mut Rectangle<TexturedVertexDataLayout>, alpha: f32) {
+    rectangle.set_material_variable("fadeOffAlpha", UniformKind::FLOAT { value: alpha });
+}
+
 #[derive(PartialEq)]
 pub enum RingStateKind {
     ALIVE,
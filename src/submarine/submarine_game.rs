@@ -3,18 +3,31 @@ use std::io;
 use std::ops::Mul;
 use std::rc::Rc;
 
-use crate::engine::api::colour::WHITE;
+use crate::engine::api::colour::{Colour, WHITE};
 use crate::engine::api::drawable::{Drawable, UpdateContext};
+use crate::engine::api::ecs::components::ColliderShape;
 use crate::engine::api::engine_utilities::EngineUtilities;
 use crate::engine::api::maths::point::Point;
 use crate::engine::api::maths::rectangle::Rectangle;
 use crate::engine::api::maths::shapes_common::Area;
+use crate::engine::api::physics::physics_world::{BodyHandle, PhysicsWorld};
+use crate::engine::api::physics::rigid_body::RigidBody;
 use crate::engine::api::render_util::RenderUtil;
 use crate::engine::opengl_context::OpenglContext;
 use crate::vertex::{ColoredVertexDataLayout, TexturedVertexDataLayout};
 
+const SUBMARINE_SIZE: glam::Vec2 = glam::const_vec2!([1.0, 1.0]);
+const SUBMARINE_MASS: f32 = 2.0;
+// water slows submarine down, with this drag one `forward` command moves it by about the same distance as before physics
+const WATER_DRAG: f32 = 3.0;
+const SEABED_ORIGIN: glam::Vec2 = glam::const_vec2!([-12.0, 7.8]);
+const SEABED_TILE_SIZE: glam::Vec2 = glam::const_vec2!([1.0, 0.5]);
+const SEABED_COLOUR: Colour = Colour::new_f(0.35, 0.3, 0.2, 1.0);
+
 pub struct SubmarineGame {
     submarine: Submarine,
+    physics_world: PhysicsWorld,
+    seabed: Vec<Rectangle<ColoredVertexDataLayout>>,
     engine_utilities: Rc<EngineUtilities>,
     lines: io::Lines<io::BufReader<File>>
 }
@@ -30,19 +43,34 @@ impl SubmarineGame {
             submarine_tx,
         );
 
-        let submarine = Submarine::new(submarine_sprite);
+        let mut physics_world = PhysicsWorld::new(glam::vec2(0.0, 0.0));
+        let seabed_tiles: Vec<(i32, i32)> = (0..12).map(|column| (column, 0)).collect();
+        physics_world.add_static_tiles(SEABED_ORIGIN, SEABED_TILE_SIZE, &seabed_tiles);
+        let seabed_material = engine_utilities.get_resource_manager().fetch_shader_material("submarine/shaders/triangle");
+        let seabed = physics_world.get_static_shapes().iter()
+            .map(|tile| {
+                let bounds = tile.get_bounds();
+                Rectangle::new_colored(&bounds.get_min().extend(0.0), &SEABED_TILE_SIZE, seabed_material.clone(), SEABED_COLOUR)
+            })
+            .collect();
+
+        let submarine = Submarine::new(submarine_sprite, &mut physics_world);
         let lines = engine_utilities.get_resource_manager().read_file_lines("submarine/commands.txt");
-        SubmarineGame { submarine, engine_utilities, lines}
+        SubmarineGame { submarine, physics_world, seabed, engine_utilities, lines }
     }
 }
 
 
 impl Drawable for SubmarineGame {
     fn render(&mut self, render_util: &RenderUtil) {
+        self.seabed.iter_mut().for_each(|tile| tile.render(render_util));
         self.submarine.render(render_util)
     }
 
     fn update(&mut self, update_context: &UpdateContext) {
+        self.physics_world.step(update_context.get_delta_time());
+        self.submarine.sync_with_body(&self.physics_world);
+
         if let Some(line) = self.lines.next() {
             let unwrapped = line.unwrap();
             let split = unwrapped.split_whitespace();
@@ -51,7 +79,7 @@ impl Drawable for SubmarineGame {
             let offset = parts[1].parse::<i32>().unwrap();
 
             return match parts[0] {
-                "forward" => self.submarine.forward(offset),
+                "forward" => self.submarine.forward(offset, &mut self.physics_world),
                 "down" => self.submarine.aim_down(offset),
                 "up" => self.submarine.aim_up(offset),
                 unknown_command => panic!("Cannot parse submarine movement command {}", unknown_command)
@@ -63,25 +91,49 @@ impl Drawable for SubmarineGame {
 
 struct Submarine {
     submarine_sprite: Rectangle<TexturedVertexDataLayout>,
+    body: BodyHandle,
     aim: i32,
 }
 
 impl Submarine {
-    pub fn new(submarine_sprite: Rectangle<TexturedVertexDataLayout>) -> Submarine {
-        Submarine { submarine_sprite, aim: 0 }
+    pub fn new(submarine_sprite: Rectangle<TexturedVertexDataLayout>, physics_world: &mut PhysicsWorld) -> Submarine {
+        let center = submarine_sprite.get_pos().truncate() + SUBMARINE_SIZE / 2.0;
+        let mut body = RigidBody::new(center, ColliderShape::RECTANGLE { size: SUBMARINE_SIZE }, SUBMARINE_MASS);
+        body.set_linear_damping(WATER_DRAG);
+        let body = physics_world.add_body(body);
+        Submarine { submarine_sprite, body, aim: 0 }
     }
 
     fn aim_up(&mut self, aim: i32) {
         self.aim = self.aim + aim;
+        self.tilt_to_aim();
     }
 
     fn aim_down(&mut self, aim: i32) {
       self.aim = self.aim - aim;
+      self.tilt_to_aim();
+    }
+
+    // engine gives a push, water drag stops submarine afterwards
+    fn forward(&mut self, offset: i32, physics_world: &mut PhysicsWorld) {
+        let body = physics_world.get_body_mut(self.body);
+        let velocity_change = self.get_move_direction().truncate().mul(offset as f32 * WATER_DRAG);
+        body.apply_impulse(velocity_change * body.get_mass());
+    }
+
+    fn sync_with_body(&mut self, physics_world: &PhysicsWorld) {
+        let center = *physics_world.get_body(self.body).get_position();
+        self.submarine_sprite.move_to((center - SUBMARINE_SIZE / 2.0).extend(0.0));
+    }
+
+    fn get_move_direction(&self) -> glam::Vec3 {
+        glam::vec3(1.0, self.aim as f32, 0.0).mul(glam::vec3(0.001, 0.000001, 1.0))
     }
 
-    fn forward(&mut self, offset: i32) {
-        let move_offset = glam::vec3(offset as f32, (offset * self.aim) as f32, 0.0).mul(glam::vec3(0.001, 0.000001,1.0 ));
-        self.submarine_sprite.move_by(move_offset);
+    // submarine sprite points where it is going to move
+    fn tilt_to_aim(&mut self) {
+        let direction = self.get_move_direction();
+        self.submarine_sprite.set_rotation_angle(direction.y.atan2(direction.x));
     }
 }
 